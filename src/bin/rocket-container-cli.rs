@@ -0,0 +1,438 @@
+//! Command-line client which queries Rocket Container's service layer directly, without running
+//! the server.
+//!
+//! The client reads the same configuration as the server (`Rocket.toml` and `ROCKET_` prefixed
+//! environment variables), so it aggregates containers exactly as the server does.
+//!
+//! # Commands
+//!
+//! - `audit`: Audit the catalogs of Rocket Container's dependencies for inconsistencies. The
+//!   `json` format outputs the whole report, `table` and `csv` only the issues found.
+//! - `containers list`: List every container.
+//! - `containers get <id>`: Get a container by ID.
+//! - `videos get <id>`: Get a video by ID.
+//! - `videos assets <id> [--type AD|IMAGE]`: List the assets of a video, optionally only those of
+//!   a type.
+//!
+//! # Flags
+//!
+//! - `--format json|table|csv`: Output format. Defaults to `table`.
+//! - `--mirror-only`: Read data from the local mirror only, without calling Rocket Container's
+//!   dependencies.
+
+use std::{env, process, str::FromStr};
+
+use serde::Serialize;
+
+use rocket_container::{
+    config::Config,
+    service::{
+        audit::{AuditIssue, AuditReport},
+        container::{Container, ContainerFilter, ContainerService, ContainerSorting},
+        report::csv_field,
+        video::{Video, VideoAsset, VideoFilter, VideoSorting},
+    },
+    types::{self, option_to_string, AssetType, ContainerId, Timestamp, VideoId},
+};
+
+/// Usage of the command line.
+const USAGE: &str = "Usage: rocket-container-cli [--format json|table|csv] [--mirror-only] \
+    (audit | containers list | containers get <id> | videos get <id> | \
+    videos assets <id> [--type <type>])";
+
+/* ****************************************** Command ******************************************* */
+
+/// Query to run against the service layer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Command {
+    /// `audit`.
+    Audit,
+    /// `containers list`.
+    ListContainers,
+    /// `containers get <id>`.
+    GetContainer(ContainerId),
+    /// `videos get <id>`.
+    GetVideo(VideoId),
+    /// `videos assets <id> [--type <type>]`.
+    GetVideoAssets(VideoId, Option<AssetType>),
+}
+
+/* ******************************************* Format ******************************************* */
+
+/// Output format.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+enum Format {
+    /// Pretty printed JSON.
+    Json,
+    /// Aligned columns.
+    #[default]
+    Table,
+    /// CSV with a header row.
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format {}", s)),
+        }
+    }
+}
+
+/* ******************************************** Args ******************************************** */
+
+/// Parsed command line arguments.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Args {
+    /// Query to run.
+    command: Command,
+    /// Output format.
+    format: Format,
+    /// Whether to read from the local mirror only.
+    mirror_only: bool,
+}
+
+impl Args {
+    /// Parse command line arguments, without the program name.
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut format: Format = Format::default();
+        let mut mirror_only: bool = false;
+        let mut asset_type: Option<AssetType> = None;
+        let mut positional: Vec<&str> = Vec::new();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => format = value(args.next(), arg)?.parse()?,
+                "--mirror-only" => mirror_only = true,
+                "--type" => {
+                    asset_type = Some(
+                        value(args.next(), arg)?
+                            .parse()
+                            .map_err(|err: types::Error| err.message)?,
+                    )
+                }
+                flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+                arg => positional.push(arg),
+            }
+        }
+
+        let command: Command = match (positional.as_slice(), asset_type) {
+            (["audit"], None) => Command::Audit,
+            (["containers", "list"], None) => Command::ListContainers,
+            (["containers", "get", id], None) => Command::GetContainer(parse_id(id)?),
+            (["videos", "get", id], None) => Command::GetVideo(parse_id(id)?),
+            (["videos", "assets", id], asset_type) => {
+                Command::GetVideoAssets(parse_id(id)?, asset_type)
+            }
+            _ => return Err(USAGE.to_string()),
+        };
+
+        Ok(Args {
+            command,
+            format,
+            mirror_only,
+        })
+    }
+}
+
+/* ****************************************** Tabular ******************************************* */
+
+/// Items which can be output as table or CSV rows.
+trait Tabular {
+    /// Column names.
+    fn header() -> Vec<&'static str>;
+
+    /// Column values.
+    fn row(&self) -> Vec<String>;
+}
+
+impl Tabular for AuditIssue {
+    fn header() -> Vec<&'static str> {
+        vec!["kind", "issue"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let kind: String = serde_json::to_value(self)
+            .ok()
+            .and_then(|value| value["kind"].as_str().map(str::to_string))
+            .unwrap_or_default();
+
+        vec![kind, self.to_string()]
+    }
+}
+
+impl Tabular for Container {
+    fn header() -> Vec<&'static str> {
+        vec!["id", "title", "status", "ads", "images", "videos"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().to_string(),
+            self.title().to_string(),
+            self.status().to_string(),
+            self.ads().len().to_string(),
+            self.images().len().to_string(),
+            self.videos().len().to_string(),
+        ]
+    }
+}
+
+impl Tabular for Video {
+    fn header() -> Vec<&'static str> {
+        vec!["id", "title", "type", "expiresAt", "assets"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id().to_string(),
+            self.title().to_string(),
+            self.r#type().to_string(),
+            option_to_string(&self.expires_at().map(Timestamp)),
+            self.assets().len().to_string(),
+        ]
+    }
+}
+
+impl Tabular for VideoAsset {
+    fn header() -> Vec<&'static str> {
+        vec!["assetId", "assetType"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.asset_id().to_string(), self.asset_type().to_string()]
+    }
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Get the value of a flag.
+fn value<'a>(value: Option<&'a String>, flag: &str) -> Result<&'a String, String> {
+    value.ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+/// Parse an ID argument.
+fn parse_id<T: FromStr<Err = types::Error>>(id: &str) -> Result<T, String> {
+    id.parse().map_err(|err: types::Error| err.message)
+}
+
+/// Render items in a format.
+fn render<T: Serialize + Tabular>(items: &[T], format: Format) -> Result<String, String> {
+    let rows: Vec<Vec<String>> = items.iter().map(Tabular::row).collect();
+    let header: Vec<String> = T::header().into_iter().map(str::to_string).collect();
+
+    match format {
+        Format::Json => serde_json::to_string_pretty(items).map_err(|err| err.to_string()),
+        Format::Csv => Ok(std::iter::once(&header)
+            .chain(rows.iter())
+            .map(|row| {
+                row.iter()
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")),
+        Format::Table => {
+            let widths: Vec<usize> = (0..header.len())
+                .map(|column| {
+                    std::iter::once(&header)
+                        .chain(rows.iter())
+                        .map(|row| row[column].chars().count())
+                        .max()
+                        .unwrap_or_default()
+                })
+                .collect();
+            let line = |row: &Vec<String>| -> String {
+                row.iter()
+                    .zip(widths.iter())
+                    .map(|(field, width)| format!("{:<width$}", field, width = width))
+                    .collect::<Vec<String>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            };
+            let separator: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+
+            Ok([line(&header), line(&separator)]
+                .into_iter()
+                .chain(rows.iter().map(line))
+                .collect::<Vec<String>>()
+                .join("\n"))
+        }
+    }
+}
+
+/// Run a command against the service layer.
+async fn run(service: &ContainerService, args: &Args) -> types::Result<String> {
+    let output: Result<String, String> = match &args.command {
+        Command::Audit => {
+            let report: AuditReport = service.audit().await?;
+
+            match args.format {
+                Format::Json => {
+                    serde_json::to_string_pretty(&report).map_err(|err| err.to_string())
+                }
+                format => render(report.issues(), format),
+            }
+        }
+        Command::ListContainers => {
+            let containers: Vec<Container> = service
+                .list_containers(&ContainerFilter::default(), &ContainerSorting::default())
+                .await?;
+
+            render(&containers, args.format)
+        }
+        Command::GetContainer(container_id) => {
+            let container: Container = service
+                .get_container(
+                    *container_id,
+                    &VideoFilter::default(),
+                    &VideoSorting::default(),
+                )
+                .await?;
+
+            render(&[container], args.format)
+        }
+        Command::GetVideo(video_id) => {
+            let video: Video = service.get_video(*video_id, false).await?;
+
+            render(&[video], args.format)
+        }
+        Command::GetVideoAssets(video_id, asset_type) => {
+            let assets: Vec<VideoAsset> = service
+                .get_video_assets(*video_id, asset_type.clone())
+                .await?;
+
+            render(&assets, args.format)
+        }
+    };
+
+    output.map_err(|message| types::Error {
+        kind: types::ErrorKind::Permanent,
+        message,
+    })
+}
+
+/// Main function for the command-line client.
+#[tokio::main]
+async fn main() {
+    let args: Args = match Args::parse(&env::args().skip(1).collect::<Vec<String>>()) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+    let config: Config = match rocket::Config::figment().extract() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("Invalid configuration: {}", err);
+            process::exit(1);
+        }
+    };
+    let service: ContainerService = match config
+        .mirror
+        .mirror(args.mirror_only)
+        .and_then(|mirror| config.container_service(mirror))
+    {
+        Ok(service) => service,
+        Err(err) => {
+            eprintln!("{}", err.message);
+            process::exit(1);
+        }
+    };
+
+    match run(&service, &args).await {
+        Ok(output) => println!("{}", output),
+        Err(err) => {
+            eprintln!("{}", err.message);
+            process::exit(1);
+        }
+    }
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use rocket_container::{
+        service::container::Container,
+        types::{AssetType, ContainerId, VideoId},
+    };
+
+    use super::{render, Args, Command, Format};
+
+    fn args(args: &str) -> Result<Args, String> {
+        Args::parse(
+            &args
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<String>>(),
+        )
+    }
+
+    #[test]
+    fn parse_video_assets() {
+        // Given
+        let line: &str = "--format csv videos assets 7 --type ad";
+
+        // When
+        let actual: Result<Args, String> = args(line);
+
+        // Then
+        assert_eq!(
+            actual,
+            Ok(Args {
+                command: Command::GetVideoAssets(VideoId(7), Some(AssetType::Ad)),
+                format: Format::Csv,
+                mirror_only: false,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_invalid_args() {
+        // Given
+        let lines: [&str; 5] = [
+            "containers",
+            "audit --type AD",
+            "containers get zero",
+            "--format xml containers list",
+            "containers list --type AD",
+        ];
+
+        // When
+        let actual: Vec<bool> = lines.iter().map(|line| args(line).is_err()).collect();
+
+        // Then
+        assert_eq!(actual, vec![true, true, true, true, true]);
+    }
+
+    #[test]
+    fn render_table() {
+        // Given
+        let containers: Vec<Container> = vec![Container::new(
+            Vec::new(),
+            ContainerId(12),
+            Vec::new(),
+            "container-12_videos".to_string(),
+            Vec::new(),
+        )];
+        let expected: &str = "id  title                status  ads  images  videos\n\
+            --  -------------------  ------  ---  ------  ------\n\
+            12  container-12_videos  empty   0    0       0";
+
+        // When
+        let actual: Result<String, String> = render(&containers, Format::Table);
+
+        // Then
+        assert_eq!(actual, Ok(expected.to_string()));
+    }
+}
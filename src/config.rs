@@ -0,0 +1,571 @@
+//! Rocket Container configuration.
+//!
+//! Configuration is read from the same sources as Rocket's own configuration (`Rocket.toml` and
+//! `ROCKET_` prefixed environment variables) and managed as [`rocket::State`] so controllers can
+//! access it.
+//!
+//! # Examples
+//!
+//! ```toml
+//! [default]
+//! unknown_types = "drop"
+//!
+//! [default.audit]
+//! concurrency = 8
+//!
+//! [[default.auth.keys]]
+//! name = "rocket-stream"
+//! sha256 = "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+//! scopes = ["catalog:read"]
+//!
+//! [default.compression]
+//! encodings = ["br", "gzip"]
+//! min_size = 2048
+//! level = 4
+//!
+//! [default.cors]
+//! allowed_origins = ["https://player.example.com"]
+//! max_age_secs = 3600
+//!
+//! [default.mirror]
+//! path = "mirror.sqlite3"
+//! max_age_secs = 604800
+//!
+//! [default.pagination]
+//! default_page_size = 25
+//! max_page_size = 100
+//!
+//! [default.limits]
+//! snapshot = "128 MiB"
+//!
+//! [default.metadata]
+//! path = "metadata.json"
+//!
+//! [default.rate_limit]
+//! default = { burst = 60, per_minute = 120 }
+//!
+//! [default.rate_limit.routes]
+//! "/containers" = { burst = 5, per_minute = 10 }
+//!
+//! [default.snapshot]
+//! max_age_secs = 300
+//!
+//! [default.titles]
+//! template = "{id} – {firstVideoTitle}"
+//! overrides = "titles.toml"
+//! ```
+
+use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controller::{
+        auth::{ApiKey, ApiKeys},
+        compression::Encoding,
+        rate_limit::{Limit, RateLimiter},
+    },
+    repository::{
+        advertisement::AdvertisementRepository, client::Client, image::ImageRepository,
+        video::VideoRepository,
+    },
+    service::{
+        advertisement::AdvertisementService,
+        container::ContainerService,
+        image::ImageService,
+        metadata::MetadataStore,
+        mirror::Mirror,
+        title::{ContainerTitler, DefaultTitler, OverrideTitler, TemplateTitler},
+        video::VideoService,
+    },
+    types::{Error, ErrorKind, Result},
+};
+
+/* ******************************************* Config ******************************************* */
+
+/// Rocket Container configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket::fairing::AdHoc;
+/// use rocket_container::config::Config;
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build().attach(AdHoc::config::<Config>())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Config {
+    /// Catalog audit configuration.
+    pub audit: AuditConfig,
+    /// API key authentication configuration.
+    pub auth: AuthConfig,
+    /// Response compression configuration.
+    pub compression: CompressionConfig,
+    /// Cross-origin resource sharing configuration.
+    pub cors: CorsConfig,
+    /// Editorial container metadata configuration.
+    pub metadata: MetadataConfig,
+    /// Local mirror of upstream catalog data configuration.
+    pub mirror: MirrorConfig,
+    /// Pagination configuration for collection routes.
+    pub pagination: PaginationConfig,
+    /// Per-client rate limit configuration.
+    pub rate_limit: RateLimitConfig,
+    /// Container snapshot configuration.
+    pub snapshot: SnapshotConfig,
+    /// Container title configuration.
+    pub titles: TitleConfig,
+    /// What to do with videos and asset references of types Rocket Container doesn't know.
+    pub unknown_types: UnknownTypePolicy,
+}
+
+impl Config {
+    /// Build a [`ContainerService`] which fetches through `mirror`, as configured.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(service)` if the configuration is valid.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if the title template or overrides, or the metadata
+    ///   file, are invalid.
+    pub fn container_service(&self, mirror: Arc<Mirror>) -> Result<ContainerService> {
+        let client: Arc<Client> = Arc::new(Client::default());
+        let advertisement_service: AdvertisementService =
+            AdvertisementService::new(AdvertisementRepository::new(client.clone()))
+                .mirror(mirror.clone());
+        let image_service: ImageService =
+            ImageService::new(ImageRepository::new(client.clone())).mirror(mirror.clone());
+        let video_service: VideoService = VideoService::new(VideoRepository::new(client))
+            .mirror(mirror)
+            .unknown_type_policy(self.unknown_types);
+
+        Ok(
+            ContainerService::new(advertisement_service, image_service, video_service)
+                .snapshot_max_age(Duration::from_secs(self.snapshot.max_age_secs))
+                .audit_concurrency(self.audit.concurrency)
+                .titler(self.titles.titler()?)
+                .metadata_store(self.metadata.store()?),
+        )
+    }
+}
+
+/* **************************************** AuditConfig ***************************************** */
+
+/// Catalog audit configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::AuditConfig;
+///
+/// let config: AuditConfig = AuditConfig::default();
+///
+/// assert_eq!(config.concurrency, 16);
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct AuditConfig {
+    /// Number of videos an audit fetches from Rocket Video at once.
+    pub concurrency: usize,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        AuditConfig { concurrency: 16 }
+    }
+}
+
+/* ***************************************** AuthConfig ***************************************** */
+
+/// API key authentication configuration.
+///
+/// Keys are configured by hash, see [`auth`][1]. While no keys are configured, catalog routes are
+/// public and admin routes are forbidden, unless authentication is disabled.
+///
+/// [1]: crate::controller::auth
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::AuthConfig;
+///
+/// let config: AuthConfig = AuthConfig::default();
+///
+/// assert!(!config.api_keys().is_enabled());
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// Whether to disable authentication, making every route public, admin routes included.
+    pub disabled: bool,
+    /// API keys which may call Rocket Container.
+    pub keys: Vec<ApiKey>,
+}
+
+impl AuthConfig {
+    /// Build the configured API keys, to be managed as [`rocket::State`].
+    pub fn api_keys(&self) -> ApiKeys {
+        ApiKeys::new(self.keys.clone()).disabled(self.disabled)
+    }
+}
+
+/* ************************************* CompressionConfig ************************************** */
+
+/// Response compression configuration, for the [`compression`][1] fairing.
+///
+/// Compression is disabled while no encodings are configured.
+///
+/// [1]: crate::controller::compression::compression
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::CompressionConfig;
+///
+/// let config: CompressionConfig = CompressionConfig::default();
+///
+/// assert_eq!(config.min_size, 1024);
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct CompressionConfig {
+    /// Encodings responses can be compressed with, in order of preference when a client accepts
+    /// several equally.
+    pub encodings: Vec<Encoding>,
+    /// Minimum size in bytes of a response body to compress.
+    pub min_size: usize,
+    /// Compression level, or each encoding's default level if this isn't set. See
+    /// [`Encoding::compress`].
+    pub level: Option<u32>,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        CompressionConfig {
+            encodings: vec![Encoding::Brotli, Encoding::Zstd, Encoding::Gzip],
+            min_size: 1024,
+            level: None,
+        }
+    }
+}
+
+/* ***************************************** CorsConfig ***************************************** */
+
+/// Cross-origin resource sharing (CORS) configuration, for the [`cors`][1] fairing.
+///
+/// CORS is disabled while no origins are allowed.
+///
+/// [1]: crate::controller::cors::cors
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::CorsConfig;
+///
+/// let config: CorsConfig = CorsConfig::default();
+///
+/// assert!(config.allowed_origins.is_empty());
+/// assert!(!config.allow_credentials);
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct CorsConfig {
+    /// Origins which may call Rocket Container, such as `https://player.example.com`, or `*` for
+    /// every origin.
+    pub allowed_origins: Vec<String>,
+    /// Methods allowed in cross-origin requests.
+    pub allowed_methods: Vec<String>,
+    /// Request headers allowed in cross-origin requests.
+    pub allowed_headers: Vec<String>,
+    /// Response headers which browsers expose to cross-origin callers.
+    pub exposed_headers: Vec<String>,
+    /// Seconds browsers may cache the answer to a preflight request, or their default if this
+    /// isn't set.
+    pub max_age_secs: Option<u64>,
+    /// Whether cross-origin requests may include credentials, such as cookies.
+    pub allow_credentials: bool,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        let strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+
+        CorsConfig {
+            allowed_origins: Vec::new(),
+            allowed_methods: strings(&["GET", "POST", "PUT", "DELETE"]),
+            allowed_headers: strings(&["Authorization", "Content-Type", "X-Api-Key"]),
+            exposed_headers: strings(&[
+                "Link",
+                "RateLimit-Limit",
+                "RateLimit-Remaining",
+                "RateLimit-Reset",
+                "Retry-After",
+                "X-Mirror-Age",
+            ]),
+            max_age_secs: None,
+            allow_credentials: false,
+        }
+    }
+}
+
+/* *************************************** MetadataConfig *************************************** */
+
+/// Editorial container metadata configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::MetadataConfig;
+///
+/// let config: MetadataConfig = MetadataConfig::default();
+///
+/// assert!(config.path.is_none());
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct MetadataConfig {
+    /// JSON file metadata is kept in. Metadata is only kept in memory if this isn't set.
+    pub path: Option<PathBuf>,
+}
+
+impl MetadataConfig {
+    /// Open the configured metadata store.
+    ///
+    /// See [`MetadataStore::open`].
+    pub fn store(&self) -> Result<MetadataStore> {
+        match &self.path {
+            Some(path) => MetadataStore::open(path),
+            None => Ok(MetadataStore::default()),
+        }
+    }
+}
+
+/* **************************************** MirrorConfig **************************************** */
+
+/// Local mirror of upstream catalog data configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::MirrorConfig;
+///
+/// let config: MirrorConfig = MirrorConfig::default();
+///
+/// assert!(config.path.is_none());
+/// assert_eq!(config.max_age_secs, Some(604_800));
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct MirrorConfig {
+    /// SQLite database data fetched from Rocket Container's dependencies is mirrored to. Nothing
+    /// is mirrored if this isn't set.
+    pub path: Option<PathBuf>,
+    /// Seconds mirrored data which isn't fetched again is kept for, or forever if this isn't set.
+    pub max_age_secs: Option<u64>,
+}
+
+impl Default for MirrorConfig {
+    fn default() -> Self {
+        MirrorConfig {
+            path: None,
+            max_age_secs: Some(604_800),
+        }
+    }
+}
+
+impl MirrorConfig {
+    /// Build the configured mirror, which only reads mirrored data if `offline` is set.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(mirror)` if a mirror database is configured, or `offline` isn't set.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if `offline` is set without a mirror database to read
+    ///   from, or the mirror database can't be opened.
+    pub fn mirror(&self, offline: bool) -> Result<Arc<Mirror>> {
+        match (&self.path, offline) {
+            (Some(path), _) => Ok(Arc::new(
+                Mirror::open(path)?
+                    .max_age(self.max_age_secs.map(Duration::from_secs))
+                    .offline(offline),
+            )),
+            (None, true) => Err(Error {
+                kind: ErrorKind::Permanent,
+                message: "Serving from the mirror only requires mirror.path to be configured"
+                    .to_string(),
+            }),
+            (None, false) => Ok(Arc::new(Mirror::default())),
+        }
+    }
+}
+
+/* ************************************** PaginationConfig ************************************** */
+
+/// Pagination configuration for collection routes.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::PaginationConfig;
+///
+/// let config: PaginationConfig = PaginationConfig::default();
+///
+/// assert!(config.default_page_size <= config.max_page_size);
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PaginationConfig {
+    /// Number of items returned when a request does not specify `limit`.
+    pub default_page_size: usize,
+    /// Maximum number of items returned in a single page.
+    ///
+    /// Requests with a larger `limit` are clamped to this value.
+    pub max_page_size: usize,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        PaginationConfig {
+            default_page_size: 50,
+            max_page_size: 100,
+        }
+    }
+}
+
+/* ************************************** RateLimitConfig *************************************** */
+
+/// Per-client rate limit configuration.
+///
+/// Routes are limited by their path, such as `/containers/<container_id>/ads`, or by the default
+/// limit if they have no limit of their own. See [`rate_limit`][1]. Nothing is rate limited while
+/// no limits are configured.
+///
+/// [1]: crate::controller::rate_limit
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::RateLimitConfig;
+///
+/// let config: RateLimitConfig = RateLimitConfig::default();
+///
+/// assert!(!config.limiter().is_enabled());
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Limit for routes without a limit of their own.
+    pub default: Option<Limit>,
+    /// Limits by route path.
+    pub routes: HashMap<String, Limit>,
+}
+
+impl RateLimitConfig {
+    /// Build the configured rate limiter, to be managed as [`rocket::State`].
+    pub fn limiter(&self) -> RateLimiter {
+        RateLimiter::new(self.default, self.routes.clone())
+    }
+}
+
+/* *************************************** SnapshotConfig *************************************** */
+
+/// Container snapshot configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::SnapshotConfig;
+///
+/// let config: SnapshotConfig = SnapshotConfig::default();
+///
+/// assert_eq!(config.max_age_secs, 300);
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct SnapshotConfig {
+    /// Seconds a snapshot of every container is served before it is fetched again.
+    ///
+    /// Expired videos are swept from the snapshot as they expire, regardless of its age.
+    pub max_age_secs: u64,
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        SnapshotConfig { max_age_secs: 300 }
+    }
+}
+
+/* **************************************** TitleConfig ***************************************** */
+
+/// Container title configuration.
+///
+/// Containers are titled `container-<id>_ads_images_videos` unless a template is configured.
+/// See [`TemplateTitler`][1] for the placeholders templates can contain.
+///
+/// [1]: crate::service::title::TemplateTitler
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::TitleConfig;
+///
+/// let config: TitleConfig = TitleConfig::default();
+///
+/// assert!(config.template.is_none());
+/// assert!(config.overrides.is_none());
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct TitleConfig {
+    /// Template for container titles, such as `{id} – {firstVideoTitle}`.
+    pub template: Option<String>,
+    /// JSON or TOML file of editorial titles by container ID, which take precedence over the
+    /// template.
+    pub overrides: Option<PathBuf>,
+}
+
+impl TitleConfig {
+    /// Build the configured container titler.
+    ///
+    /// See [`TemplateTitler::new`] and [`OverrideTitler::from_file`].
+    pub fn titler(&self) -> Result<Box<dyn ContainerTitler>> {
+        let titler: Box<dyn ContainerTitler> = match &self.template {
+            Some(template) => Box::new(TemplateTitler::new(template)?),
+            None => Box::new(DefaultTitler),
+        };
+
+        match &self.overrides {
+            Some(path) => Ok(Box::new(OverrideTitler::from_file(path, titler)?)),
+            None => Ok(titler),
+        }
+    }
+}
+
+/* ************************************* UnknownTypePolicy ************************************** */
+
+/// What to do with videos and asset references whose type Rocket Container doesn't know, such
+/// as a `TRAILER` video or a `SUBTITLE` asset added to Rocket Video after this release.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::config::UnknownTypePolicy;
+///
+/// assert_eq!(UnknownTypePolicy::default(), UnknownTypePolicy::Drop);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UnknownTypePolicy {
+    /// Return items with unknown types as is, with the type's name.
+    Pass,
+    /// Leave items with unknown types out of responses, and log a warning.
+    #[default]
+    Drop,
+    /// Fail requests which would return items with unknown types.
+    Report,
+}
@@ -0,0 +1,456 @@
+//! API key authentication.
+//!
+//! Clients authenticate with an API key in an `X-Api-Key` header, or an `Authorization: Bearer`
+//! header. Keys are configured by the SHA-256 hash of the key, never the key itself, along with the
+//! [`Scope`]s they are granted (see [`AuthConfig`][1]). The hash of a key can be computed with
+//! `printf %s "$KEY" | sha256sum`.
+//!
+//! Routes require a scope by taking a [`CatalogKey`] or [`AdminKey`] request guard. Requests
+//! without a known key fail with `401 Unauthorized`, and requests with a key which lacks the scope
+//! fail with `403 Forbidden`, both with an [`ErrorResponse`] body from the [`unauthorized`] and
+//! [`forbidden`] catchers.
+//!
+//! While no keys are configured, routes which require [`Scope::CatalogRead`] are public, and routes
+//! which require [`Scope::Admin`] fail with `403 Forbidden`, unless authentication is explicitly
+//! disabled with `auth.disabled = true`, which makes every route public.
+//!
+//! [1]: crate::config::AuthConfig
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
+
+use log::{trace, warn};
+use rocket::{
+    catch,
+    http::Status,
+    request::{FromRequest, Outcome},
+    serde::json::Json,
+    Request,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::controller::{Error, ErrorResponse};
+
+/// Header with an API key.
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+/* ******************************************* Scope ******************************************** */
+
+/// Permission granted to an API key.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Scope {
+    /// Read containers, advertisements, images, videos, and reports.
+    #[serde(rename = "catalog:read")]
+    CatalogRead,
+    /// Use admin routes. Grants every other scope too.
+    #[serde(rename = "admin")]
+    Admin,
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Scope::CatalogRead => write!(f, "catalog:read"),
+            Scope::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/* ******************************************* ApiKey ******************************************* */
+
+/// API key configuration.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::auth::{hash_key, ApiKey, Scope};
+///
+/// let key: ApiKey = ApiKey {
+///     name: "rocket-stream".to_string(),
+///     sha256: hash_key("secret"),
+///     scopes: vec![Scope::CatalogRead],
+/// };
+///
+/// assert!(key.grants(Scope::CatalogRead));
+/// assert!(!key.grants(Scope::Admin));
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ApiKey {
+    /// Name of the client the key belongs to, for logs.
+    pub name: String,
+    /// Hex encoded SHA-256 hash of the key.
+    pub sha256: String,
+    /// Scopes granted to the key.
+    pub scopes: Vec<Scope>,
+}
+
+impl ApiKey {
+    /// Check if the key is granted `scope`.
+    pub fn grants(&self, scope: Scope) -> bool {
+        self.scopes
+            .iter()
+            .any(|granted| *granted == scope || *granted == Scope::Admin)
+    }
+}
+
+/* ****************************************** ApiKeys ******************************************* */
+
+/// Configured API keys by hash, managed as [`rocket::State`] for the request guards.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::auth::{hash_key, ApiKey, ApiKeys, Scope};
+///
+/// let keys: ApiKeys = ApiKeys::new(vec![ApiKey {
+///     name: "rocket-stream".to_string(),
+///     sha256: hash_key("secret"),
+///     scopes: vec![Scope::CatalogRead],
+/// }]);
+///
+/// assert_eq!(keys.find("secret").map(|key| key.name.as_str()), Some("rocket-stream"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ApiKeys {
+    /// Whether authentication is disabled, making every route public.
+    disabled: bool,
+    /// API keys by hash.
+    keys: HashMap<String, ApiKey>,
+}
+
+impl ApiKeys {
+    /// Create a new [`ApiKeys`] from configured keys.
+    pub fn new(keys: Vec<ApiKey>) -> Self {
+        ApiKeys {
+            disabled: false,
+            keys: keys
+                .into_iter()
+                .map(|key| (key.sha256.to_lowercase(), key))
+                .collect(),
+        }
+    }
+
+    /// Set whether authentication is disabled, making every route public.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Check if authentication is enabled, which it is once any key is configured, unless it is
+    /// disabled.
+    pub fn is_enabled(&self) -> bool {
+        !self.disabled && !self.keys.is_empty()
+    }
+
+    /// Check if authentication is explicitly disabled.
+    pub fn is_disabled(&self) -> bool {
+        self.disabled
+    }
+
+    /// Find the configuration of a key, or [None] if the key isn't configured.
+    pub fn find(&self, key: &str) -> Option<&ApiKey> {
+        self.keys.get(&hash_key(key))
+    }
+}
+
+/* ***************************************** CatalogKey ***************************************** */
+
+/// Request guard for routes which require the [`Scope::CatalogRead`] scope.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::controller::auth::CatalogKey;
+///
+/// #[get("/catalog")]
+/// fn catalog(_key: CatalogKey) -> &'static str {
+///     "catalog"
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CatalogKey {
+    /// Name of the authenticated key, or [None] if authentication is disabled.
+    pub name: Option<String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CatalogKey {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize(request, Scope::CatalogRead).map(|name| CatalogKey { name })
+    }
+}
+
+/* ****************************************** AdminKey ****************************************** */
+
+/// Request guard for routes which require the [`Scope::Admin`] scope.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::controller::auth::AdminKey;
+///
+/// #[get("/admin")]
+/// fn admin(_key: AdminKey) -> &'static str {
+///     "admin"
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminKey {
+    /// Name of the authenticated key, or [None] if authentication is disabled.
+    pub name: Option<String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminKey {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        authorize(request, Scope::Admin).map(|name| AdminKey { name })
+    }
+}
+
+/* ****************************************** Catchers ****************************************** */
+
+/// Catcher for `401 Unauthorized`, with the message from the failed request guard.
+#[catch(401)]
+pub fn unauthorized(request: &Request<'_>) -> Error {
+    Error::Unauthorized(Json(ErrorResponse {
+        message: failure(request),
+    }))
+}
+
+/// Catcher for `403 Forbidden`, with the message from the failed request guard.
+#[catch(403)]
+pub fn forbidden(request: &Request<'_>) -> Error {
+    Error::Forbidden(Json(ErrorResponse {
+        message: failure(request),
+    }))
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Hash an API key as it is configured: hex encoded SHA-256.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::auth::hash_key;
+///
+/// assert_eq!(
+///     hash_key("secret"),
+///     "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+/// );
+/// ```
+pub fn hash_key(key: &str) -> String {
+    Sha256::digest(key.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Get the API key a request was made with, from its `X-Api-Key` or `Authorization: Bearer`
+/// header.
+pub(crate) fn presented_key<'r>(request: &'r Request<'_>) -> Option<&'r str> {
+    request
+        .headers()
+        .get_one(API_KEY_HEADER)
+        .or_else(|| {
+            request
+                .headers()
+                .get_one("Authorization")
+                .and_then(|value| value.strip_prefix("Bearer "))
+        })
+        .map(str::trim)
+}
+
+/// Message of an authentication failure, cached for the catchers.
+struct AuthFailure(String);
+
+/// Check that a request has a key granted `scope`.
+///
+/// Succeeds with the key's name, or [None] if the route is public. Without any keys,
+/// [`Scope::Admin`] is only granted if authentication is explicitly disabled.
+fn authorize(request: &Request<'_>, scope: Scope) -> Outcome<Option<String>, String> {
+    let keys: &ApiKeys = match request.rocket().state::<ApiKeys>() {
+        Some(keys) if keys.is_enabled() => keys,
+        Some(keys) if keys.is_disabled() => return Outcome::Success(None),
+        _ if scope != Scope::Admin => return Outcome::Success(None),
+        _ => {
+            warn!("No API keys are configured, refusing {}", request.uri());
+
+            return fail(
+                request,
+                Status::Forbidden,
+                "Admin routes require an API key, but none are configured".to_string(),
+            );
+        }
+    };
+    let (status, message): (Status, String) = match presented_key(request).map(|key| keys.find(key))
+    {
+        None => (Status::Unauthorized, "Missing API key".to_string()),
+        Some(None) => (Status::Unauthorized, "Invalid API key".to_string()),
+        Some(Some(key)) if key.grants(scope) => {
+            trace!("Authorized {} for {}", key.name, scope);
+
+            return Outcome::Success(Some(key.name.clone()));
+        }
+        Some(Some(key)) => {
+            warn!(
+                "Key {} is missing scope {} for {}",
+                key.name,
+                scope,
+                request.uri()
+            );
+
+            (Status::Forbidden, format!("API key lacks scope {}", scope))
+        }
+    };
+
+    fail(request, status, message)
+}
+
+/// Fail a request guard with `status`, caching `message` for the catchers.
+fn fail<T>(request: &Request<'_>, status: Status, message: String) -> Outcome<T, String> {
+    request.local_cache(|| AuthFailure(message.clone()));
+
+    Outcome::Error((status, message))
+}
+
+/// Get the message of a request's authentication failure.
+fn failure(request: &Request<'_>) -> String {
+    request
+        .local_cache(|| AuthFailure("Not authorized".to_string()))
+        .0
+        .clone()
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use rocket::{
+        catchers, get,
+        http::{Header, Status},
+        local::blocking::{Client, LocalResponse},
+        routes, Build, Rocket,
+    };
+
+    use super::{forbidden, hash_key, unauthorized, AdminKey, ApiKey, ApiKeys, CatalogKey, Scope};
+
+    #[get("/catalog")]
+    fn catalog(_key: CatalogKey) {}
+
+    #[get("/admin")]
+    fn admin(_key: AdminKey) {}
+
+    fn rocket(keys: ApiKeys) -> Rocket<Build> {
+        rocket::build()
+            .manage(keys)
+            .mount("/", routes![admin, catalog])
+            .register("/", catchers![forbidden, unauthorized])
+    }
+
+    fn keys() -> ApiKeys {
+        ApiKeys::new(vec![
+            ApiKey {
+                name: "reader".to_string(),
+                sha256: hash_key("reader-key"),
+                scopes: vec![Scope::CatalogRead],
+            },
+            ApiKey {
+                name: "admin".to_string(),
+                sha256: hash_key("admin-key"),
+                scopes: vec![Scope::Admin],
+            },
+        ])
+    }
+
+    fn status(client: &Client, uri: &'static str, header: Option<Header<'static>>) -> Status {
+        let mut request = client.get(uri);
+
+        if let Some(header) = header {
+            request = request.header(header);
+        }
+
+        request.dispatch().status()
+    }
+
+    #[test]
+    fn authorize_by_scope() {
+        // Given
+        let client: Client = Client::tracked(rocket(keys())).unwrap();
+        let reader = || Some(Header::new("X-Api-Key", "reader-key"));
+        let admin = || Some(Header::new("Authorization", "Bearer admin-key"));
+
+        // When
+        let actual: Vec<Status> = vec![
+            status(&client, "/catalog", reader()),
+            status(&client, "/admin", reader()),
+            status(&client, "/catalog", admin()),
+            status(&client, "/admin", admin()),
+        ];
+
+        // Then
+        assert_eq!(
+            actual,
+            vec![Status::Ok, Status::Forbidden, Status::Ok, Status::Ok]
+        );
+    }
+
+    #[test]
+    fn reject_unknown_key() {
+        // Given
+        let client: Client = Client::tracked(rocket(keys())).unwrap();
+
+        // When
+        let response: LocalResponse = client
+            .get("/catalog")
+            .header(Header::new("X-Api-Key", "wrong-key"))
+            .dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert_eq!(
+            response.into_string().unwrap(),
+            r#"{"message":"Invalid API key"}"#
+        );
+    }
+
+    #[test]
+    fn forbid_admin_without_keys() {
+        // Given
+        let client: Client = Client::tracked(rocket(ApiKeys::default())).unwrap();
+
+        // When
+        let catalog: Status = status(&client, "/catalog", None);
+        let admin: Status = status(&client, "/admin", None);
+
+        // Then
+        assert_eq!(catalog, Status::Ok);
+        assert_eq!(admin, Status::Forbidden);
+    }
+
+    #[test]
+    fn allow_every_request_when_disabled() {
+        // Given
+        let client: Client = Client::tracked(rocket(ApiKeys::default().disabled(true))).unwrap();
+
+        // When
+        let actual: Status = status(&client, "/admin", None);
+
+        // Then
+        assert_eq!(actual, Status::Ok);
+    }
+}
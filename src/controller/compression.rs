@@ -0,0 +1,395 @@
+//! Response compression.
+//!
+//! The [`compression`] fairing compresses response bodies with the [`Encoding`] the client
+//! prefers in its `Accept-Encoding` header, out of those configured. Bodies smaller than the
+//! configured minimum size, and bodies which aren't JSON or text, are sent as is. See
+//! [`CompressionConfig`].
+
+use std::{
+    fmt::{Display, Formatter},
+    io::{Cursor, Write},
+};
+
+use flate2::{write::GzEncoder, Compression};
+use log::{trace, warn};
+use rocket::{
+    fairing::AdHoc,
+    http::{ContentType, Header, Status},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config::CompressionConfig;
+
+/* ****************************************** Encoding ****************************************** */
+
+/// Content encoding a response can be compressed with.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Encoding {
+    /// Brotli.
+    #[serde(rename = "br")]
+    Brotli,
+    /// Gzip.
+    #[serde(rename = "gzip")]
+    Gzip,
+    /// Zstandard.
+    #[serde(rename = "zstd")]
+    Zstd,
+}
+
+impl Encoding {
+    /// Compress bytes at `level`, or the encoding's default level if [None].
+    ///
+    /// Levels are clamped to the range the encoding supports: 0 to 9 for gzip, 0 to 11 for
+    /// brotli, and 1 to 22 for zstd.
+    pub fn compress(&self, bytes: &[u8], level: Option<u32>) -> std::io::Result<Vec<u8>> {
+        match self {
+            Encoding::Brotli => {
+                let mut writer =
+                    brotli::CompressorWriter::new(Vec::new(), 4096, level.unwrap_or(5).min(11), 22);
+
+                writer.write_all(bytes)?;
+
+                Ok(writer.into_inner())
+            }
+            Encoding::Gzip => {
+                let mut encoder: GzEncoder<Vec<u8>> =
+                    GzEncoder::new(Vec::new(), Compression::new(level.unwrap_or(6).min(9)));
+
+                encoder.write_all(bytes)?;
+                encoder.finish()
+            }
+            Encoding::Zstd => zstd::encode_all(bytes, level.unwrap_or(3).clamp(1, 22) as i32),
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encoding::Brotli => write!(f, "br"),
+            Encoding::Gzip => write!(f, "gzip"),
+            Encoding::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+/* ****************************************** Fairing ******************************************* */
+
+/// Fairing which compresses response bodies as negotiated with `Accept-Encoding`.
+///
+/// Does nothing while no encodings are configured. Streamed bodies, such as NDJSON exports, are
+/// sent uncompressed so they aren't buffered in memory.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{config::CompressionConfig, controller::compression::compression};
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build().attach(compression(CompressionConfig::default()))
+/// }
+/// ```
+pub fn compression(config: CompressionConfig) -> AdHoc {
+    AdHoc::on_response("Compression", move |request, response| {
+        let encoding: Option<Encoding> = request
+            .headers()
+            .get_one("Accept-Encoding")
+            .and_then(|accept| negotiate(accept, &config.encodings));
+        let enabled: bool = !config.encodings.is_empty();
+        let level: Option<u32> = config.level;
+        let min_size: usize = config.min_size;
+
+        Box::pin(async move {
+            if !enabled
+                || response.status() == Status::NoContent
+                || response.headers().contains("Content-Encoding")
+                || !response
+                    .content_type()
+                    .is_some_and(|content_type| is_compressible(&content_type))
+            {
+                return;
+            }
+
+            // Whether or not this response is compressed, the resource may be served differently
+            // for another `Accept-Encoding`, so shared caches must not reuse this copy for it.
+            response.adjoin_header(Header::new("Vary", "Accept-Encoding"));
+
+            let encoding: Encoding = match encoding {
+                Some(encoding) => encoding,
+                None => return,
+            };
+
+            // Streamed bodies have no preset size, and are passed through rather than buffered.
+            match response.body().preset_size() {
+                Some(size) if size >= min_size => {}
+                _ => return,
+            }
+
+            let body: Vec<u8> = match response.body_mut().to_bytes().await {
+                Ok(body) => body,
+                Err(err) => {
+                    warn!("Failed to read response body with error {}", err);
+                    return;
+                }
+            };
+
+            if body.len() < min_size {
+                response.set_sized_body(body.len(), Cursor::new(body));
+                return;
+            }
+
+            match encoding.compress(&body, level) {
+                Ok(compressed) => {
+                    trace!(
+                        "Compressed response from {} to {} bytes with {}",
+                        body.len(),
+                        compressed.len(),
+                        encoding
+                    );
+
+                    response.set_header(Header::new("Content-Encoding", encoding.to_string()));
+                    response.set_sized_body(compressed.len(), Cursor::new(compressed));
+                }
+                Err(err) => {
+                    warn!("Failed to compress response with error {}", err);
+
+                    response.set_sized_body(body.len(), Cursor::new(body));
+                }
+            }
+        })
+    })
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Choose the encoding to compress a response with for an `Accept-Encoding` header.
+///
+/// The encoding with the highest quality value is chosen, and encodings with the same quality
+/// value are chosen in the order of `encodings`. Encodings with a quality value of 0, and those
+/// not in `encodings`, are never chosen.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::compression::{negotiate, Encoding};
+///
+/// let encodings: [Encoding; 2] = [Encoding::Brotli, Encoding::Gzip];
+///
+/// assert_eq!(negotiate("gzip, br", &encodings), Some(Encoding::Brotli));
+/// assert_eq!(negotiate("gzip, br;q=0.5", &encodings), Some(Encoding::Gzip));
+/// assert_eq!(negotiate("zstd", &encodings), None);
+/// ```
+pub fn negotiate(accept_encoding: &str, encodings: &[Encoding]) -> Option<Encoding> {
+    let accepted: Vec<(&str, f32)> = accept_encoding
+        .split(',')
+        .filter_map(|value| {
+            let mut parts = value.split(';').map(str::trim);
+            let name: &str = parts.next().filter(|name| !name.is_empty())?;
+            let quality: f32 = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |quality| quality.parse().ok())?;
+
+            Some((name, quality))
+        })
+        .collect();
+    let quality = |encoding: &Encoding| -> f32 {
+        let name: String = encoding.to_string();
+
+        accepted
+            .iter()
+            .find(|(accepted, _)| accepted.eq_ignore_ascii_case(&name))
+            .or_else(|| accepted.iter().find(|(accepted, _)| *accepted == "*"))
+            .map_or(0.0, |(_, quality)| *quality)
+    };
+
+    encodings
+        .iter()
+        .map(|encoding| (*encoding, quality(encoding)))
+        .filter(|(_, quality)| *quality > 0.0)
+        .fold(
+            None,
+            |best: Option<(Encoding, f32)>, candidate| match best {
+                Some(best) if best.1 >= candidate.1 => Some(best),
+                _ => Some(candidate),
+            },
+        )
+        .map(|(encoding, _)| encoding)
+}
+
+/// Check if a content type is worth compressing: text, and JSON including vendor types such as
+/// [`V2_MEDIA_TYPE`](crate::controller::version::V2_MEDIA_TYPE).
+fn is_compressible(content_type: &ContentType) -> bool {
+    content_type.is_json()
+        || content_type.sub().as_str().ends_with("+json")
+        || content_type.top() == "text"
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use std::{io::Read, vec::IntoIter};
+
+    use flate2::read::GzDecoder;
+    use rocket::{
+        futures::stream::{self, Iter},
+        get,
+        http::{ContentType, Header},
+        local::blocking::{Client, LocalResponse},
+        response::stream::TextStream,
+        routes,
+        serde::json::{json, Value},
+        Build, Rocket,
+    };
+
+    use crate::{config::CompressionConfig, controller::version::V2_MEDIA_TYPE};
+
+    use super::{compression, is_compressible, Encoding};
+
+    #[get("/containers")]
+    fn containers() -> Value {
+        json!((0..100)
+            .map(|id| json!({"id": id, "title": format!("container-{}_videos", id)}))
+            .collect::<Vec<Value>>())
+    }
+
+    #[get("/stream")]
+    fn streamed() -> TextStream<Iter<IntoIter<String>>> {
+        TextStream(stream::iter(
+            (0..100)
+                .map(|id| format!("{{\"id\":{}}}\n", id))
+                .collect::<Vec<String>>(),
+        ))
+    }
+
+    #[get("/small")]
+    fn small() -> Value {
+        json!({"id": 0})
+    }
+
+    fn rocket() -> Rocket<Build> {
+        rocket::build()
+            .attach(compression(CompressionConfig::default()))
+            .mount("/", routes![containers, small, streamed])
+    }
+
+    fn get<'c>(
+        client: &'c Client,
+        uri: &'static str,
+        accept_encoding: &'static str,
+    ) -> LocalResponse<'c> {
+        client
+            .get(uri)
+            .header(Header::new("Accept-Encoding", accept_encoding))
+            .dispatch()
+    }
+
+    fn decompress(encoding: Encoding, bytes: &[u8]) -> Vec<u8> {
+        let mut decompressed: Vec<u8> = Vec::new();
+
+        match encoding {
+            Encoding::Brotli => {
+                brotli::Decompressor::new(bytes, 4096)
+                    .read_to_end(&mut decompressed)
+                    .unwrap();
+            }
+            Encoding::Gzip => {
+                GzDecoder::new(bytes)
+                    .read_to_end(&mut decompressed)
+                    .unwrap();
+            }
+            Encoding::Zstd => decompressed = zstd::decode_all(bytes).unwrap(),
+        }
+
+        decompressed
+    }
+
+    #[test]
+    fn compress_with_each_encoding() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+        let expected: Vec<u8> = get(&client, "/containers", "identity")
+            .into_bytes()
+            .unwrap();
+
+        for encoding in [Encoding::Brotli, Encoding::Gzip, Encoding::Zstd] {
+            // When
+            let response: LocalResponse = get(
+                &client,
+                "/containers",
+                match encoding {
+                    Encoding::Brotli => "br",
+                    Encoding::Gzip => "gzip",
+                    Encoding::Zstd => "zstd",
+                },
+            );
+
+            // Then
+            assert_eq!(
+                response.headers().get_one("Content-Encoding"),
+                Some(encoding.to_string().as_str())
+            );
+
+            let actual: Vec<u8> = response.into_bytes().unwrap();
+
+            assert!(actual.len() < expected.len());
+            assert_eq!(decompress(encoding, &actual), expected);
+        }
+    }
+
+    #[test]
+    fn compress_json_and_text() {
+        // Given
+        let compressible =
+            |media_type: &str| is_compressible(&ContentType::parse_flexible(media_type).unwrap());
+
+        // Then
+        assert!(compressible("application/json"));
+        assert!(compressible(V2_MEDIA_TYPE));
+        assert!(compressible("text/csv"));
+        assert!(!compressible("application/gzip"));
+    }
+
+    #[test]
+    fn skip_small_responses() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let response: LocalResponse = get(&client, "/small", "gzip");
+
+        // Then
+        assert!(!response.headers().contains("Content-Encoding"));
+        assert_eq!(response.into_string().unwrap(), r#"{"id":0}"#);
+    }
+
+    #[test]
+    fn vary_uncompressed_responses() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let response: LocalResponse = get(&client, "/containers", "identity");
+
+        // Then
+        assert!(!response.headers().contains("Content-Encoding"));
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept-Encoding"));
+    }
+
+    #[test]
+    fn skip_streamed_responses() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let response: LocalResponse = get(&client, "/stream", "gzip");
+
+        // Then
+        assert!(!response.headers().contains("Content-Encoding"));
+        assert!(response.into_string().unwrap().starts_with("{\"id\":0}\n"));
+    }
+}
@@ -0,0 +1,241 @@
+//! Cross-origin resource sharing (CORS) for browser clients.
+//!
+//! The [`cors`] fairing adds CORS headers to responses to requests from allowed origins, and
+//! answers CORS preflight `OPTIONS` requests for every route with `204 No Content`, so browsers can
+//! call Rocket Container directly. See [`CorsConfig`] for what is allowed.
+
+use log::debug;
+use rocket::{
+    fairing::AdHoc,
+    http::{Header, Method, Status},
+    Request,
+};
+
+use crate::config::CorsConfig;
+
+/* ****************************************** Fairing ******************************************* */
+
+/// Fairing which adds CORS headers to responses, and answers CORS preflight requests.
+///
+/// Does nothing while no origins are allowed.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{config::CorsConfig, controller::cors::cors};
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build().attach(cors(CorsConfig {
+///         allowed_origins: vec!["https://player.example.com".to_string()],
+///         ..CorsConfig::default()
+///     }))
+/// }
+/// ```
+pub fn cors(config: CorsConfig) -> AdHoc {
+    AdHoc::on_response("CORS", move |request, response| {
+        let headers: Vec<Header<'static>> = match request
+            .headers()
+            .get_one("Origin")
+            .and_then(|origin| allowed_origin(&config, origin))
+        {
+            Some(origin) => cors_headers(&config, origin, is_preflight(request)),
+            None => Vec::new(),
+        };
+        let preflight: bool = !headers.is_empty() && is_preflight(request);
+
+        Box::pin(async move {
+            if preflight {
+                debug!("Answering CORS preflight request");
+
+                response.set_status(Status::NoContent);
+                response.set_sized_body(0, std::io::Cursor::new(""));
+                response.remove_header("Content-Type");
+            }
+
+            for header in headers {
+                if header.name() == "Vary" {
+                    response.adjoin_header(header);
+                } else {
+                    response.set_header(header);
+                }
+            }
+        })
+    })
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Get the `Access-Control-Allow-Origin` value for a request's origin, or [None] if the origin
+/// isn't allowed.
+///
+/// A wildcard `*` allows every origin. The origin itself is returned rather than `*` when
+/// credentials are allowed, since browsers reject credentialed responses for `*`.
+fn allowed_origin(config: &CorsConfig, origin: &str) -> Option<String> {
+    let wildcard: bool = config.allowed_origins.iter().any(|allowed| allowed == "*");
+
+    if wildcard && !config.allow_credentials {
+        Some("*".to_string())
+    } else if wildcard
+        || config
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+    {
+        Some(origin.to_string())
+    } else {
+        None
+    }
+}
+
+/// Build the CORS headers for a response to an allowed origin.
+fn cors_headers(config: &CorsConfig, origin: String, preflight: bool) -> Vec<Header<'static>> {
+    let mut headers: Vec<Header<'static>> = Vec::new();
+
+    if origin != "*" {
+        headers.push(Header::new("Vary", "Origin"));
+    }
+
+    headers.push(Header::new("Access-Control-Allow-Origin", origin));
+
+    if config.allow_credentials {
+        headers.push(Header::new("Access-Control-Allow-Credentials", "true"));
+    }
+
+    match preflight {
+        true => {
+            headers.push(Header::new(
+                "Access-Control-Allow-Methods",
+                config.allowed_methods.join(", "),
+            ));
+            headers.push(Header::new(
+                "Access-Control-Allow-Headers",
+                config.allowed_headers.join(", "),
+            ));
+
+            if let Some(max_age_secs) = config.max_age_secs {
+                headers.push(Header::new(
+                    "Access-Control-Max-Age",
+                    max_age_secs.to_string(),
+                ));
+            }
+        }
+        false if !config.exposed_headers.is_empty() => headers.push(Header::new(
+            "Access-Control-Expose-Headers",
+            config.exposed_headers.join(", "),
+        )),
+        false => {}
+    }
+
+    headers
+}
+
+/// Check if a request is a CORS preflight request.
+fn is_preflight(request: &Request<'_>) -> bool {
+    request.method() == Method::Options
+        && request.headers().contains("Access-Control-Request-Method")
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use rocket::{
+        get,
+        http::{Header, Status},
+        local::blocking::{Client, LocalResponse},
+        routes, Build, Rocket,
+    };
+
+    use crate::config::CorsConfig;
+
+    use super::cors;
+
+    #[get("/containers")]
+    fn containers() -> &'static str {
+        "[]"
+    }
+
+    fn rocket(config: CorsConfig) -> Rocket<Build> {
+        rocket::build()
+            .attach(cors(config))
+            .mount("/", routes![containers])
+    }
+
+    fn config() -> CorsConfig {
+        CorsConfig {
+            allowed_origins: vec!["https://player.example.com".to_string()],
+            max_age_secs: Some(600),
+            ..CorsConfig::default()
+        }
+    }
+
+    #[test]
+    fn answer_preflight_request() {
+        // Given
+        let client: Client = Client::tracked(rocket(config())).unwrap();
+
+        // When
+        let response: LocalResponse = client
+            .options("/containers")
+            .header(Header::new("Origin", "https://player.example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::NoContent);
+        assert_eq!(
+            response.headers().get_one("Access-Control-Allow-Origin"),
+            Some("https://player.example.com")
+        );
+        assert_eq!(
+            response.headers().get_one("Access-Control-Allow-Methods"),
+            Some("GET, POST, PUT, DELETE")
+        );
+        assert_eq!(
+            response.headers().get_one("Access-Control-Max-Age"),
+            Some("600")
+        );
+    }
+
+    #[test]
+    fn add_headers_for_allowed_origin() {
+        // Given
+        let client: Client = Client::tracked(rocket(config())).unwrap();
+
+        // When
+        let response: LocalResponse = client
+            .get("/containers")
+            .header(Header::new("Origin", "https://player.example.com"))
+            .dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.headers().get_one("Access-Control-Allow-Origin"),
+            Some("https://player.example.com")
+        );
+        assert_eq!(response.headers().get_one("Vary"), Some("Origin"));
+        assert!(response.headers().contains("Access-Control-Expose-Headers"));
+    }
+
+    #[test]
+    fn ignore_other_origins() {
+        // Given
+        let client: Client = Client::tracked(rocket(config())).unwrap();
+
+        // When
+        let response: LocalResponse = client
+            .options("/containers")
+            .header(Header::new("Origin", "https://evil.example.com"))
+            .header(Header::new("Access-Control-Request-Method", "GET"))
+            .dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::NotFound);
+        assert!(!response.headers().contains("Access-Control-Allow-Origin"));
+    }
+}
@@ -0,0 +1,491 @@
+//! Export formats of collection routes.
+//!
+//! `GET /containers` and the per-container `/videos`, `/images`, and `/ads` routes choose the
+//! format of their response from the client's preferred `Accept` media type:
+//!
+//! - JSON by default, as a page of items in the shape of the request's API version.
+//! - [`NDJSON_MEDIA_TYPE`], streamed one JSON record per line. Records respect `fields`.
+//! - [`CSV_MEDIA_TYPE`], flattened into one row per item by [`service::export`][1].
+//!
+//! Exports are meant for pulling whole collections, so they include every matching item unless a
+//! `limit` is given, and then link to the next page in a `Link` header instead of the body.
+//!
+//! Collections are fetched and filtered in memory before they are exported, so an export is bounded
+//! by the size of the collection upstream rather than streamed from it. Only the serialization of
+//! NDJSON records is streamed, and those responses are not compressed, so they aren't buffered
+//! again by the [`compression`](crate::controller::compression) fairing.
+//!
+//! [1]: crate::service::export
+
+use std::convert::Infallible;
+
+use log::error;
+use rocket::{
+    futures::stream,
+    http::{uri::Origin, ContentType, Header, MediaType, Status},
+    request::{FromRequest, Outcome},
+    response::{self, stream::TextStream, Responder},
+    Request,
+};
+
+use crate::{
+    config::{Config, PaginationConfig},
+    controller::{
+        fields::{Fields, Selectable, Sparse},
+        page::{Page, PageQuery},
+        version::{Enveloped, Versioned},
+        Error,
+    },
+};
+
+/// Media type of newline delimited JSON exports.
+pub const NDJSON_MEDIA_TYPE: &str = "application/x-ndjson";
+
+/// Media type of CSV exports.
+pub const CSV_MEDIA_TYPE: &str = "text/csv";
+
+/* **************************************** ExportFormat **************************************** */
+
+/// Response format of a collection route, negotiated with the `Accept` header.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::controller::export::ExportFormat;
+///
+/// #[get("/format")]
+/// fn format(format: ExportFormat) -> String {
+///     format!("{:?}", format)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// A JSON page.
+    #[default]
+    Json,
+    /// Newline delimited JSON, one record per line.
+    Ndjson,
+    /// CSV with a header row.
+    Csv,
+}
+
+impl ExportFormat {
+    /// Get the export format a media type selects, or [None] if it isn't an export format.
+    pub fn from_media_type(media_type: &MediaType) -> Option<Self> {
+        let essence: String = format!("{}/{}", media_type.top(), media_type.sub());
+
+        if essence.eq_ignore_ascii_case(NDJSON_MEDIA_TYPE) {
+            Some(ExportFormat::Ndjson)
+        } else if essence.eq_ignore_ascii_case(CSV_MEDIA_TYPE) {
+            Some(ExportFormat::Csv)
+        } else {
+            None
+        }
+    }
+
+    /// Get the pagination of the format: `config` for JSON, and every item unless a `limit` is
+    /// given for exports.
+    pub fn pagination(&self, config: &PaginationConfig) -> PaginationConfig {
+        match self {
+            ExportFormat::Json => config.clone(),
+            ExportFormat::Ndjson | ExportFormat::Csv => PaginationConfig {
+                default_page_size: usize::MAX,
+                max_page_size: usize::MAX,
+            },
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ExportFormat {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(
+            request
+                .accept()
+                .and_then(|accept| ExportFormat::from_media_type(accept.preferred().media_type()))
+                .unwrap_or_default(),
+        )
+    }
+}
+
+/* *************************************** ExportRequest **************************************** */
+
+/// Request guard of a collection route, with the negotiated [`ExportFormat`], the origin to link
+/// the next page to, and the pagination of the format.
+///
+/// Requires [`Config`] to be managed, e.g. by `AdHoc::config::<Config>()`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket::fairing::AdHoc;
+/// use rocket_container::{
+///     config::Config,
+///     controller::{
+///         export::{Export, ExportRequest},
+///         page::PageQuery,
+///         Error,
+///     },
+///     service::{export::images_to_csv, image::Image},
+///     types::{ContainerId, ImageId},
+/// };
+///
+/// #[get("/images")]
+/// fn images(export: ExportRequest<'_>) -> Result<Export<Image>, Error> {
+///     let images: Vec<Image> = vec![Image::new(
+///         ImageId(1),
+///         "Image".to_string(),
+///         "https://example.com/image".to_string(),
+///     )];
+///     let page = export.paginate(images, &PageQuery::default())?;
+///
+///     Ok(export.export(page, None, |images| images_to_csv(ContainerId(1), images)))
+/// }
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build()
+///         .attach(AdHoc::config::<Config>())
+///         .mount("/", routes![images])
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ExportRequest<'r> {
+    format: ExportFormat,
+    origin: &'r Origin<'r>,
+    pagination: PaginationConfig,
+}
+
+impl ExportRequest<'_> {
+    /// Get the export format.
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+
+    /// Get a page of items, paginated as the export format is.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(page)` if the query is valid.
+    /// - `Err(`[`Error::BadRequest`]`)` if `limit` is zero.
+    pub fn paginate<T>(&self, items: Vec<T>, query: &PageQuery) -> Result<Page<T>, Error> {
+        Page::paginate(items, query, &self.pagination, self.origin)
+    }
+
+    /// Export a page of items. See [`Export::new`].
+    pub fn export<T: Selectable>(
+        &self,
+        page: Page<T>,
+        fields: Option<Fields<T>>,
+        to_csv: impl FnOnce(&[T]) -> String,
+    ) -> Export<T> {
+        Export::new(self.format, page, fields, to_csv)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ExportRequest<'r> {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let config: &Config = match request.rocket().state::<Config>() {
+            Some(config) => config,
+            None => {
+                error!("Config is not managed");
+
+                return Outcome::Error((
+                    Status::InternalServerError,
+                    "Config is not managed".to_string(),
+                ));
+            }
+        };
+        let format: ExportFormat = match ExportFormat::from_request(request).await {
+            Outcome::Success(format) => format,
+            _ => ExportFormat::default(),
+        };
+
+        Outcome::Success(ExportRequest {
+            format,
+            origin: request.uri(),
+            pagination: format.pagination(&config.pagination),
+        })
+    }
+}
+
+/* ******************************************* Export ******************************************* */
+
+/// Responder for a page of a collection in an [`ExportFormat`].
+///
+/// Responses vary by the `Accept` header.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::{
+///         export::{Export, ExportFormat},
+///         page::Page,
+///     },
+///     service::{export::images_to_csv, image::Image},
+///     types::{ContainerId, ImageId},
+/// };
+///
+/// #[get("/images")]
+/// fn images(format: ExportFormat) -> Export<Image> {
+///     let page: Page<Image> = Page {
+///         items: vec![Image::new(
+///             ImageId(1),
+///             "Image".to_string(),
+///             "https://example.com/image".to_string(),
+///         )],
+///         next: None,
+///     };
+///
+///     Export::new(format, page, None, |images| images_to_csv(ContainerId(1), images))
+/// }
+/// ```
+#[derive(Debug)]
+pub enum Export<T> {
+    /// A JSON page, in the shape of the request's API version.
+    Json(Versioned<Page<Sparse<T>>>),
+    /// Records streamed one per line, with the link to the next page. The page itself is already
+    /// in memory; only its serialization is streamed.
+    Ndjson(Page<Sparse<T>>),
+    /// CSV, with the link to the next page.
+    Csv(String, Option<String>),
+}
+
+impl<T: Selectable> Export<T> {
+    /// Export a page of items in `format`, with only the selected `fields` of each JSON record,
+    /// or as the CSV `to_csv` formats the items as.
+    pub fn new(
+        format: ExportFormat,
+        page: Page<T>,
+        fields: Option<Fields<T>>,
+        to_csv: impl FnOnce(&[T]) -> String,
+    ) -> Self {
+        match format {
+            ExportFormat::Json => Export::Json(Versioned(
+                page.map(|item| Sparse::new(item, fields.clone())),
+            )),
+            ExportFormat::Ndjson => {
+                Export::Ndjson(page.map(|item| Sparse::new(item, fields.clone())))
+            }
+            ExportFormat::Csv => Export::Csv(to_csv(&page.items), page.next),
+        }
+    }
+}
+
+impl<'r, T> Responder<'r, 'r> for Export<T>
+where
+    T: Selectable + Enveloped + Send + 'r,
+{
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'r> {
+        let (mut response, next) = match self {
+            Export::Json(page) => (page.respond_to(request)?, None),
+            Export::Ndjson(page) => {
+                let records = stream::iter(page.items.into_iter().filter_map(|item| {
+                    match serde_json::to_string(&item) {
+                        Ok(record) => Some(format!("{}\n", record)),
+                        Err(err) => {
+                            error!("Error while exporting record {}", err);
+
+                            None
+                        }
+                    }
+                }));
+                let mut response = TextStream(records).respond_to(request)?;
+
+                response.set_header(ContentType::new("application", "x-ndjson"));
+
+                (response, page.next)
+            }
+            Export::Csv(csv, next) => ((ContentType::CSV, csv).respond_to(request)?, next),
+        };
+
+        if let Some(next) = next {
+            response.set_header(Header::new("Link", format!("<{}>; rel=\"next\"", next)));
+        }
+
+        response.set_header(Header::new("Vary", "Accept"));
+
+        Ok(response)
+    }
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use rocket::{
+        get,
+        http::{ContentType, Header},
+        local::blocking::{Client, LocalResponse},
+        routes,
+        serde::json::{json, Value},
+        Build, Rocket,
+    };
+
+    use crate::{
+        config::{Config, PaginationConfig},
+        controller::{
+            page::{Page, PageQuery},
+            Error,
+        },
+        service::{export::images_to_csv, image::Image},
+        types::{ContainerId, ImageId},
+    };
+
+    use super::{Export, ExportFormat, ExportRequest, CSV_MEDIA_TYPE, NDJSON_MEDIA_TYPE};
+
+    fn image_list() -> Vec<Image> {
+        (1..=2)
+            .map(|id| {
+                Image::new(
+                    ImageId(id),
+                    format!("Image {}", id),
+                    format!("https://example.com/{}.png", id),
+                )
+            })
+            .collect()
+    }
+
+    #[get("/images?<fields>")]
+    fn images(format: ExportFormat, fields: Option<&str>) -> Export<Image> {
+        let page: Page<Image> = Page {
+            items: image_list(),
+            next: Some("/images?limit=2&cursor=2".to_string()),
+        };
+
+        Export::new(
+            format,
+            page,
+            fields.map(|fields| fields.parse().unwrap()),
+            |images| images_to_csv(ContainerId(3), images),
+        )
+    }
+
+    #[get("/paginated")]
+    fn paginated(export: ExportRequest<'_>) -> Result<Export<Image>, Error> {
+        let page: Page<Image> = export.paginate(image_list(), &PageQuery::default())?;
+
+        Ok(export.export(page, None, |images| images_to_csv(ContainerId(3), images)))
+    }
+
+    fn rocket() -> Rocket<Build> {
+        let config: Config = Config {
+            pagination: PaginationConfig {
+                default_page_size: 1,
+                max_page_size: 1,
+            },
+            ..Config::default()
+        };
+
+        rocket::build()
+            .manage(config)
+            .mount("/", routes![images, paginated])
+    }
+
+    fn get<'c>(client: &'c Client, uri: &'static str, accept: &'static str) -> LocalResponse<'c> {
+        client
+            .get(uri)
+            .header(Header::new("Accept", accept))
+            .dispatch()
+    }
+
+    #[test]
+    fn export_json_by_default() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let response: LocalResponse = get(&client, "/images", "application/json, */*");
+
+        // Then
+        assert_eq!(response.content_type(), Some(ContentType::JSON));
+        assert_eq!(response.headers().get_one("Link"), None);
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept"));
+
+        let actual: Value = response.into_json().unwrap();
+
+        assert_eq!(actual["items"][1]["id"], json!(2));
+        assert_eq!(actual["next"], json!("/images?limit=2&cursor=2"));
+    }
+
+    #[test]
+    fn export_ndjson_record_per_line() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let response: LocalResponse = get(
+            &client,
+            "/images?fields=id,url",
+            "application/json;q=0.5, application/x-ndjson",
+        );
+
+        // Then
+        assert_eq!(
+            response
+                .content_type()
+                .map(|content_type| content_type.to_string()),
+            Some(NDJSON_MEDIA_TYPE.to_string())
+        );
+        assert_eq!(
+            response.headers().get_one("Link"),
+            Some("</images?limit=2&cursor=2>; rel=\"next\"")
+        );
+        assert_eq!(
+            response.into_string().unwrap(),
+            "{\"id\":1,\"url\":\"https://example.com/1.png\"}\n\
+            {\"id\":2,\"url\":\"https://example.com/2.png\"}\n"
+        );
+    }
+
+    #[test]
+    fn export_csv_row_per_item() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let response: LocalResponse = get(&client, "/images", CSV_MEDIA_TYPE);
+
+        // Then
+        assert_eq!(response.content_type(), Some(ContentType::CSV));
+        assert_eq!(
+            response.into_string().unwrap(),
+            "containerId,imageId,name,url\r\n\
+            3,1,Image 1,https://example.com/1.png\r\n\
+            3,2,Image 2,https://example.com/2.png\r\n"
+        );
+    }
+
+    #[test]
+    fn export_request_paginates_json_only() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let json: Value = get(&client, "/paginated", "application/json")
+            .into_json()
+            .unwrap();
+        let csv: LocalResponse = get(&client, "/paginated", CSV_MEDIA_TYPE);
+
+        // Then
+        assert_eq!(json["items"].as_array().map(Vec::len), Some(1));
+        assert_eq!(json["next"], json!("/paginated?limit=1&cursor=1"));
+        assert_eq!(csv.headers().get_one("Link"), None);
+        assert_eq!(csv.into_string().map(|csv| csv.lines().count()), Some(3));
+    }
+}
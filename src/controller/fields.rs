@@ -0,0 +1,501 @@
+//! Sparse fieldsets.
+//!
+//! Routes which respond with containers, videos, images, or advertisements accept a `fields` query
+//! parameter, a comma separated list of the fields to respond with, e.g.
+//! `fields=id,title,videos.id,videos.playbackUrl`. Fields of nested objects and arrays of objects
+//! are selected by path. Selecting a field selects all of its nested fields, and an empty list
+//! selects every field.
+//!
+//! Field names are validated against the [`Selectable`] type a route responds with, so unknown
+//! fields are rejected with `400 Bad Request` like any other malformed query parameter. Fields are
+//! selected from the serialized response, after `expand` and before the version 2 envelope.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{Debug, Formatter},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use rocket::{
+    form::{self, FromFormField, ValueField},
+    serde::json::Value,
+};
+use serde::{ser::Error as _, Serialize, Serializer};
+
+use crate::{
+    controller::version::Enveloped,
+    service::{advertisement::Advertisement, container::Container, image::Image, video::Video},
+};
+
+/* ******************************************* Field ******************************************** */
+
+/// A field of a [`Selectable`] type, by its serialized name.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::fields::Field;
+///
+/// const IMAGE: &[Field] = &[Field::new("id"), Field::new("url")];
+/// let images: Field = Field::nested("images", IMAGE);
+///
+/// assert_eq!(images.name, "images");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Field {
+    /// Serialized name of the field.
+    pub name: &'static str,
+    /// Fields of the object, or of each object in the array, the field holds, if they can be
+    /// selected.
+    pub nested: &'static [Field],
+}
+
+impl Field {
+    /// Construct a field without selectable nested fields.
+    pub const fn new(name: &'static str) -> Self {
+        Field { name, nested: &[] }
+    }
+
+    /// Construct a field with selectable nested fields.
+    pub const fn nested(name: &'static str, nested: &'static [Field]) -> Self {
+        Field { name, nested }
+    }
+}
+
+/* ***************************************** Selectable ***************************************** */
+
+/// Response data whose fields can be selected with the `fields` query parameter.
+pub trait Selectable: Serialize {
+    /// Fields which can be selected.
+    const FIELDS: &'static [Field];
+}
+
+impl Selectable for Advertisement {
+    const FIELDS: &'static [Field] = &[Field::new("id"), Field::new("name"), Field::new("url")];
+}
+
+impl Selectable for Image {
+    const FIELDS: &'static [Field] = &[Field::new("id"), Field::new("name"), Field::new("url")];
+}
+
+impl Selectable for Video {
+    const FIELDS: &'static [Field] = &[
+        Field::new("assets"),
+        Field::new("description"),
+        Field::new("expiresAt"),
+        Field::new("id"),
+        Field::new("playbackUrl"),
+        Field::new("title"),
+        Field::new("type"),
+    ];
+}
+
+impl Selectable for Container {
+    const FIELDS: &'static [Field] = &[
+        Field::nested("ads", Advertisement::FIELDS),
+        Field::new("id"),
+        Field::nested("images", Image::FIELDS),
+        Field::new("title"),
+        Field::nested("videos", Video::FIELDS),
+        Field::new("description"),
+        Field::new("heroImageId"),
+        Field::new("featured"),
+        Field::new("displayOrder"),
+        Field::new("status"),
+    ];
+}
+
+/* ******************************************* Fields ******************************************* */
+
+/// Fields of `T` selected with the `fields` query parameter.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{controller::fields::Fields, service::image::Image};
+///
+/// let fields: Result<Fields<Image>, String> = "id,url".parse();
+///
+/// assert!(fields.is_ok());
+/// assert!("id,colour".parse::<Fields<Image>>().is_err());
+/// ```
+pub struct Fields<T> {
+    selection: Selection,
+    selectable: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Fields<T> {
+    fn clone(&self) -> Self {
+        Fields {
+            selection: self.selection.clone(),
+            selectable: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for Fields<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Fields").field(&self.selection).finish()
+    }
+}
+
+impl<T> Eq for Fields<T> {}
+
+impl<T> PartialEq for Fields<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.selection == other.selection
+    }
+}
+
+impl<T: Selectable> Fields<T> {
+    /// Keep only the selected fields of a serialized `T`, or of each `T` in a serialized array.
+    pub fn project(&self, value: Value) -> Value {
+        match self.selection.0.is_empty() {
+            true => value,
+            false => self.selection.project(value),
+        }
+    }
+}
+
+impl<T: Selectable> FromStr for Fields<T> {
+    type Err = String;
+
+    fn from_str(fields: &str) -> Result<Self, Self::Err> {
+        let mut selection: Selection = Selection::default();
+
+        for path in fields.split(',').map(str::trim) {
+            if path.is_empty() {
+                continue;
+            }
+
+            selection.insert(&path.split('.').collect::<Vec<&str>>());
+        }
+
+        let unknown: Vec<String> = selection.unknown(T::FIELDS, "");
+
+        match unknown.is_empty() {
+            true => Ok(Fields {
+                selection,
+                selectable: PhantomData,
+            }),
+            false => Err(format!(
+                "unknown fields {}, expected any of {}",
+                unknown.join(", "),
+                field_paths(T::FIELDS).join(", ")
+            )),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'v, T: Selectable + Send> FromFormField<'v> for Fields<T> {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        field
+            .value
+            .parse()
+            .map_err(|err: String| form::Error::validation(err).into())
+    }
+}
+
+/// Tree of selected field names. A field selected as a whole has no nested [`Selection`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+struct Selection(BTreeMap<String, Option<Selection>>);
+
+impl Selection {
+    /// Select the field at `path`, such as `["videos", "id"]`.
+    fn insert(&mut self, path: &[&str]) {
+        if let Some((name, rest)) = path.split_first() {
+            let nested: &mut Option<Selection> = self
+                .0
+                .entry(name.to_string())
+                .or_insert_with(|| Some(Selection::default()));
+
+            match rest.is_empty() {
+                true => *nested = None,
+                false => {
+                    if let Some(nested) = nested {
+                        nested.insert(rest);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the paths of selected fields which aren't in `fields`, prefixed with `prefix`.
+    fn unknown(&self, fields: &[Field], prefix: &str) -> Vec<String> {
+        self.0
+            .iter()
+            .flat_map(|(name, nested)| {
+                let path: String = format!("{}{}", prefix, name);
+
+                match (fields.iter().find(|field| field.name == name), nested) {
+                    (None, _) => vec![path],
+                    (Some(_), None) => Vec::new(),
+                    (Some(field), Some(nested)) => {
+                        nested.unknown(field.nested, &format!("{}.", path))
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Keep only the selected fields of an object, or of each object in an array.
+    fn project(&self, value: Value) -> Value {
+        match value {
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .filter_map(|(name, value)| match self.0.get(&name)? {
+                        Some(nested) => Some((name, nested.project(value))),
+                        None => Some((name, value)),
+                    })
+                    .collect(),
+            ),
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(|item| self.project(item)).collect())
+            }
+            value => value,
+        }
+    }
+}
+
+/* ******************************************* Sparse ******************************************* */
+
+/// Response data which serializes only the [`Fields`] selected by the request, or every field if
+/// none were selected.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     controller::fields::{Fields, Sparse},
+///     service::image::Image,
+///     types::ImageId,
+/// };
+///
+/// let image: Image = Image::new(
+///     ImageId(1),
+///     "Image".to_string(),
+///     "https://example.com/image".to_string(),
+/// );
+/// let fields: Fields<Image> = "url".parse().unwrap();
+///
+/// assert_eq!(
+///     serde_json::to_string(&Sparse::new(image, Some(fields))).unwrap(),
+///     r#"{"url":"https://example.com/image"}"#
+/// );
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Sparse<T> {
+    value: T,
+    fields: Option<Fields<T>>,
+}
+
+impl<T: Selectable> Sparse<T> {
+    /// Construct a new Sparse.
+    pub fn new(value: T, fields: Option<Fields<T>>) -> Self {
+        Sparse { value, fields }
+    }
+
+    /// Get the complete value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Selectable> Serialize for Sparse<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.fields {
+            Some(fields) => fields
+                .project(serde_json::to_value(&self.value).map_err(S::Error::custom)?)
+                .serialize(serializer),
+            None => self.value.serialize(serializer),
+        }
+    }
+}
+
+impl<T: Selectable + Enveloped> Enveloped for Sparse<T> {
+    type Data = Self;
+
+    fn into_data(self) -> Self::Data {
+        self
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.value.warnings()
+    }
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Get the path of every field in `fields` and their nested fields, e.g. `videos.id`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     controller::fields::{field_paths, Selectable},
+///     service::image::Image,
+/// };
+///
+/// assert_eq!(field_paths(Image::FIELDS), vec!["id", "name", "url"]);
+/// ```
+pub fn field_paths(fields: &[Field]) -> Vec<String> {
+    fields
+        .iter()
+        .flat_map(|field| {
+            std::iter::once(field.name.to_string()).chain(
+                field_paths(field.nested)
+                    .into_iter()
+                    .map(move |path| format!("{}.{}", field.name, path)),
+            )
+        })
+        .collect()
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use rocket::serde::json::{json, Value};
+    use schemars::{gen::SchemaGenerator, JsonSchema};
+
+    use crate::{
+        service::{advertisement::Advertisement, container::Container, image::Image, video::Video},
+        types::{ImageId, VideoId, VideoType},
+    };
+
+    use super::{Field, Fields, Selectable, Sparse};
+
+    fn schema_fields<T: JsonSchema>() -> Vec<String> {
+        match SchemaGenerator::default()
+            .root_schema_for::<T>()
+            .schema
+            .object
+        {
+            Some(object) => object.properties.keys().cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn selectable_fields<T: Selectable>() -> Vec<String> {
+        let mut fields: Vec<String> = T::FIELDS
+            .iter()
+            .map(|field: &Field| field.name.to_string())
+            .collect();
+
+        fields.sort();
+        fields
+    }
+
+    fn video() -> Video {
+        Video::builder(VideoId(2))
+            .description("Description".to_string())
+            .playback_url("https://example.com/video".to_string())
+            .title("Video".to_string())
+            .r#type(VideoType::Movie)
+            .build()
+    }
+
+    #[test]
+    fn select_every_serialized_field() {
+        // Given
+        let selectable: Vec<Vec<String>> = vec![
+            selectable_fields::<Advertisement>(),
+            selectable_fields::<Container>(),
+            selectable_fields::<Image>(),
+            selectable_fields::<Video>(),
+        ];
+
+        // When
+        let serialized: Vec<Vec<String>> = vec![
+            schema_fields::<Advertisement>(),
+            schema_fields::<Container>(),
+            schema_fields::<Image>(),
+            schema_fields::<Video>(),
+        ];
+
+        // Then
+        assert_eq!(selectable, serialized);
+    }
+
+    #[test]
+    fn project_nested_fields() {
+        // Given
+        let fields: Fields<Container> = "id,title,videos.id,videos.playbackUrl".parse().unwrap();
+        let container: Value = json!({
+            "id": 1,
+            "title": "Container",
+            "description": "Description",
+            "images": [{"id": 3, "name": "Image", "url": "https://example.com/image"}],
+            "videos": [
+                {"id": 2, "title": "Video", "playbackUrl": "https://example.com/video"},
+            ],
+        });
+
+        // When
+        let actual: Value = fields.project(container);
+
+        // Then
+        assert_eq!(
+            actual,
+            json!({
+                "id": 1,
+                "title": "Container",
+                "videos": [{"id": 2, "playbackUrl": "https://example.com/video"}],
+            })
+        );
+    }
+
+    #[test]
+    fn select_whole_nested_field() {
+        // Given
+        let fields: Fields<Container> = "videos.id,videos".parse().unwrap();
+
+        // When
+        let actual: Value =
+            fields.project(json!({"id": 1, "videos": [{"id": 2, "title": "Video"}]}));
+
+        // Then
+        assert_eq!(actual, json!({"videos": [{"id": 2, "title": "Video"}]}));
+    }
+
+    #[test]
+    fn reject_unknown_fields() {
+        // Given
+        let fields: &str = "id,colour,videos.playbackUrl,videos.colour,title.length";
+
+        // When
+        let result = fields.parse::<Fields<Container>>();
+
+        // Then
+        match result {
+            Ok(_) => panic!("Unknown fields should be rejected"),
+            Err(err) => assert!(
+                err.starts_with("unknown fields colour, title.length, videos.colour"),
+                "Unexpected error {}",
+                err
+            ),
+        }
+    }
+
+    #[test]
+    fn serialize_selected_fields() {
+        // Given
+        let fields: Fields<Video> = "id,title".parse().unwrap();
+        let image: Image = Image::new(
+            ImageId(3),
+            "Image".to_string(),
+            "https://example.com/image".to_string(),
+        );
+
+        // When
+        let sparse: Value = serde_json::to_value(Sparse::new(video(), Some(fields))).unwrap();
+        let complete: Value = serde_json::to_value(Sparse::new(image.clone(), None)).unwrap();
+
+        // Then
+        assert_eq!(sparse, json!({"id": 2, "title": "Video"}));
+        assert_eq!(complete, serde_json::to_value(image).unwrap());
+    }
+}
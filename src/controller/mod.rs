@@ -1,15 +1,74 @@
 //! Rocket Container controller layer.
+//!
+//! Routes under `/admin` require an API key with the `admin` scope, and every other route but
+//! `/openapi.json` one with the `catalog:read` scope, once API keys are configured. See [`auth`].
+//! Every route is rate limited per client once rate limits are configured. See [`rate_limit`].
+//! Routes which respond with containers, videos, images, or advertisements respond with only the
+//! fields selected by their `fields` query parameter. See [`fields`]. Collection routes export
+//! NDJSON or CSV instead of JSON if the `Accept` header prefers them. See [`export`].
+//!
+//! The routes are described by an OpenAPI document served at `/openapi.json`. See [`openapi`].
 
+use chrono::Utc;
 use log::{error, trace};
-use rocket::{get, serde::json::Json, Responder, State};
-use serde::Serialize;
-
-use crate::service::{
-    advertisement::Advertisement,
-    container::{Container, ContainerService},
-    image::Image,
-    video::Video,
+use rocket::{
+    data::{ByteUnit, Capped, Limits, ToByteUnit},
+    delete,
+    form::{Errors, Strict},
+    get, post, put,
+    response::status::NoContent,
+    routes,
+    serde::json::{self, Json, Value},
+    Data, Responder, Route, State,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    controller::{
+        auth::{AdminKey, CatalogKey},
+        export::{Export, ExportRequest},
+        fields::Sparse,
+        openapi::OpenApi,
+        page::Page,
+        query::{
+            validate, AssetsQuery, CollectionQuery, ContainerQuery, ContainersQuery, ExpiringQuery,
+            FieldsQuery, ReportFormat, VideoQuery, VideosQuery,
+        },
+        rate_limit::RateLimit,
+        version::Versioned,
+    },
+    service::{
+        advertisement::Advertisement,
+        audit::AuditReport,
+        catalog::CatalogSnapshot,
+        container::{Container, ContainerBatch, ContainerService},
+        export::{advertisements_to_csv, containers_to_csv, images_to_csv, videos_to_csv},
+        image::Image,
+        metadata::ContainerMetadata,
+        report::{self, ExpiringVideo},
+        video::{Video, VideoAsset},
+    },
+    types::{self, AdvertisementId, ContainerId, ErrorKind, ImageId, VideoId},
+};
+
+pub mod auth;
+pub mod compression;
+pub mod cors;
+pub mod export;
+pub mod fields;
+pub mod openapi;
+pub mod page;
+pub mod query;
+pub mod rate_limit;
+pub mod version;
+
+/// Name of the body size limit of `PUT /admin/snapshot`, configurable as `limits.snapshot`.
+pub const SNAPSHOT_LIMIT: &str = "snapshot";
+
+/// Body size limit of `PUT /admin/snapshot` if `limits.snapshot` isn't configured.
+const DEFAULT_SNAPSHOT_LIMIT: u64 = 64;
 
 /* ************************************** Error Responder *************************************** */
 
@@ -35,9 +94,21 @@ pub enum Error {
     /// 400 - Bad Request.
     #[response(status = 400, content_type = "json")]
     BadRequest(Json<ErrorResponse>),
+    /// 401 - Unauthorized.
+    #[response(status = 401, content_type = "json")]
+    Unauthorized(Json<ErrorResponse>),
+    /// 403 - Forbidden.
+    #[response(status = 403, content_type = "json")]
+    Forbidden(Json<ErrorResponse>),
+    /// 429 - Too Many Requests.
+    #[response(status = 429, content_type = "json")]
+    TooManyRequests(Json<ErrorResponse>),
     /// 404 - Not Found.
     #[response(status = 404, content_type = "json")]
     NotFound(Json<ErrorResponse>),
+    /// 413 - Payload Too Large.
+    #[response(status = 413, content_type = "json")]
+    PayloadTooLarge(Json<ErrorResponse>),
     /// 500 - Internal Server Error.
     #[response(status = 500, content_type = "json")]
     InternalServiceError(Json<ErrorResponse>),
@@ -60,28 +131,67 @@ pub enum Error {
 ///     message: "No advertisements found for this container".to_string(),
 /// })));
 /// ```
-#[derive(Debug, Serialize, Responder)]
+#[derive(Debug, JsonSchema, Serialize, Responder)]
 pub struct ErrorResponse {
     /// Error message.
     pub message: String,
 }
 
+/* *********************************** ContainerBatchRequest ************************************ */
+
+/// Body of `POST /containers:batchGet`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{controller::ContainerBatchRequest, types::ContainerId};
+///
+/// let request: ContainerBatchRequest = serde_json::from_str(r#"{"ids":[1,5,9]}"#).unwrap();
+///
+/// assert_eq!(request.ids, vec![ContainerId(1), ContainerId(5), ContainerId(9)]);
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ContainerBatchRequest {
+    /// IDs of the containers to get.
+    pub ids: Vec<ContainerId>,
+}
+
+/* ************************************** Report Responder ************************************** */
+
+/// Report Responder.
+///
+/// Reports are returned as JSON, or exported as CSV.
+#[derive(Debug, Responder)]
+pub enum Report<T> {
+    /// JSON report.
+    Json(Versioned<T>),
+    /// CSV report.
+    #[response(content_type = "text/csv")]
+    Csv(String),
+}
+
 /* ************************************** Request Result **************************************** */
 
 /// Controller result.
 ///
-/// An alias for [`std::result::Result`] where Ok is a [`Json`] of `T` and Err is an [`Error`].
+/// An alias for [`std::result::Result`] where Ok is a [`Versioned`] `T` and Err is an [`Error`].
 ///
 /// # Examples
 ///
 /// ```rust
 /// use rocket_container::{
-///     controller::{Error, ErrorResponse, Result},
-///     service::advertisement::Advertisement
+///     controller::{version::Versioned, Result},
+///     service::advertisement::Advertisement,
+///     types::AdvertisementId,
 /// };
-/// use rocket::serde::json::Json;
 ///
-/// let ok: Result<Advertisement> = Ok(Json(advertisement));
+/// let advertisement: Advertisement = Advertisement::new(
+///     AdvertisementId(1),
+///     "Advertisement".to_string(),
+///     "https://example.com/ad".to_string(),
+/// );
+/// let ok: Result<Advertisement> = Ok(Versioned(advertisement));
 /// ```
 ///
 /// ```rust
@@ -95,13 +205,46 @@ pub struct ErrorResponse {
 ///     message: "No advertisements found for this container".to_string(),
 /// })));
 /// ```
-pub type Result<T> = std::result::Result<Json<T>, Error>;
+pub type Result<T> = std::result::Result<Versioned<T>, Error>;
 
 /* ************************************** GET /containers *************************************** */
 
 /// GET /containers.
 ///
-/// Controller for getting all containers.
+/// Controller for getting a page of containers.
+///
+/// # Query Parameters
+///
+/// - `sort`: Field to sort containers by, one of `id` (default), `title`, `videoCount`, or
+///   `displayOrder`.
+/// - `order`: Container sort order, either `asc` (default) or `desc`.
+/// - `videos.sort`: Field to sort videos in each container by, one of `id` (default), `title`,
+///   `type`, or `expirationDate`.
+/// - `videos.order`: Video sort order, either `asc` (default) or `desc`.
+/// - `type`: Comma separated list of video types, e.g. `MOVIE,EPISODE`.
+/// - `hasAds`: Only containers with (`true`) or without (`false`) advertisements.
+/// - `hasImages`: Only containers with (`true`) or without (`false`) images.
+/// - `status`: Comma separated list of container statuses, `empty`, `upcoming` (advertisements or
+///   images but no videos yet), or `active` (videos). E.g. `active` excludes incomplete containers.
+/// - `expiresBefore`: Only videos which expire before this RFC 3339 timestamp or ISO-8601 date.
+/// - `expiresAfter`: Only videos which expire after this RFC 3339 timestamp or ISO-8601 date, or
+///   never expire.
+/// - `includeExpired`: `true` to include videos which have already expired. Defaults to `false`.
+/// - `q`: Only videos whose title contains this string, ignoring case.
+/// - `expand`: `assets` to replace video asset references with the advertisements and images they
+///   reference.
+/// - `fields`: Comma separated list of the fields of each container to respond with, e.g.
+///   `id,title,videos.id,videos.playbackUrl`. See [`fields`].
+/// - `limit`, `cursor`: See [`PageQuery`](page::PageQuery).
+///
+/// Containers which have advertisements or images but no videos are listed too, with status
+/// `upcoming`. Video filters (`type`, `expiresBefore`, `expiresAfter`, and `q`) are applied to the
+/// videos in each container, and containers without any matching videos are excluded. A
+/// container's status is computed before its videos are filtered. Unknown parameters and
+/// malformed values are rejected with `400 Bad Request`.
+///
+/// Responds with JSON, or exports NDJSON or CSV with one row per video if the `Accept` header
+/// prefers them. See [`export`].
 ///
 /// # Examples
 ///
@@ -109,7 +252,9 @@ pub type Result<T> = std::result::Result<Json<T>, Error>;
 /// #[macro_use]
 /// extern crate rocket;
 ///
+/// use rocket::fairing::AdHoc;
 /// use rocket_container::{
+///     config::Config,
 ///     controller::list_containers,
 ///     service::container::ContainerService,
 /// };
@@ -119,16 +264,41 @@ pub type Result<T> = std::result::Result<Json<T>, Error>;
 ///     let container_service: ContainerService = ContainerService::default();
 ///
 ///     rocket::build()
+///         .attach(AdHoc::config::<Config>())
 ///         .manage(container_service)
 ///         .mount("/", routes![list_containers])
 /// }
 /// ```
-#[get("/containers")]
-pub async fn list_containers(service: &State<ContainerService>) -> Result<Vec<Container>> {
+#[get("/containers?<query..>")]
+pub async fn list_containers(
+    query: std::result::Result<Strict<ContainersQuery>, Errors<'_>>,
+    export: ExportRequest<'_>,
+    _limit: RateLimit,
+    _key: CatalogKey,
+    service: &State<ContainerService>,
+) -> std::result::Result<Export<Container>, Error> {
     trace!("GET /containers");
 
-    match service.inner().list_containers().await {
-        Ok(containers) => Ok(Json(containers)),
+    let query: ContainersQuery = validate(query)?;
+
+    match service
+        .inner()
+        .list_containers(&query.filter(), &query.sorting())
+        .await
+    {
+        Ok(containers) => {
+            let mut page: Page<Container> = export.paginate(containers, &query.page())?;
+
+            if query.expand_assets() {
+                service
+                    .inner()
+                    .expand_container_assets(&mut page.items)
+                    .await
+                    .map_err(|error| service_error(error, "assets".to_string()))?;
+            }
+
+            Ok(export.export(page, query.fields(), containers_to_csv))
+        }
         Err(error) => {
             error!("Error while getting containers {}", error);
 
@@ -145,6 +315,22 @@ pub async fn list_containers(service: &State<ContainerService>) -> Result<Vec<Co
 ///
 /// Controller for getting a container by ID.
 ///
+/// Containers which editors hid respond `404 Not Found`, as they are left out of
+/// [`list_containers`] and reported as missing by [`batch_get_containers`].
+///
+/// # Query Parameters
+///
+/// - `videos.sort`: Field to sort videos by, one of `id` (default), `title`, `type`, or
+///   `expirationDate`.
+/// - `videos.order`: Video sort order, either `asc` (default) or `desc`.
+/// - `includeExpired`: `true` to include videos which have already expired. Defaults to `false`.
+/// - `expand`: `assets` to replace video asset references with the advertisements and images they
+///   reference.
+/// - `fields`: Comma separated list of the fields of the container to respond with, e.g.
+///   `id,title,videos.id`. See [`fields`].
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`.
+///
 /// # Examples
 ///
 /// ```rust
@@ -165,20 +351,135 @@ pub async fn list_containers(service: &State<ContainerService>) -> Result<Vec<Co
 ///         .mount("/", routes![get_container])
 /// }
 /// ```
-#[get("/containers/<container_id>")]
+#[get("/containers/<container_id>?<query..>")]
 pub async fn get_container(
-    container_id: u32,
+    container_id: ContainerId,
+    query: std::result::Result<Strict<ContainerQuery>, Errors<'_>>,
+    _limit: RateLimit,
+    _key: CatalogKey,
     service: &State<ContainerService>,
-) -> Result<Container> {
+) -> Result<Sparse<Container>> {
     trace!("GET /containers/{}", container_id);
 
-    match service.inner().get_container(container_id).await {
-        Ok(container) => Ok(Json(container)),
+    let query: ContainerQuery = validate(query)?;
+
+    match service
+        .inner()
+        .get_container(container_id, &query.filter(), &query.sorting())
+        .await
+    {
+        Ok(mut container) => {
+            if query.expand_assets() {
+                service
+                    .inner()
+                    .expand_container_assets(std::slice::from_mut(&mut container))
+                    .await
+                    .map_err(|error| service_error(error, "assets".to_string()))?;
+            }
+
+            Ok(Versioned(Sparse::new(container, query.fields())))
+        }
+        Err(error) => Err(service_error(error, format!("container {}", container_id))),
+    }
+}
+
+/* ********************************* POST /containers:batchGet ********************************** */
+
+/// POST /containers:batchGet.
+///
+/// Controller for getting containers by ID in one request.
+///
+/// The body is a JSON object with the `ids` of the containers to get, e.g. `{"ids": [1, 5, 9]}`.
+/// Containers are resolved in a single pass over the catalog instead of one request each, and
+/// respond in the requested order with duplicate IDs ignored. IDs which aren't in the catalog, or
+/// whose containers are hidden, are listed in `missing`. Malformed bodies and more IDs than the
+/// maximum page size are rejected with `400 Bad Request`.
+///
+/// # Query Parameters
+///
+/// The same as [`get_container`], applied to each container.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket::fairing::AdHoc;
+/// use rocket_container::{
+///     config::Config,
+///     controller::batch_get_containers,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .attach(AdHoc::config::<Config>())
+///         .manage(container_service)
+///         .mount("/", routes![batch_get_containers])
+/// }
+/// ```
+#[post("/containers:batchGet?<query..>", data = "<request>")]
+pub async fn batch_get_containers(
+    request: std::result::Result<Json<ContainerBatchRequest>, json::Error<'_>>,
+    query: std::result::Result<Strict<ContainerQuery>, Errors<'_>>,
+    config: &State<Config>,
+    _limit: RateLimit,
+    _key: CatalogKey,
+    service: &State<ContainerService>,
+) -> Result<ContainerBatch<Sparse<Container>>> {
+    trace!("POST /containers:batchGet");
+
+    let query: ContainerQuery = validate(query)?;
+    let ids: Vec<ContainerId> = match request {
+        Ok(request) => request.into_inner().ids,
         Err(error) => {
-            error!("Error while getting container {} {}", container_id, error);
+            return Err(Error::BadRequest(Json(ErrorResponse {
+                message: format!("Invalid container batch request: {}", error),
+            })))
+        }
+    };
+
+    if ids.len() > config.pagination.max_page_size {
+        return Err(Error::BadRequest(Json(ErrorResponse {
+            message: format!(
+                "Too many container IDs: {}, expected at most {}",
+                ids.len(),
+                config.pagination.max_page_size
+            ),
+        })));
+    }
+
+    match service
+        .inner()
+        .get_containers(&ids, &query.filter(), &query.sorting())
+        .await
+    {
+        Ok(mut batch) => {
+            if query.expand_assets() {
+                service
+                    .inner()
+                    .expand_container_assets(&mut batch.containers)
+                    .await
+                    .map_err(|error| service_error(error, "assets".to_string()))?;
+            }
+
+            Ok(Versioned(
+                batch.map(|container| Sparse::new(container, query.fields())),
+            ))
+        }
+        Err(error) => {
+            error!(
+                "Error while getting containers {} {}",
+                types::array_to_string(&ids),
+                error
+            );
 
             Err(Error::InternalServiceError(Json(ErrorResponse {
-                message: "Error getting container".to_string(),
+                message: "Error getting containers".to_string(),
             })))
         }
     }
@@ -188,7 +489,16 @@ pub async fn get_container(
 
 /// GET /containers/<container_id>/ads.
 ///
-/// Controller for getting all advertisements for a container.
+/// Controller for getting a page of advertisements for a container, sorted by ID.
+///
+/// # Query Parameters
+///
+/// - `fields`: Comma separated list of the fields of each advertisement to respond with, e.g.
+///   `id,url`. See [`fields`].
+/// - `limit`, `cursor`: See [`PageQuery`](page::PageQuery).
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`. Responds with
+/// JSON, or exports NDJSON or CSV if the `Accept` header prefers them. See [`export`].
 ///
 /// # Examples
 ///
@@ -196,7 +506,9 @@ pub async fn get_container(
 /// #[macro_use]
 /// extern crate rocket;
 ///
+/// use rocket::fairing::AdHoc;
 /// use rocket_container::{
+///     config::Config,
 ///     controller::get_advertisements,
 ///     service::container::ContainerService,
 /// };
@@ -206,19 +518,30 @@ pub async fn get_container(
 ///     let container_service: ContainerService = ContainerService::default();
 ///
 ///     rocket::build()
+///         .attach(AdHoc::config::<Config>())
 ///         .manage(container_service)
 ///         .mount( "/", routes![get_advertisements])
 /// }
 /// ```
-#[get("/containers/<container_id>/ads")]
+#[get("/containers/<container_id>/ads?<query..>")]
 pub async fn get_advertisements(
-    container_id: u32,
+    container_id: ContainerId,
+    query: std::result::Result<Strict<CollectionQuery<Advertisement>>, Errors<'_>>,
+    export: ExportRequest<'_>,
+    _limit: RateLimit,
+    _key: CatalogKey,
     service: &State<ContainerService>,
-) -> Result<Vec<Advertisement>> {
+) -> std::result::Result<Export<Advertisement>, Error> {
     trace!("GET /containers/{}/ads", container_id);
 
+    let query: CollectionQuery<Advertisement> = validate(query)?;
+
     match service.inner().get_advertisements(container_id).await {
-        Ok(advertisements) => Ok(Json(advertisements)),
+        Ok(advertisements) => Ok(export.export(
+            export.paginate(advertisements, &query.page())?,
+            query.fields(),
+            |advertisements| advertisements_to_csv(container_id, advertisements),
+        )),
         Err(error) => {
             error!(
                 "Error while listing advertisements by container {} {}",
@@ -236,7 +559,16 @@ pub async fn get_advertisements(
 
 /// GET /containers/<container_id>/images.
 ///
-/// Controller for getting all images for a container.
+/// Controller for getting a page of images for a container, sorted by ID.
+///
+/// # Query Parameters
+///
+/// - `fields`: Comma separated list of the fields of each image to respond with, e.g. `id,url`.
+///   See [`fields`].
+/// - `limit`, `cursor`: See [`PageQuery`](page::PageQuery).
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`. Responds with
+/// JSON, or exports NDJSON or CSV if the `Accept` header prefers them. See [`export`].
 ///
 /// # Examples
 ///
@@ -244,7 +576,9 @@ pub async fn get_advertisements(
 /// #[macro_use]
 /// extern crate rocket;
 ///
+/// use rocket::fairing::AdHoc;
 /// use rocket_container::{
+///     config::Config,
 ///     controller::get_images,
 ///     service::container::ContainerService,
 /// };
@@ -254,19 +588,30 @@ pub async fn get_advertisements(
 ///     let container_service: ContainerService = ContainerService::default();
 ///
 ///     rocket::build()
+///         .attach(AdHoc::config::<Config>())
 ///         .manage(container_service)
 ///         .mount("/", routes![get_images])
 /// }
 /// ```
-#[get("/containers/<container_id>/images")]
+#[get("/containers/<container_id>/images?<query..>")]
 pub async fn get_images(
-    container_id: u32,
+    container_id: ContainerId,
+    query: std::result::Result<Strict<CollectionQuery<Image>>, Errors<'_>>,
+    export: ExportRequest<'_>,
+    _limit: RateLimit,
+    _key: CatalogKey,
     service: &State<ContainerService>,
-) -> Result<Vec<Image>> {
+) -> std::result::Result<Export<Image>, Error> {
     trace!("GET /containers/{}/images", container_id);
 
+    let query: CollectionQuery<Image> = validate(query)?;
+
     match service.inner().get_images(container_id).await {
-        Ok(images) => Ok(Json(images)),
+        Ok(images) => Ok(export.export(
+            export.paginate(images, &query.page())?,
+            query.fields(),
+            |images| images_to_csv(container_id, images),
+        )),
         Err(error) => {
             error!(
                 "Error while listing images by container {} {}",
@@ -284,7 +629,27 @@ pub async fn get_images(
 
 /// GET /containers/<container_id>/videos.
 ///
-/// Controller for getting all videos for a container.
+/// Controller for getting a page of videos for a container.
+///
+/// # Query Parameters
+///
+/// - `sort`: Field to sort videos by, one of `id` (default), `title`, `type`, or
+///   `expirationDate`.
+/// - `order`: Video sort order, either `asc` (default) or `desc`.
+/// - `type`: Comma separated list of video types, e.g. `MOVIE,EPISODE`.
+/// - `expiresBefore`: Only videos which expire before this RFC 3339 timestamp or ISO-8601 date.
+/// - `expiresAfter`: Only videos which expire after this RFC 3339 timestamp or ISO-8601 date, or
+///   never expire.
+/// - `includeExpired`: `true` to include videos which have already expired. Defaults to `false`.
+/// - `q`: Only videos whose title contains this string, ignoring case.
+/// - `expand`: `assets` to replace asset references with the advertisements and images they
+///   reference.
+/// - `fields`: Comma separated list of the fields of each video to respond with, e.g.
+///   `id,title,playbackUrl`. See [`fields`].
+/// - `limit`, `cursor`: See [`PageQuery`](page::PageQuery).
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`. Responds with
+/// JSON, or exports NDJSON or CSV if the `Accept` header prefers them. See [`export`].
 ///
 /// # Examples
 ///
@@ -292,7 +657,9 @@ pub async fn get_images(
 /// #[macro_use]
 /// extern crate rocket;
 ///
+/// use rocket::fairing::AdHoc;
 /// use rocket_container::{
+///     config::Config,
 ///     controller::get_videos,
 ///     service::container::ContainerService,
 /// };
@@ -302,19 +669,44 @@ pub async fn get_images(
 ///     let container_service: ContainerService = ContainerService::default();
 ///
 ///     rocket::build()
+///         .attach(AdHoc::config::<Config>())
 ///         .manage(container_service)
 ///         .mount("/", routes![get_videos])
 /// }
 /// ```
-#[get("/containers/<container_id>/videos")]
+#[get("/containers/<container_id>/videos?<query..>")]
 pub async fn get_videos(
-    container_id: u32,
+    container_id: ContainerId,
+    query: std::result::Result<Strict<VideosQuery>, Errors<'_>>,
+    export: ExportRequest<'_>,
+    _limit: RateLimit,
+    _key: CatalogKey,
     service: &State<ContainerService>,
-) -> Result<Vec<Video>> {
+) -> std::result::Result<Export<Video>, Error> {
     trace!("GET /containers/{}/videos", container_id);
 
-    match service.inner().get_videos(container_id).await {
-        Ok(videos) => Ok(Json(videos)),
+    let query: VideosQuery = validate(query)?;
+
+    match service
+        .inner()
+        .get_videos(container_id, &query.filter(), &query.sorting())
+        .await
+    {
+        Ok(videos) => {
+            let mut page: Page<Video> = export.paginate(videos, &query.page())?;
+
+            if query.expand_assets() {
+                service
+                    .inner()
+                    .expand_video_assets(&mut page.items)
+                    .await
+                    .map_err(|error| service_error(error, "assets".to_string()))?;
+            }
+
+            Ok(export.export(page, query.fields(), |videos| {
+                videos_to_csv(container_id, videos)
+            }))
+        }
         Err(error) => {
             error!(
                 "Error while listing videos by container {} {}",
@@ -327,3 +719,707 @@ pub async fn get_videos(
         }
     }
 }
+
+/* *************************** GET /advertisements/<advertisement_id> *************************** */
+
+/// GET /advertisements/<advertisement_id>.
+///
+/// Controller for getting an advertisement by ID. Unknown IDs return `404 Not Found`.
+///
+/// # Query Parameters
+///
+/// - `fields`: Comma separated list of the fields to respond with, e.g. `id,url`. See
+///   [`fields`].
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::get_advertisement,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![get_advertisement])
+/// }
+/// ```
+#[get("/advertisements/<advertisement_id>?<query..>")]
+pub async fn get_advertisement(
+    advertisement_id: AdvertisementId,
+    query: std::result::Result<Strict<FieldsQuery<Advertisement>>, Errors<'_>>,
+    _limit: RateLimit,
+    _key: CatalogKey,
+    service: &State<ContainerService>,
+) -> Result<Sparse<Advertisement>> {
+    trace!("GET /advertisements/{}", advertisement_id);
+
+    let query: FieldsQuery<Advertisement> = validate(query)?;
+
+    match service.inner().get_advertisement(advertisement_id).await {
+        Ok(advertisement) => Ok(Versioned(Sparse::new(advertisement, query.fields()))),
+        Err(error) => Err(service_error(
+            error,
+            format!("advertisement {}", advertisement_id),
+        )),
+    }
+}
+
+/* *********************************** GET /images/<image_id> *********************************** */
+
+/// GET /images/<image_id>.
+///
+/// Controller for getting an image by ID. Unknown IDs return `404 Not Found`.
+///
+/// # Query Parameters
+///
+/// - `fields`: Comma separated list of the fields to respond with, e.g. `id,url`. See
+///   [`fields`].
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::get_image,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![get_image])
+/// }
+/// ```
+#[get("/images/<image_id>?<query..>")]
+pub async fn get_image(
+    image_id: ImageId,
+    query: std::result::Result<Strict<FieldsQuery<Image>>, Errors<'_>>,
+    _limit: RateLimit,
+    _key: CatalogKey,
+    service: &State<ContainerService>,
+) -> Result<Sparse<Image>> {
+    trace!("GET /images/{}", image_id);
+
+    let query: FieldsQuery<Image> = validate(query)?;
+
+    match service.inner().get_image(image_id).await {
+        Ok(image) => Ok(Versioned(Sparse::new(image, query.fields()))),
+        Err(error) => Err(service_error(error, format!("image {}", image_id))),
+    }
+}
+
+/* *********************************** GET /videos/<video_id> *********************************** */
+
+/// GET /videos/<video_id>.
+///
+/// Controller for getting a video by ID. Unknown IDs and expired videos return `404 Not Found`.
+///
+/// # Query Parameters
+///
+/// - `includeExpired`: `true` to get the video even if it has expired. Defaults to `false`.
+/// - `expand`: `assets` to replace asset references with the advertisements and images they
+///   reference.
+/// - `fields`: Comma separated list of the fields to respond with, e.g. `id,title,playbackUrl`.
+///   See [`fields`].
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::get_video,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![get_video])
+/// }
+/// ```
+#[get("/videos/<video_id>?<query..>")]
+pub async fn get_video(
+    video_id: VideoId,
+    query: std::result::Result<Strict<VideoQuery>, Errors<'_>>,
+    _limit: RateLimit,
+    _key: CatalogKey,
+    service: &State<ContainerService>,
+) -> Result<Sparse<Video>> {
+    trace!("GET /videos/{}", video_id);
+
+    let query: VideoQuery = validate(query)?;
+
+    match service
+        .inner()
+        .get_video(video_id, query.include_expired.0.unwrap_or(false))
+        .await
+    {
+        Ok(mut video) => {
+            if query.expand_assets() {
+                service
+                    .inner()
+                    .expand_video_assets(std::slice::from_mut(&mut video))
+                    .await
+                    .map_err(|error| service_error(error, "assets".to_string()))?;
+            }
+
+            Ok(Versioned(Sparse::new(video, query.fields())))
+        }
+        Err(error) => Err(service_error(error, format!("video {}", video_id))),
+    }
+}
+
+/* ******************************* GET /videos/<video_id>/assets ******************************** */
+
+/// GET /videos/<video_id>/assets.
+///
+/// Controller for getting the assets of a video. Unknown IDs return `404 Not Found`.
+///
+/// # Query Parameters
+///
+/// - `assetType`: Only assets of this type, either `AD` or `IMAGE`.
+/// - `expand`: `assets` to replace asset references with the advertisements and images they
+///   reference.
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::get_video_assets,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![get_video_assets])
+/// }
+/// ```
+#[get("/videos/<video_id>/assets?<query..>")]
+pub async fn get_video_assets(
+    video_id: VideoId,
+    query: std::result::Result<Strict<AssetsQuery>, Errors<'_>>,
+    _limit: RateLimit,
+    _key: CatalogKey,
+    service: &State<ContainerService>,
+) -> Result<Vec<VideoAsset>> {
+    trace!("GET /videos/{}/assets", video_id);
+
+    let query: AssetsQuery = validate(query)?;
+
+    match service
+        .inner()
+        .get_video_assets(video_id, query.asset_type.0.clone())
+        .await
+    {
+        Ok(mut assets) => {
+            if query.expand_assets() {
+                service
+                    .inner()
+                    .expand_assets(&mut assets)
+                    .await
+                    .map_err(|error| service_error(error, "assets".to_string()))?;
+            }
+
+            Ok(Versioned(assets))
+        }
+        Err(error) => Err(service_error(
+            error,
+            format!("assets for video {}", video_id),
+        )),
+    }
+}
+
+/* ********************************** GET /reports/expiring ************************************ */
+
+/// GET /reports/expiring.
+///
+/// Controller for getting a report of videos which expire soon, with the container each video
+/// belongs to, sorted by when they expire.
+///
+/// # Query Parameters
+///
+/// - `within`: How far ahead to look for expiring videos, such as `7d` (default) or `12h`.
+/// - `format`: Report format, either `json` (default) or `csv`.
+///
+/// Unknown parameters and malformed values are rejected with `400 Bad Request`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::get_expiring_report,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![get_expiring_report])
+/// }
+/// ```
+#[get("/reports/expiring?<query..>")]
+pub async fn get_expiring_report(
+    query: std::result::Result<Strict<ExpiringQuery>, Errors<'_>>,
+    _limit: RateLimit,
+    _key: CatalogKey,
+    service: &State<ContainerService>,
+) -> std::result::Result<Report<Vec<ExpiringVideo>>, Error> {
+    trace!("GET /reports/expiring");
+
+    let query: ExpiringQuery = validate(query)?;
+
+    match service
+        .inner()
+        .list_expiring_videos(Utc::now(), query.within())
+        .await
+    {
+        Ok(videos) => match query.format() {
+            ReportFormat::Json => Ok(Report::Json(Versioned(videos))),
+            ReportFormat::Csv => Ok(Report::Csv(report::to_csv(&videos))),
+        },
+        Err(error) => Err(service_error(error, "expiring videos".to_string())),
+    }
+}
+
+/* *********************** GET /admin/containers/<container_id>/metadata ************************ */
+
+/// GET /admin/containers/<container_id>/metadata.
+///
+/// Controller for getting the editorial metadata of a container. Containers without metadata
+/// return `404 Not Found`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::get_container_metadata,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![get_container_metadata])
+/// }
+/// ```
+#[get("/admin/containers/<container_id>/metadata")]
+pub async fn get_container_metadata(
+    container_id: ContainerId,
+    _limit: RateLimit,
+    _key: AdminKey,
+    service: &State<ContainerService>,
+) -> Result<ContainerMetadata> {
+    trace!("GET /admin/containers/{}/metadata", container_id);
+
+    match service.inner().get_container_metadata(container_id) {
+        Ok(metadata) => Ok(Versioned(metadata)),
+        Err(error) => Err(service_error(
+            error,
+            format!("metadata for container {}", container_id),
+        )),
+    }
+}
+
+/* *********************** PUT /admin/containers/<container_id>/metadata ************************ */
+
+/// PUT /admin/containers/<container_id>/metadata.
+///
+/// Controller for setting the editorial metadata of a container, replacing any metadata it
+/// already has. Returns the stored metadata.
+///
+/// The body is a JSON object with the optional fields `title`, `description`, `heroImageId`,
+/// `featured`, `hidden`, and `displayOrder`. Malformed bodies are rejected with
+/// `400 Bad Request`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::put_container_metadata,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![put_container_metadata])
+/// }
+/// ```
+#[put("/admin/containers/<container_id>/metadata", data = "<metadata>")]
+pub async fn put_container_metadata(
+    container_id: ContainerId,
+    metadata: std::result::Result<Json<ContainerMetadata>, json::Error<'_>>,
+    _limit: RateLimit,
+    _key: AdminKey,
+    service: &State<ContainerService>,
+) -> Result<ContainerMetadata> {
+    trace!("PUT /admin/containers/{}/metadata", container_id);
+
+    let metadata: ContainerMetadata = match metadata {
+        Ok(metadata) => metadata.into_inner(),
+        Err(error) => {
+            return Err(Error::BadRequest(Json(ErrorResponse {
+                message: format!("Invalid container metadata: {}", error),
+            })))
+        }
+    };
+
+    match service
+        .inner()
+        .set_container_metadata(container_id, metadata.clone())
+        .await
+    {
+        Ok(()) => Ok(Versioned(metadata)),
+        Err(error) => {
+            error!(
+                "Error while setting metadata for container {} {}",
+                container_id, error
+            );
+
+            Err(Error::InternalServiceError(Json(ErrorResponse {
+                message: "Error setting container metadata".to_string(),
+            })))
+        }
+    }
+}
+
+/* ********************** DELETE /admin/containers/<container_id>/metadata ********************** */
+
+/// DELETE /admin/containers/<container_id>/metadata.
+///
+/// Controller for removing the editorial metadata of a container. Returns `204 No Content`, or
+/// `404 Not Found` if the container has no metadata.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::delete_container_metadata,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![delete_container_metadata])
+/// }
+/// ```
+#[delete("/admin/containers/<container_id>/metadata")]
+pub async fn delete_container_metadata(
+    container_id: ContainerId,
+    _limit: RateLimit,
+    _key: AdminKey,
+    service: &State<ContainerService>,
+) -> std::result::Result<NoContent, Error> {
+    trace!("DELETE /admin/containers/{}/metadata", container_id);
+
+    match service
+        .inner()
+        .delete_container_metadata(container_id)
+        .await
+    {
+        Ok(()) => Ok(NoContent),
+        Err(error) => Err(service_error(
+            error,
+            format!("metadata for container {}", container_id),
+        )),
+    }
+}
+
+/* ************************************ GET /admin/snapshot ************************************* */
+
+/// GET /admin/snapshot.
+///
+/// Controller for exporting every container, including expired videos, as a catalog snapshot.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::export_snapshot,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![export_snapshot])
+/// }
+/// ```
+#[get("/admin/snapshot")]
+pub async fn export_snapshot(
+    _limit: RateLimit,
+    _key: AdminKey,
+    service: &State<ContainerService>,
+) -> Result<CatalogSnapshot> {
+    trace!("GET /admin/snapshot");
+
+    match service.inner().export_catalog().await {
+        Ok(snapshot) => Ok(Versioned(snapshot)),
+        Err(error) => Err(service_error(error, "catalog snapshot".to_string())),
+    }
+}
+
+/* ************************************ PUT /admin/snapshot ************************************* */
+
+/// PUT /admin/snapshot.
+///
+/// Controller for importing a catalog snapshot, which containers are served from instead of
+/// Rocket Container's dependencies from then on. Returns `204 No Content`.
+///
+/// The body is a catalog snapshot as exported by [`export_snapshot`], optionally gzip compressed.
+/// Malformed snapshots and unsupported versions are rejected with `400 Bad Request`, and bodies
+/// larger than the [`SNAPSHOT_LIMIT`] limit (64 MiB by default) with `413 Payload Too Large`.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::import_snapshot,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![import_snapshot])
+/// }
+/// ```
+#[put("/admin/snapshot", data = "<snapshot>")]
+pub async fn import_snapshot(
+    snapshot: Data<'_>,
+    limits: &Limits,
+    _limit: RateLimit,
+    _key: AdminKey,
+    service: &State<ContainerService>,
+) -> std::result::Result<NoContent, Error> {
+    trace!("PUT /admin/snapshot");
+
+    let limit: ByteUnit = limits
+        .get(SNAPSHOT_LIMIT)
+        .unwrap_or(DEFAULT_SNAPSHOT_LIMIT.mebibytes());
+    let snapshot: Capped<Vec<u8>> = snapshot.open(limit).into_bytes().await.map_err(|err| {
+        Error::BadRequest(Json(ErrorResponse {
+            message: format!("Failed to read catalog snapshot: {}", err),
+        }))
+    })?;
+
+    if !snapshot.is_complete() {
+        return Err(Error::PayloadTooLarge(Json(ErrorResponse {
+            message: format!("Catalog snapshot is larger than {}", limit),
+        })));
+    }
+
+    match CatalogSnapshot::from_bytes(&snapshot) {
+        Ok(snapshot) => {
+            service.inner().import_catalog(snapshot);
+
+            Ok(NoContent)
+        }
+        Err(error) => Err(Error::BadRequest(Json(ErrorResponse {
+            message: error.message,
+        }))),
+    }
+}
+
+/* ************************************** GET /admin/audit ************************************** */
+
+/// GET /admin/audit.
+///
+/// Controller for auditing the catalogs of Rocket Container's dependencies for inconsistencies,
+/// such as asset references to advertisements or images which don't exist.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::get_audit,
+///     service::container::ContainerService,
+/// };
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     let container_service: ContainerService = ContainerService::default();
+///
+///     rocket::build()
+///         .manage(container_service)
+///         .mount("/", routes![get_audit])
+/// }
+/// ```
+#[get("/admin/audit")]
+pub async fn get_audit(
+    _limit: RateLimit,
+    _key: AdminKey,
+    service: &State<ContainerService>,
+) -> Result<AuditReport> {
+    trace!("GET /admin/audit");
+
+    match service.inner().audit().await {
+        Ok(report) => Ok(Versioned(report)),
+        Err(error) => Err(service_error(error, "audit".to_string())),
+    }
+}
+
+/* ************************************* GET /openapi.json ************************************** */
+
+/// GET /openapi.json.
+///
+/// Controller for getting the OpenAPI document of the routes Rocket Container has mounted, as
+/// built by the [`openapi_document`](openapi::openapi_document) fairing. Public, even when API
+/// keys are configured.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::controller::{get_image, get_openapi, openapi::openapi_document};
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build()
+///         .attach(openapi_document())
+///         .mount("/", routes![get_image, get_openapi])
+/// }
+/// ```
+#[get("/openapi.json")]
+pub fn get_openapi(_limit: RateLimit, document: &State<OpenApi>) -> Json<Value> {
+    trace!("GET /openapi.json");
+
+    Json(document.inner().0.clone())
+}
+
+/* ******************************************* Routes ******************************************* */
+
+/// Get every versioned controller route, which is every route but [`get_openapi`].
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{controller::routes, service::container::ContainerService};
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build()
+///         .manage(ContainerService::default())
+///         .mount("/", routes())
+/// }
+/// ```
+pub fn routes() -> Vec<Route> {
+    routes![
+        batch_get_containers,
+        delete_container_metadata,
+        export_snapshot,
+        get_advertisement,
+        get_advertisements,
+        get_audit,
+        get_container,
+        get_container_metadata,
+        get_expiring_report,
+        get_image,
+        get_images,
+        get_video,
+        get_video_assets,
+        get_videos,
+        import_snapshot,
+        list_containers,
+        put_container_metadata
+    ]
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Convert a service error into an [`Error`] response.
+///
+/// [`ErrorKind::NotFound`] becomes [`Error::NotFound`]; every other error is logged and becomes
+/// [`Error::InternalServiceError`].
+fn service_error(error: types::Error, resource: String) -> Error {
+    match error.kind {
+        ErrorKind::NotFound => Error::NotFound(Json(ErrorResponse {
+            message: format!("No {} found", resource),
+        })),
+        _ => {
+            error!("Error while getting {} {}", resource, error);
+
+            Error::InternalServiceError(Json(ErrorResponse {
+                message: format!("Error getting {}", resource),
+            }))
+        }
+    }
+}
@@ -0,0 +1,1097 @@
+//! OpenAPI document for the controller routes.
+//!
+//! [`openapi`] builds an [OpenAPI 3.0](https://spec.openapis.org/oas/v3.0.3) document from the
+//! routes a Rocket application has mounted, with schemas generated from the same serde types the
+//! routes respond with, in the shape of each route's API version (see [`version`][1]). The
+//! [`openapi_document`] fairing builds the document once the routes are mounted, and
+//! [`get_openapi`][2] serves it at `/openapi.json`.
+//!
+//! The published copy of the document is `openapi.json` at the root of the repository. Regenerate
+//! it with `cargo run -- openapi > openapi.json` whenever a route changes; a test fails until it
+//! matches the routes again.
+//!
+//! [1]: crate::controller::version
+//! [2]: crate::controller::get_openapi
+
+use log::{debug, warn};
+use rocket::{
+    fairing::AdHoc,
+    serde::json::{json, Value},
+    Route,
+};
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    JsonSchema,
+};
+use serde_json::Map;
+
+use crate::{
+    controller::{
+        auth::{Scope, API_KEY_HEADER},
+        batch_get_containers, delete_container_metadata,
+        export::{CSV_MEDIA_TYPE, NDJSON_MEDIA_TYPE},
+        export_snapshot,
+        fields::{field_paths, Selectable},
+        get_advertisement, get_advertisements, get_audit, get_container, get_container_metadata,
+        get_expiring_report, get_image, get_images, get_openapi, get_video, get_video_assets,
+        get_videos, import_snapshot, list_containers,
+        page::Page,
+        put_container_metadata,
+        query::{
+            AssetsQuery, CollectionQuery, ContainerQuery, ContainersQuery, ExpiringQuery,
+            FieldsQuery, QueryParameters, VideoQuery, VideosQuery,
+        },
+        version::{ApiVersion, Links, Meta, V1_MEDIA_TYPE, V2_MEDIA_TYPE},
+        ContainerBatchRequest, ErrorResponse,
+    },
+    service::{
+        advertisement::Advertisement,
+        audit::AuditReport,
+        catalog::CatalogSnapshot,
+        container::{Container, ContainerBatch},
+        image::Image,
+        metadata::ContainerMetadata,
+        report::ExpiringVideo,
+        video::Video,
+        video::VideoAsset,
+    },
+};
+
+/// Version of the OpenAPI specification the document follows.
+pub const OPENAPI_VERSION: &str = "3.0.3";
+
+/// Values of the `sort` query parameter of `GET /containers`.
+const CONTAINER_SORT_FIELDS: [&str; 4] = ["id", "title", "videoCount", "displayOrder"];
+
+/// Values of the `sort` and `videos.sort` query parameters.
+const VIDEO_SORT_FIELDS: [&str; 4] = ["id", "title", "type", "expirationDate"];
+
+/* ****************************************** Document ****************************************** */
+
+/// Build the OpenAPI document for `routes`.
+///
+/// Routes which aren't Rocket Container controller routes are left out of the document.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket::{routes, serde::json::Value};
+/// use rocket_container::controller::{get_image, openapi::openapi};
+///
+/// let document: Value = openapi(&routes![get_image]);
+///
+/// assert!(document["paths"]["/images/{image_id}"]["get"].is_object());
+/// ```
+pub fn openapi<'a>(routes: impl IntoIterator<Item = &'a Route>) -> Value {
+    let mut gen: SchemaGenerator = SchemaSettings::openapi3().into_generator();
+    let error: Value = schema::<ErrorResponse>(&mut gen);
+    let mut paths: Map<String, Value> = Map::new();
+    let mut schemas: Map<String, Value> = Map::new();
+    let documentation: Vec<(Route, Document)> = documentation();
+
+    for route in routes {
+        let (name, document): (&str, Document) =
+            match documentation.iter().find(|(documented, _)| {
+                documented.name == route.name && documented.method == route.method
+            }) {
+                Some((documented, document)) => {
+                    (documented.name.as_deref().unwrap_or_default(), *document)
+                }
+                None => {
+                    debug!("Leaving route {} out of the OpenAPI document", route);
+                    continue;
+                }
+            };
+        let operation: Operation = document(&mut gen);
+        let path: &str = route.uri.path();
+        let version: Option<ApiVersion> = ApiVersion::from_prefix(route.uri.base());
+        let operation: Operation = match version {
+            Some(ApiVersion::V1) => operation,
+            Some(ApiVersion::V2) => operation.envelope(&mut gen),
+            None => operation.negotiated(&mut gen),
+        };
+
+        paths.entry(openapi_path(path)).or_insert_with(|| json!({}))
+            [route.method.as_str().to_lowercase()] = operation.to_json(name, path, version, &error);
+    }
+
+    for (name, schema) in gen.take_definitions() {
+        let mut schema: Value = serde_json::to_value(schema).unwrap_or_default();
+
+        if let Some(Value::String(description)) = schema.get_mut("description") {
+            description.truncate(description.find("\n\n").unwrap_or(description.len()));
+        }
+
+        clean_descriptions(&mut schema);
+        schemas.insert(name, schema);
+    }
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "Rocket Container",
+            "description": env!("CARGO_PKG_DESCRIPTION"),
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas,
+            "securitySchemes": {
+                "apiKey": {
+                    "type": "apiKey",
+                    "in": "header",
+                    "name": API_KEY_HEADER,
+                },
+                "bearer": {
+                    "type": "http",
+                    "scheme": "bearer",
+                },
+            },
+        },
+        "security": [{"apiKey": []}, {"bearer": []}],
+    })
+}
+
+/* ****************************************** OpenApi ******************************************* */
+
+/// OpenAPI document of the mounted routes, managed as [`rocket::State`] by the
+/// [`openapi_document`] fairing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenApi(pub Value);
+
+/* ****************************************** Fairing ******************************************* */
+
+/// Fairing which builds the OpenAPI document of every mounted route on ignition, and manages it as
+/// [`OpenApi`].
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::controller::openapi::openapi_document;
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build().attach(openapi_document())
+/// }
+/// ```
+pub fn openapi_document() -> AdHoc {
+    AdHoc::on_ignite("OpenAPI document", |rocket| async move {
+        let document: Value = openapi(rocket.routes());
+
+        rocket.manage(OpenApi(document))
+    })
+}
+
+/* ***************************************** Operation ****************************************** */
+
+/// Builder of a route's [`Operation`], generating the schemas it refers to.
+type Document = fn(&mut SchemaGenerator) -> Operation;
+
+/// Documentation of a route.
+struct Operation {
+    /// Short summary of what the route does.
+    summary: &'static str,
+    /// Scope the route requires, or [None] if the route is public.
+    scope: Option<Scope>,
+    /// Query parameters.
+    query: Vec<Value>,
+    /// Request body, or [None] if the route doesn't take one.
+    body: Option<Value>,
+    /// Status of successful responses.
+    status: u16,
+    /// Successful response.
+    response: Value,
+    /// Schema of the data which version 2 wraps in an envelope, or [None] if the response has the
+    /// same shape in every version.
+    data: Option<Value>,
+    /// Whether the route exports NDJSON and CSV too. See [`export`][1].
+    ///
+    /// [1]: crate::controller::export
+    exports: bool,
+    /// Error responses besides those implied by the route's parameters, body, and scope.
+    errors: Vec<(u16, &'static str)>,
+}
+
+impl Operation {
+    /// Create a new [`Operation`] which responds `200 OK` with `response`.
+    fn new(summary: &'static str, scope: Option<Scope>, response: Value) -> Self {
+        let data: Value = response["content"]["application/json"]["schema"].clone();
+
+        Operation {
+            summary,
+            scope,
+            query: Vec::new(),
+            body: None,
+            status: 200,
+            response,
+            data: Some(data).filter(|data| !data.is_null()),
+            exports: false,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Add every query parameter `Q` parses. See [`query_parameter`].
+    fn query<Q: QueryParameters>(mut self) -> Self {
+        self.query
+            .extend(Q::NAMES.iter().map(|name| query_parameter(name)));
+        self
+    }
+
+    /// Document the `sort` query parameter with the `fields` to sort by.
+    fn sort(self, fields: &[&str]) -> Self {
+        self.refine(parameter(
+            "sort",
+            "Field to sort by.",
+            json!({"type": "string", "enum": fields, "default": "id"}),
+            json!("title"),
+        ))
+    }
+
+    /// Document the `fields` query parameter with the fields of `T` which can be selected.
+    fn fields<T: Selectable>(self, example: &str) -> Self {
+        self.refine(parameter(
+            "fields",
+            &format!(
+                "Comma separated list of fields to respond with, out of {}.",
+                field_paths(T::FIELDS)
+                    .iter()
+                    .map(|path| format!("`{}`", path))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            json!({"type": "string"}),
+            json!(example),
+        ))
+    }
+
+    /// Replace the query parameter with the name of `parameter`, which must already be added by
+    /// [`Operation::query`].
+    fn refine(mut self, parameter: Value) -> Self {
+        match self
+            .query
+            .iter_mut()
+            .find(|query| query["name"] == parameter["name"])
+        {
+            Some(query) => *query = parameter,
+            None => warn!("Query parameter {} isn't parsed", parameter["name"]),
+        }
+
+        self
+    }
+
+    /// Set the request body.
+    fn body(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Add an error response with `status`.
+    fn error(mut self, status: u16, description: &'static str) -> Self {
+        self.errors.push((status, description));
+        self
+    }
+
+    /// Respond `204 No Content` instead.
+    fn no_content(mut self) -> Self {
+        self.status = 204;
+        self.response = json!({"description": "No Content"});
+        self.data = None;
+        self
+    }
+
+    /// Set the response to a page of items with `schema`, which version 2 wraps as an array.
+    fn page(mut self, schema: Value) -> Self {
+        self.data = Some(json!({"type": "array", "items": schema}));
+        self
+    }
+
+    /// Export NDJSON records with `schema`, or CSV, if the `Accept` header prefers them.
+    fn exports(mut self, schema: Value) -> Self {
+        self.response["content"][NDJSON_MEDIA_TYPE] = json!({"schema": schema});
+        self.response["content"][CSV_MEDIA_TYPE] = json!({"schema": {"type": "string"}});
+        self.response["headers"] = json!({
+            "Link": {
+                "description": "Link to the next page of an export, if `limit` is given and it \
+                    isn't the last page.",
+                "schema": {"type": "string"},
+            },
+        });
+        self.exports = true;
+        self
+    }
+
+    /// Respond with the same shape in every version.
+    fn unversioned(mut self) -> Self {
+        self.data = None;
+        self
+    }
+
+    /// Wrap the JSON data of the successful response in a version 2 [`Envelope`].
+    fn envelope(mut self, gen: &mut SchemaGenerator) -> Self {
+        if let Some(data) = self.data.take() {
+            self.response["content"]["application/json"]["schema"] = envelope(data, gen);
+        }
+
+        self
+    }
+
+    /// Add the media types which select a version with the `Accept` header to the successful
+    /// response of an unversioned path, each with the shape of its version.
+    fn negotiated(mut self, gen: &mut SchemaGenerator) -> Self {
+        if let Some(data) = self.data.clone() {
+            self.response["content"][V1_MEDIA_TYPE] =
+                self.response["content"]["application/json"].clone();
+            self.response["content"][V2_MEDIA_TYPE] = json!({"schema": envelope(data, gen)});
+        }
+
+        self
+    }
+
+    /// Convert to an OpenAPI operation object for the route named `name` at `path`, mounted under
+    /// the prefix of `version`, or at its unversioned path if [None].
+    fn to_json(&self, name: &str, path: &str, version: Option<ApiVersion>, error: &Value) -> Value {
+        let path_parameters: Vec<Value> = path_parameters(path);
+        let mut responses: Map<String, Value> = Map::new();
+        let mut errors: Vec<(u16, &str)> = Vec::new();
+
+        if !self.query.is_empty() || self.body.is_some() {
+            errors.push((400, "Bad Request"));
+        }
+
+        if self.scope.is_some() {
+            errors.push((401, "Unauthorized"));
+            errors.push((403, "Forbidden"));
+        }
+
+        if !path_parameters.is_empty() {
+            errors.push((404, "Not Found"));
+        }
+
+        errors.extend(self.errors.iter().copied());
+        errors.push((429, "Too Many Requests"));
+
+        if self.scope.is_some() {
+            errors.push((500, "Internal Server Error"));
+        }
+
+        responses.insert(self.status.to_string(), self.response.clone());
+
+        for (status, description) in errors {
+            responses.insert(
+                status.to_string(),
+                json!({"description": description, "content": json_content(error.clone())}),
+            );
+        }
+
+        let parameters: Vec<Value> = path_parameters
+            .into_iter()
+            .chain(self.query.clone())
+            .collect();
+        let mut description: Vec<String> = Vec::new();
+        let mut operation: Value = json!({
+            "operationId": match version {
+                Some(version) => camel_case(&format!("{}_{}", version, name)),
+                None => camel_case(name),
+            },
+            "summary": self.summary,
+            "parameters": parameters,
+            "responses": responses,
+        });
+
+        if version.is_none() && self.data.is_some() {
+            description.push(format!(
+                "Responds like `{}{}`, or like `{}{}` if the `Accept` header is `{}`.",
+                ApiVersion::V1.prefix(),
+                path,
+                ApiVersion::V2.prefix(),
+                path,
+                V2_MEDIA_TYPE
+            ));
+        }
+
+        if self.exports {
+            description.push(format!(
+                "Exports one JSON record per line if the `Accept` header prefers `{}`, or CSV if \
+                it prefers `{}`, with every item unless `limit` is given.",
+                NDJSON_MEDIA_TYPE, CSV_MEDIA_TYPE
+            ));
+        }
+
+        match self.scope {
+            Some(scope) => {
+                description.push(format!("Requires an API key with the `{}` scope.", scope))
+            }
+            None => operation["security"] = json!([]),
+        }
+
+        if !description.is_empty() {
+            operation["description"] = json!(description.join(" "));
+        }
+
+        if let Some(body) = &self.body {
+            operation["requestBody"] = body.clone();
+        }
+
+        operation
+    }
+}
+
+/* ***************************************** Operations ***************************************** */
+
+/// Document every controller route, paired with the route it documents.
+///
+/// Routes come from their handlers, so documentation can't refer to a route which was renamed or
+/// removed.
+fn documentation() -> Vec<(Route, Document)> {
+    const CATALOG: Option<Scope> = Some(Scope::CatalogRead);
+    const ADMIN: Option<Scope> = Some(Scope::Admin);
+
+    vec![
+        (list_containers {}.into_route(), |gen| {
+            Operation::new(
+                "List containers",
+                CATALOG,
+                ok("A page of containers.", schema::<Page<Container>>(gen)),
+            )
+            .page(schema::<Container>(gen))
+            .exports(schema::<Container>(gen))
+            .query::<ContainersQuery>()
+            .sort(&CONTAINER_SORT_FIELDS)
+            .fields::<Container>("id,title,videos.id,videos.playbackUrl")
+        }),
+        (get_container {}.into_route(), |gen| {
+            Operation::new(
+                "Get a container",
+                CATALOG,
+                ok("The container.", schema::<Container>(gen)),
+            )
+            .query::<ContainerQuery>()
+            .fields::<Container>("id,title,videos.id")
+        }),
+        (batch_get_containers {}.into_route(), |gen| {
+            Operation::new(
+                "Get containers by ID",
+                CATALOG,
+                ok(
+                    "The containers, and the IDs which aren't in the catalog.",
+                    schema::<ContainerBatch>(gen),
+                ),
+            )
+            .query::<ContainerQuery>()
+            .fields::<Container>("id,title,videos.id")
+            .body(json!({
+                "required": true,
+                "content": json_content(schema::<ContainerBatchRequest>(gen)),
+            }))
+        }),
+        (get_advertisements {}.into_route(), |gen| {
+            Operation::new(
+                "List the advertisements of a container",
+                CATALOG,
+                ok(
+                    "A page of advertisements.",
+                    schema::<Page<Advertisement>>(gen),
+                ),
+            )
+            .page(schema::<Advertisement>(gen))
+            .exports(schema::<Advertisement>(gen))
+            .query::<CollectionQuery<Advertisement>>()
+            .fields::<Advertisement>("id,url")
+        }),
+        (get_images {}.into_route(), |gen| {
+            Operation::new(
+                "List the images of a container",
+                CATALOG,
+                ok("A page of images.", schema::<Page<Image>>(gen)),
+            )
+            .page(schema::<Image>(gen))
+            .exports(schema::<Image>(gen))
+            .query::<CollectionQuery<Image>>()
+            .fields::<Image>("id,url")
+        }),
+        (get_videos {}.into_route(), |gen| {
+            Operation::new(
+                "List the videos of a container",
+                CATALOG,
+                ok("A page of videos.", schema::<Page<Video>>(gen)),
+            )
+            .page(schema::<Video>(gen))
+            .exports(schema::<Video>(gen))
+            .query::<VideosQuery>()
+            .sort(&VIDEO_SORT_FIELDS)
+            .fields::<Video>("id,title,playbackUrl")
+        }),
+        (get_advertisement {}.into_route(), |gen| {
+            Operation::new(
+                "Get an advertisement",
+                CATALOG,
+                ok("The advertisement.", schema::<Advertisement>(gen)),
+            )
+            .query::<FieldsQuery<Advertisement>>()
+            .fields::<Advertisement>("id,url")
+        }),
+        (get_image {}.into_route(), |gen| {
+            Operation::new(
+                "Get an image",
+                CATALOG,
+                ok("The image.", schema::<Image>(gen)),
+            )
+            .query::<FieldsQuery<Image>>()
+            .fields::<Image>("id,url")
+        }),
+        (get_video {}.into_route(), |gen| {
+            Operation::new(
+                "Get a video",
+                CATALOG,
+                ok("The video.", schema::<Video>(gen)),
+            )
+            .query::<VideoQuery>()
+            .fields::<Video>("id,title,playbackUrl")
+        }),
+        (get_video_assets {}.into_route(), |gen| {
+            Operation::new(
+                "List the assets of a video",
+                CATALOG,
+                ok("The assets of the video.", schema::<Vec<VideoAsset>>(gen)),
+            )
+            .query::<AssetsQuery>()
+        }),
+        (get_expiring_report {}.into_route(), |gen| {
+            Operation::new(
+                "Report videos which expire soon",
+                CATALOG,
+                json!({
+                    "description": "Videos which expire soon, sorted by when they expire.",
+                    "content": {
+                        "application/json": {"schema": schema::<Vec<ExpiringVideo>>(gen)},
+                        "text/csv": {"schema": {"type": "string"}},
+                    },
+                }),
+            )
+            .query::<ExpiringQuery>()
+        }),
+        (get_container_metadata {}.into_route(), |gen| {
+            Operation::new(
+                "Get the editorial metadata of a container",
+                ADMIN,
+                ok("The container metadata.", schema::<ContainerMetadata>(gen)),
+            )
+        }),
+        (put_container_metadata {}.into_route(), |gen| {
+            Operation::new(
+                "Set the editorial metadata of a container",
+                ADMIN,
+                ok(
+                    "The stored container metadata.",
+                    schema::<ContainerMetadata>(gen),
+                ),
+            )
+            .body(json!({
+                "required": true,
+                "content": json_content(schema::<ContainerMetadata>(gen)),
+            }))
+        }),
+        (delete_container_metadata {}.into_route(), |_| {
+            Operation::new(
+                "Delete the editorial metadata of a container",
+                ADMIN,
+                Value::Null,
+            )
+            .no_content()
+        }),
+        (export_snapshot {}.into_route(), |gen| {
+            Operation::new(
+                "Export a catalog snapshot",
+                ADMIN,
+                ok("The catalog snapshot.", schema::<CatalogSnapshot>(gen)),
+            )
+        }),
+        (import_snapshot {}.into_route(), |gen| {
+            Operation::new("Import a catalog snapshot", ADMIN, Value::Null)
+                .body(json!({
+                    "required": true,
+                    "content": {
+                        "application/json": {"schema": schema::<CatalogSnapshot>(gen)},
+                        "application/gzip": {"schema": {"type": "string", "format": "binary"}},
+                    },
+                }))
+                .error(413, "Payload Too Large")
+                .no_content()
+        }),
+        (get_audit {}.into_route(), |gen| {
+            Operation::new(
+                "Audit the catalogs of Rocket Container's dependencies",
+                ADMIN,
+                ok("The audit report.", schema::<AuditReport>(gen)),
+            )
+        }),
+        (get_openapi {}.into_route(), |_| {
+            Operation::new(
+                "Get this OpenAPI document",
+                None,
+                ok("The OpenAPI document.", json!({"type": "object"})),
+            )
+            .unversioned()
+        }),
+    ]
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Generate the schema of `T`, a reference to a component schema for named types.
+fn schema<T: JsonSchema>(gen: &mut SchemaGenerator) -> Value {
+    serde_json::to_value(gen.subschema_for::<T>()).unwrap_or_default()
+}
+
+/// Rewrite the intra-doc links in descriptions generated from doc comments as code, e.g.
+/// ``[`Video`]`` as `` `Video` ``, and [None] as `` `null` ``.
+///
+/// Only the summary of each type's documentation is kept, since the rest is about using the type
+/// from Rust; see [`openapi`].
+fn clean_descriptions(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match value {
+                    Value::String(description) if key == "description" => {
+                        *description = description
+                            .replace("[None]", "`null`")
+                            .replace("[`None`]", "`null`")
+                            .replace("[`", "`")
+                            .replace("`]", "`");
+                    }
+                    _ => clean_descriptions(value),
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(clean_descriptions),
+        _ => {}
+    }
+}
+
+/// Convert a route's function name into an operation ID, e.g. `get_image` into `getImage`.
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .enumerate()
+        .map(|(i, word)| match (i, word.chars().next()) {
+            (0, _) | (_, None) => word.to_string(),
+            (_, Some(first)) => first.to_uppercase().chain(word.chars().skip(1)).collect(),
+        })
+        .collect()
+}
+
+/// Build the schema of a version 2 [`Envelope`] around `data`.
+fn envelope(data: Value, gen: &mut SchemaGenerator) -> Value {
+    json!({
+        "type": "object",
+        "required": ["data", "meta", "links", "warnings"],
+        "properties": {
+            "data": data,
+            "meta": schema::<Meta>(gen),
+            "links": schema::<Links>(gen),
+            "warnings": {"type": "array", "items": {"type": "string"}},
+        },
+    })
+}
+
+/// Build a successful JSON response.
+fn ok(description: &str, schema: Value) -> Value {
+    json!({"description": description, "content": json_content(schema)})
+}
+
+/// Build the content of a JSON request or response body.
+fn json_content(schema: Value) -> Value {
+    json!({"application/json": {"schema": schema}})
+}
+
+/// Build a query parameter.
+fn parameter(name: &str, description: &str, schema: Value, example: Value) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "description": description,
+        "required": false,
+        "schema": schema,
+        "example": example,
+    })
+}
+
+/// Build a query parameter by name, as parsed by [`query`](crate::controller::query).
+///
+/// Parameters which aren't documented get an entry without a description, which fails the
+/// `document_every_route` test.
+fn query_parameter(name: &str) -> Value {
+    let order: Value = json!({"type": "string", "enum": ["asc", "desc"], "default": "asc"});
+    let flag: Value = json!({"type": "boolean"});
+    let string: Value = json!({"type": "string"});
+
+    match name {
+        "limit" => parameter(
+            name,
+            "Maximum number of items to return.",
+            json!({"type": "integer", "minimum": 1}),
+            json!(10),
+        ),
+        "cursor" => parameter(
+            name,
+            "Cursor from the `next` link of a previous page.",
+            json!({"type": "integer", "minimum": 0}),
+            json!(0),
+        ),
+        "sort" => parameter(name, "Field to sort by.", string, json!("title")),
+        "order" => parameter(name, "Sort order.", order, json!("desc")),
+        "videos.sort" => parameter(
+            name,
+            "Field to sort videos by.",
+            json!({"type": "string", "enum": VIDEO_SORT_FIELDS, "default": "id"}),
+            json!("expirationDate"),
+        ),
+        "videos.order" => parameter(name, "Video sort order.", order, json!("desc")),
+        "type" => parameter(
+            name,
+            "Comma separated list of video types.",
+            string,
+            json!("MOVIE,EPISODE"),
+        ),
+        "hasAds" => parameter(
+            name,
+            "Only containers with (`true`) or without (`false`) advertisements.",
+            flag,
+            json!(true),
+        ),
+        "hasImages" => parameter(
+            name,
+            "Only containers with (`true`) or without (`false`) images.",
+            flag,
+            json!(false),
+        ),
+        "status" => parameter(
+            name,
+            "Comma separated list of container statuses: `empty`, `upcoming`, or `active`.",
+            string,
+            json!("upcoming,active"),
+        ),
+        "expiresBefore" => parameter(
+            name,
+            "Only videos which expire before this RFC 3339 timestamp or ISO-8601 date.",
+            string,
+            json!("2022-04-01"),
+        ),
+        "expiresAfter" => parameter(
+            name,
+            "Only videos which expire after this RFC 3339 timestamp or ISO-8601 date, or never \
+            expire.",
+            string,
+            json!("2022-03-01T00:00:00Z"),
+        ),
+        "includeExpired" => parameter(
+            name,
+            "Include videos which have already expired.",
+            json!({"type": "boolean", "default": false}),
+            json!(true),
+        ),
+        "q" => parameter(
+            name,
+            "Only videos whose title contains this string, ignoring case.",
+            string,
+            json!("rocket"),
+        ),
+        "expand" => parameter(
+            name,
+            "`assets` to replace asset references with the advertisements and images they \
+            reference.",
+            json!({"type": "string", "enum": ["assets"]}),
+            json!("assets"),
+        ),
+        "assetType" => parameter(
+            name,
+            "Only assets of this type.",
+            json!({"type": "string", "enum": ["AD", "IMAGE"]}),
+            json!("AD"),
+        ),
+        "within" => parameter(
+            name,
+            "How far ahead to look for expiring videos, such as `7d` or `12h`.",
+            json!({"type": "string", "default": "7d"}),
+            json!("12h"),
+        ),
+        "format" => parameter(
+            name,
+            "Report format.",
+            json!({"type": "string", "enum": ["json", "csv"], "default": "json"}),
+            json!("csv"),
+        ),
+        "fields" => parameter(
+            name,
+            "Comma separated list of fields to respond with.",
+            string,
+            json!("id"),
+        ),
+        _ => {
+            warn!("Query parameter {} isn't documented", name);
+
+            parameter(name, "", string, Value::Null)
+        }
+    }
+}
+
+/// Build the path parameters of a Rocket route path, such as `container_id` for
+/// `/containers/<container_id>`. Every path parameter is an ID.
+fn path_parameters(path: &str) -> Vec<Value> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('<')?.strip_suffix('>'))
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "description": format!(
+                    "Unique {} identifier.",
+                    name.trim_end_matches("_id").replace('_', " ")
+                ),
+                "required": true,
+                "schema": {"type": "integer", "format": "uint32", "minimum": 0},
+            })
+        })
+        .collect()
+}
+
+/// Convert a Rocket route path into an OpenAPI path, e.g. `/images/<image_id>` into
+/// `/images/{image_id}`.
+fn openapi_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| match segment.strip_prefix('<') {
+            Some(name) => format!("{{{}}}", name.trim_end_matches('>')),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use std::fmt::Debug;
+
+    use rocket::{
+        form::{Form, FromForm, Strict},
+        routes,
+        serde::json::{json, Value},
+        Route,
+    };
+
+    use crate::{
+        controller::{
+            batch_get_containers, get_advertisement, get_advertisements, get_container,
+            get_expiring_report, get_image, get_images, get_openapi, get_video, get_video_assets,
+            get_videos, list_containers,
+            query::{
+                AssetsQuery, CollectionQuery, ContainerQuery, ContainersQuery, ExpiringQuery,
+                FieldsQuery, VideoQuery, VideosQuery,
+            },
+            version::{V1_MEDIA_TYPE, V2_MEDIA_TYPE},
+        },
+        service::{advertisement::Advertisement, image::Image},
+    };
+
+    use super::{openapi, openapi_path, query_parameter};
+
+    fn routes() -> Vec<Route> {
+        crate::controller::routes()
+            .into_iter()
+            .chain(routes![get_openapi])
+            .collect()
+    }
+
+    /// Parse a query string strictly into `T`, formatted with [`Debug`].
+    fn parses<'r, T: FromForm<'r> + Debug>(query: &'r str) -> Option<Result<String, String>> {
+        Some(
+            Form::<Strict<T>>::parse(query)
+                .map(|parsed| format!("{:?}", parsed.into_inner()))
+                .map_err(|errors| errors.to_string()),
+        )
+    }
+
+    /// Parse a query string into the form `route` parses it into, or [None] if `route` isn't
+    /// known to take a query string.
+    fn parse_query(route: &Route, query: &str) -> Option<Result<String, String>> {
+        let is = |handler: Route| handler.name == route.name;
+
+        if is(list_containers {}.into_route()) {
+            parses::<ContainersQuery>(query)
+        } else if is(get_container {}.into_route()) || is(batch_get_containers {}.into_route()) {
+            parses::<ContainerQuery>(query)
+        } else if is(get_advertisements {}.into_route()) {
+            parses::<CollectionQuery<Advertisement>>(query)
+        } else if is(get_images {}.into_route()) {
+            parses::<CollectionQuery<Image>>(query)
+        } else if is(get_advertisement {}.into_route()) {
+            parses::<FieldsQuery<Advertisement>>(query)
+        } else if is(get_image {}.into_route()) {
+            parses::<FieldsQuery<Image>>(query)
+        } else if is(get_videos {}.into_route()) {
+            parses::<VideosQuery>(query)
+        } else if is(get_video {}.into_route()) {
+            parses::<VideoQuery>(query)
+        } else if is(get_video_assets {}.into_route()) {
+            parses::<AssetsQuery>(query)
+        } else if is(get_expiring_report {}.into_route()) {
+            parses::<ExpiringQuery>(query)
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn document_every_route() {
+        // Given
+        let routes: Vec<Route> = routes();
+
+        // When
+        let document: Value = openapi(&routes);
+
+        // Then
+        for route in &routes {
+            let path: String = openapi_path(route.uri.path());
+            let operation: &Value = &document["paths"][&path][route.method.as_str().to_lowercase()];
+
+            assert!(operation.is_object(), "{} is undocumented", route);
+            assert!(
+                operation["responses"].is_object(),
+                "{} has no responses",
+                route
+            );
+
+            for parameter in operation["parameters"].as_array().unwrap() {
+                assert!(
+                    parameter["description"]
+                        .as_str()
+                        .is_some_and(|description| !description.is_empty()),
+                    "{} has undocumented parameter {}",
+                    route,
+                    parameter["name"]
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn document_every_query_parameter() {
+        // Given
+        let routes: Vec<Route> = routes();
+        let document: Value = openapi(&routes);
+
+        for route in &routes {
+            let path: String = openapi_path(route.uri.path());
+            let operation: &Value = &document["paths"][&path][route.method.as_str().to_lowercase()];
+
+            // When
+            let query: String = operation["parameters"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|parameter| parameter["in"] == "query")
+                .map(|parameter| {
+                    let example: String = match &parameter["example"] {
+                        Value::String(example) => example.clone(),
+                        example => example.to_string(),
+                    };
+
+                    format!("{}={}", parameter["name"].as_str().unwrap(), example)
+                })
+                .collect::<Vec<String>>()
+                .join("&");
+
+            // Then
+            match parse_query(route, &query) {
+                Some(Ok(parsed)) => assert!(
+                    !parsed.contains("Optional(None)"),
+                    "{} parses query parameters which aren't documented: {}",
+                    route,
+                    parsed
+                ),
+                Some(Err(err)) => {
+                    panic!("{} rejects its documented query {}: {}", route, query, err)
+                }
+                None => assert!(
+                    route.uri.query().is_none() && query.is_empty(),
+                    "{} takes a query string, but isn't known to the test",
+                    route
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn wrap_v2_responses_in_envelope() {
+        // Given
+        let rocket = rocket::build()
+            .mount("/v1", routes![get_image, get_images])
+            .mount("/v2", routes![get_image, get_images]);
+        let schema = |document: &Value, path: &str| -> Value {
+            document["paths"][path]["get"]["responses"]["200"]["content"]["application/json"]
+                ["schema"]
+                .clone()
+        };
+
+        // When
+        let document: Value = openapi(rocket.routes());
+
+        // Then
+        assert_eq!(
+            schema(&document, "/v1/images/{image_id}"),
+            json!({"$ref": "#/components/schemas/Image"})
+        );
+        assert_eq!(
+            schema(&document, "/v2/images/{image_id}")["properties"]["data"],
+            json!({"$ref": "#/components/schemas/Image"})
+        );
+        assert_eq!(
+            schema(&document, "/v2/containers/{container_id}/images")["properties"]["data"],
+            json!({"type": "array", "items": {"$ref": "#/components/schemas/Image"}})
+        );
+    }
+
+    #[test]
+    fn negotiate_version_on_unversioned_paths() {
+        // Given
+        let rocket = rocket::build().mount("/", routes![get_image]);
+
+        // When
+        let document: Value = openapi(rocket.routes());
+
+        // Then
+        let content: &Value =
+            &document["paths"]["/images/{image_id}"]["get"]["responses"]["200"]["content"];
+        assert_eq!(
+            content[V1_MEDIA_TYPE]["schema"],
+            json!({"$ref": "#/components/schemas/Image"})
+        );
+        assert_eq!(
+            content[V2_MEDIA_TYPE]["schema"]["properties"]["data"],
+            json!({"$ref": "#/components/schemas/Image"})
+        );
+    }
+
+    #[test]
+    fn undocumented_query_parameter() {
+        // When
+        let parameter: Value = query_parameter("unknown");
+
+        // Then
+        assert_eq!(parameter["name"], "unknown");
+        assert_eq!(parameter["description"], "");
+    }
+
+    #[test]
+    fn convert_path() {
+        // When
+        let actual: String = openapi_path("/containers/<container_id>/videos");
+
+        // Then
+        assert_eq!(actual, "/containers/{container_id}/videos");
+    }
+}
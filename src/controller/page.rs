@@ -0,0 +1,273 @@
+//! Pagination for collection routes.
+//!
+//! Collection routes accept `limit` and `cursor` query parameters and return a [`Page`] of items
+//! with a link to the next page. Cursors are opaque to clients; they are only valid for the
+//! ordering they were issued with, which is why every collection is sorted before it is paged.
+
+use std::cmp::min;
+
+use rocket::{http::uri::Origin, serde::json::Json};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::PaginationConfig,
+    controller::{Error, ErrorResponse},
+};
+
+/// Cursor query parameter.
+const CURSOR: &str = "cursor";
+
+/// Limit query parameter.
+const LIMIT: &str = "limit";
+
+/* ***************************************** PageQuery ****************************************** */
+
+/// Pagination query parameters.
+///
+/// Routes parse `limit` and `cursor` as part of their [query](crate::controller::query) and
+/// convert them into a [`PageQuery`].
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::page::PageQuery;
+///
+/// let query: PageQuery = PageQuery {
+///     limit: Some(10),
+///     cursor: None,
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PageQuery {
+    /// Maximum number of items to return.
+    pub limit: Option<usize>,
+    /// Cursor returned in [`Page::next`] by a previous request.
+    pub cursor: Option<usize>,
+}
+
+/* ******************************************** Page ******************************************** */
+
+/// A page of items returned from a collection route.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket::http::uri::Origin;
+/// use rocket_container::{
+///     config::PaginationConfig,
+///     controller::page::{Page, PageQuery},
+/// };
+///
+/// let origin: Origin = Origin::parse("/containers?limit=2").unwrap();
+/// let query: PageQuery = PageQuery {
+///     limit: Some(2),
+///     cursor: None,
+/// };
+/// let page: Page<u32> =
+///     Page::paginate(vec![1, 2, 3], &query, &PaginationConfig::default(), &origin).unwrap();
+///
+/// assert_eq!(page.items, vec![1, 2]);
+/// assert_eq!(page.next, Some("/containers?limit=2&cursor=2".to_string()));
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    /// Items in this page.
+    pub items: Vec<T>,
+    /// Link to the next page, or [`None`] if this is the last page.
+    pub next: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Get a page of items from a sorted list of items.
+    ///
+    /// `limit` defaults to [`PaginationConfig::default_page_size`] and is clamped to
+    /// [`PaginationConfig::max_page_size`]. The link to the next page keeps every query parameter
+    /// of `origin` except for `limit` and `cursor`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(page)` if the query is valid.
+    /// - `Err(`[`Error::BadRequest`]`)` if `limit` is zero.
+    pub fn paginate(
+        items: Vec<T>,
+        query: &PageQuery,
+        config: &PaginationConfig,
+        origin: &Origin<'_>,
+    ) -> Result<Self, Error> {
+        let limit: usize = match query.limit {
+            Some(0) => {
+                return Err(Error::BadRequest(Json(ErrorResponse {
+                    message: "limit must be greater than zero".to_string(),
+                })));
+            }
+            Some(limit) => min(limit, config.max_page_size),
+            None => config.default_page_size,
+        };
+        let cursor: usize = min(query.cursor.unwrap_or(0), items.len());
+        let end: usize = min(cursor.saturating_add(limit), items.len());
+
+        let next: Option<String> = match end < items.len() {
+            true => Some(next_link(origin, limit, end)),
+            false => None,
+        };
+        let items: Vec<T> = items.into_iter().skip(cursor).take(end - cursor).collect();
+
+        Ok(Page { items, next })
+    }
+
+    /// Convert each item of the page, keeping its link to the next page.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next: self.next,
+        }
+    }
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Build a link to the page starting at `cursor`.
+fn next_link(origin: &Origin<'_>, limit: usize, cursor: usize) -> String {
+    let mut segments: Vec<String> = match origin.query() {
+        Some(query) => query
+            .raw_segments()
+            .filter(|segment| {
+                let (key, _) = segment.split_at_byte(b'=');
+
+                key != LIMIT && key != CURSOR
+            })
+            .map(|segment| segment.to_string())
+            .collect(),
+        None => Vec::new(),
+    };
+
+    segments.push(format!("{}={}", LIMIT, limit));
+    segments.push(format!("{}={}", CURSOR, cursor));
+
+    format!("{}?{}", origin.path(), segments.join("&"))
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use rocket::http::uri::Origin;
+
+    use crate::config::PaginationConfig;
+
+    use super::{Page, PageQuery};
+
+    fn config() -> PaginationConfig {
+        PaginationConfig {
+            default_page_size: 2,
+            max_page_size: 3,
+        }
+    }
+
+    #[test]
+    fn paginate_first_page() {
+        // Given
+        let origin: Origin = Origin::parse("/containers").unwrap();
+        let query: PageQuery = PageQuery::default();
+        let expected: Page<u32> = Page {
+            items: vec![0, 1],
+            next: Some("/containers?limit=2&cursor=2".to_string()),
+        };
+
+        // When
+        let result = Page::paginate(vec![0, 1, 2, 3, 4], &query, &config(), &origin);
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual, expected),
+            Err(err) => panic!("Failed to paginate with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn paginate_last_page() {
+        // Given
+        let origin: Origin = Origin::parse("/containers?limit=2&cursor=4").unwrap();
+        let query: PageQuery = PageQuery {
+            limit: Some(2),
+            cursor: Some(4),
+        };
+        let expected: Page<u32> = Page {
+            items: vec![4],
+            next: None,
+        };
+
+        // When
+        let result = Page::paginate(vec![0, 1, 2, 3, 4], &query, &config(), &origin);
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual, expected),
+            Err(err) => panic!("Failed to paginate with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn paginate_clamps_limit_and_keeps_query() {
+        // Given
+        let origin: Origin = Origin::parse("/containers?sort=title&limit=10&cursor=1").unwrap();
+        let query: PageQuery = PageQuery {
+            limit: Some(10),
+            cursor: Some(1),
+        };
+        let expected: Page<u32> = Page {
+            items: vec![1, 2, 3],
+            next: Some("/containers?sort=title&limit=3&cursor=4".to_string()),
+        };
+
+        // When
+        let result = Page::paginate(vec![0, 1, 2, 3, 4], &query, &config(), &origin);
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual, expected),
+            Err(err) => panic!("Failed to paginate with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn paginate_cursor_past_end() {
+        // Given
+        let origin: Origin = Origin::parse("/containers?cursor=10").unwrap();
+        let query: PageQuery = PageQuery {
+            limit: None,
+            cursor: Some(10),
+        };
+        let expected: Page<u32> = Page {
+            items: Vec::new(),
+            next: None,
+        };
+
+        // When
+        let result = Page::paginate(vec![0, 1, 2], &query, &config(), &origin);
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual, expected),
+            Err(err) => panic!("Failed to paginate with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn paginate_zero_limit() {
+        // Given
+        let origin: Origin = Origin::parse("/containers?limit=0").unwrap();
+        let query: PageQuery = PageQuery {
+            limit: Some(0),
+            cursor: None,
+        };
+
+        // When
+        let result = Page::paginate(vec![0, 1, 2], &query, &config(), &origin);
+
+        // Then
+        assert!(result.is_err(), "Zero limit should be rejected");
+    }
+}
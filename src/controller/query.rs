@@ -0,0 +1,839 @@
+//! Query parameters for controller routes.
+//!
+//! Each route parses its query string into one [`FromForm`] struct. Query strings are parsed
+//! strictly: unknown parameters and malformed values are rejected with [`Error::BadRequest`]
+//! instead of being ignored. Rocket ignores `#[field(default = ...)]` when parsing strictly, so
+//! every parameter is [`Optional`] and defaults are applied when converting to service types.
+
+use std::time::Duration;
+
+use rocket::{
+    form::{self, DataField, Errors, FromForm, FromFormField, Options, Strict, ValueField},
+    serde::json::Json,
+};
+
+use crate::{
+    controller::{
+        fields::{Fields, Selectable},
+        page::PageQuery,
+        Error, ErrorResponse,
+    },
+    service::{
+        container::{Container, ContainerFilter, ContainerSort, ContainerSorting, ContainerStatus},
+        video::{Video, VideoFilter, VideoSort, VideoSorting},
+    },
+    types::{AssetType, CommaSeparated, HumanDuration, SortOrder, Timestamp, VideoType},
+};
+
+/// Default window of `GET /reports/expiring`.
+const DEFAULT_EXPIRING_WITHIN: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/* ****************************************** Optional ****************************************** */
+
+/// An optional query parameter.
+///
+/// [`Option`] parses as [`None`] both when a parameter is missing and when its value is malformed.
+/// [`Optional`] is only empty when the parameter is missing, so malformed values are reported as
+/// errors.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::query::Optional;
+///
+/// let limit: Optional<usize> = Optional(Some(10));
+///
+/// assert_eq!(limit.into_inner(), Some(10));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Optional<T>(pub Option<T>);
+
+impl<T> Default for Optional<T> {
+    fn default() -> Self {
+        Optional(None)
+    }
+}
+
+impl<T> Optional<T> {
+    /// Get the wrapped [`Option`].
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'v, T: FromForm<'v>> FromForm<'v> for Optional<T> {
+    type Context = T::Context;
+
+    fn init(opts: Options) -> Self::Context {
+        T::init(opts)
+    }
+
+    fn push_value(ctxt: &mut Self::Context, field: ValueField<'v>) {
+        T::push_value(ctxt, field)
+    }
+
+    async fn push_data(ctxt: &mut Self::Context, field: DataField<'v, '_>) {
+        T::push_data(ctxt, field).await
+    }
+
+    fn finalize(ctxt: Self::Context) -> form::Result<'v, Self> {
+        T::finalize(ctxt).map(|value| Optional(Some(value)))
+    }
+
+    fn default(_opts: Options) -> Option<Self> {
+        Some(Optional(None))
+    }
+}
+
+/* ************************************** QueryParameters *************************************** */
+
+/// Query strings which list the names of the parameters they accept, so routes can be documented
+/// from the same type which parses their query string.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::query::{AssetsQuery, QueryParameters};
+///
+/// assert_eq!(AssetsQuery::NAMES, ["assetType", "expand"]);
+/// ```
+pub trait QueryParameters {
+    /// Name of each parameter in the query string, in the order they are documented.
+    const NAMES: &'static [&'static str];
+}
+
+/* ******************************************* Expand ******************************************* */
+
+/// Objects which can be expanded in a response with the `expand` query parameter.
+#[derive(Clone, Copy, Debug, Eq, FromFormField, PartialEq)]
+pub enum Expand {
+    /// Replace asset references with the advertisements and images they reference.
+    #[field(value = "assets")]
+    Assets,
+}
+
+/* **************************************** ReportFormat **************************************** */
+
+/// Formats a report can be exported in with the `format` query parameter.
+#[derive(Clone, Copy, Debug, Default, Eq, FromFormField, PartialEq)]
+pub enum ReportFormat {
+    /// A JSON array.
+    #[default]
+    #[field(value = "json")]
+    Json,
+    /// CSV with a header row.
+    #[field(value = "csv")]
+    Csv,
+}
+
+/* **************************************** AssetsQuery ***************************************** */
+
+/// Query parameters for `GET /videos/<video_id>/assets`.
+///
+/// See [`get_video_assets`](crate::controller::get_video_assets) for a description of each
+/// parameter.
+#[derive(Clone, Debug, Default, Eq, FromForm, PartialEq)]
+pub struct AssetsQuery {
+    /// Type of assets to return, or all assets if empty.
+    #[field(name = "assetType")]
+    pub asset_type: Optional<AssetType>,
+    /// Objects to expand, e.g. `assets`.
+    pub expand: Optional<Expand>,
+}
+
+impl QueryParameters for AssetsQuery {
+    const NAMES: &'static [&'static str] = &["assetType", "expand"];
+}
+
+impl AssetsQuery {
+    /// Check if asset references should be expanded.
+    pub fn expand_assets(&self) -> bool {
+        self.expand.0 == Some(Expand::Assets)
+    }
+}
+
+/* ************************************** CollectionQuery *************************************** */
+
+/// Query parameters for collection routes which only support pagination and sparse fieldsets.
+///
+/// - `limit`, `cursor`: See [`PageQuery`].
+/// - `fields`: See [`Fields`].
+#[derive(Clone, Debug, Eq, FromForm, PartialEq)]
+pub struct CollectionQuery<T: Selectable + Send> {
+    /// See [`PageQuery::limit`].
+    pub limit: Optional<usize>,
+    /// See [`PageQuery::cursor`].
+    pub cursor: Optional<usize>,
+    /// Fields of each item to respond with.
+    pub fields: Optional<Fields<T>>,
+}
+
+impl<T: Selectable + Send> QueryParameters for CollectionQuery<T> {
+    const NAMES: &'static [&'static str] = &["limit", "cursor", "fields"];
+}
+
+impl<T: Selectable + Send> CollectionQuery<T> {
+    /// Get selected fields.
+    pub fn fields(&self) -> Option<Fields<T>> {
+        self.fields.0.clone()
+    }
+
+    /// Get pagination parameters.
+    pub fn page(&self) -> PageQuery {
+        PageQuery {
+            limit: self.limit.0,
+            cursor: self.cursor.0,
+        }
+    }
+}
+
+/* ************************************** ContainersQuery *************************************** */
+
+/// Query parameters for `GET /containers`.
+///
+/// See [`list_containers`](crate::controller::list_containers) for a description of each
+/// parameter.
+#[derive(Clone, Debug, Default, Eq, FromForm, PartialEq)]
+pub struct ContainersQuery {
+    /// See [`PageQuery::limit`].
+    pub limit: Optional<usize>,
+    /// See [`PageQuery::cursor`].
+    pub cursor: Optional<usize>,
+    /// See [`ContainerSorting::sort`].
+    pub sort: Optional<ContainerSort>,
+    /// See [`ContainerSorting::order`].
+    pub order: Optional<SortOrder>,
+    /// See [`ContainerSorting::videos`].
+    pub videos: Optional<VideoSortingQuery>,
+    /// See [`VideoFilter::types`].
+    #[field(name = "type")]
+    pub video_types: Optional<CommaSeparated<VideoType>>,
+    /// See [`ContainerFilter::has_ads`].
+    #[field(name = "hasAds")]
+    pub has_ads: Optional<bool>,
+    /// See [`ContainerFilter::has_images`].
+    #[field(name = "hasImages")]
+    pub has_images: Optional<bool>,
+    /// See [`ContainerFilter::statuses`].
+    pub status: Optional<CommaSeparated<ContainerStatus>>,
+    /// See [`VideoFilter::expires_before`].
+    #[field(name = "expiresBefore")]
+    pub expires_before: Optional<Timestamp>,
+    /// See [`VideoFilter::expires_after`].
+    #[field(name = "expiresAfter")]
+    pub expires_after: Optional<Timestamp>,
+    /// See [`VideoFilter::include_expired`].
+    #[field(name = "includeExpired")]
+    pub include_expired: Optional<bool>,
+    /// See [`VideoFilter::title`].
+    pub q: Optional<String>,
+    /// Objects to expand, e.g. `assets`.
+    pub expand: Optional<Expand>,
+    /// Fields of each container to respond with.
+    pub fields: Optional<Fields<Container>>,
+}
+
+impl QueryParameters for ContainersQuery {
+    const NAMES: &'static [&'static str] = &[
+        "limit",
+        "cursor",
+        "sort",
+        "order",
+        "videos.sort",
+        "videos.order",
+        "type",
+        "hasAds",
+        "hasImages",
+        "status",
+        "expiresBefore",
+        "expiresAfter",
+        "includeExpired",
+        "q",
+        "expand",
+        "fields",
+    ];
+}
+
+impl ContainersQuery {
+    /// Check if asset references should be expanded.
+    pub fn expand_assets(&self) -> bool {
+        self.expand.0 == Some(Expand::Assets)
+    }
+
+    /// Get selected fields.
+    pub fn fields(&self) -> Option<Fields<Container>> {
+        self.fields.0.clone()
+    }
+
+    /// Get container filter.
+    pub fn filter(&self) -> ContainerFilter {
+        ContainerFilter {
+            has_ads: self.has_ads.0,
+            has_images: self.has_images.0,
+            statuses: self
+                .status
+                .0
+                .as_ref()
+                .map(|statuses| statuses.0.clone())
+                .unwrap_or_default(),
+            videos: video_filter(
+                &self.video_types,
+                &self.expires_before,
+                &self.expires_after,
+                &self.include_expired,
+                &self.q,
+            ),
+        }
+    }
+
+    /// Get pagination parameters.
+    pub fn page(&self) -> PageQuery {
+        PageQuery {
+            limit: self.limit.0,
+            cursor: self.cursor.0,
+        }
+    }
+
+    /// Get container sorting.
+    pub fn sorting(&self) -> ContainerSorting {
+        ContainerSorting {
+            sort: self.sort.0.unwrap_or_default(),
+            order: self.order.0.unwrap_or_default(),
+            videos: video_sorting(&self.videos),
+        }
+    }
+}
+
+/* *************************************** ContainerQuery *************************************** */
+
+/// Query parameters for `GET /containers/<container_id>` and `POST /containers:batchGet`.
+///
+/// See [`get_container`](crate::controller::get_container) for a description of each parameter.
+#[derive(Clone, Debug, Default, Eq, FromForm, PartialEq)]
+pub struct ContainerQuery {
+    /// See [`ContainerSorting::videos`].
+    pub videos: Optional<VideoSortingQuery>,
+    /// See [`VideoFilter::include_expired`].
+    #[field(name = "includeExpired")]
+    pub include_expired: Optional<bool>,
+    /// Objects to expand, e.g. `assets`.
+    pub expand: Optional<Expand>,
+    /// Fields of the container to respond with.
+    pub fields: Optional<Fields<Container>>,
+}
+
+impl QueryParameters for ContainerQuery {
+    const NAMES: &'static [&'static str] = &[
+        "videos.sort",
+        "videos.order",
+        "includeExpired",
+        "expand",
+        "fields",
+    ];
+}
+
+impl ContainerQuery {
+    /// Get video filter.
+    pub fn filter(&self) -> VideoFilter {
+        VideoFilter {
+            include_expired: self.include_expired.0.unwrap_or(false),
+            ..VideoFilter::default()
+        }
+    }
+
+    /// Check if asset references should be expanded.
+    pub fn expand_assets(&self) -> bool {
+        self.expand.0 == Some(Expand::Assets)
+    }
+
+    /// Get selected fields.
+    pub fn fields(&self) -> Option<Fields<Container>> {
+        self.fields.0.clone()
+    }
+
+    /// Get video sorting.
+    pub fn sorting(&self) -> VideoSorting {
+        video_sorting(&self.videos)
+    }
+}
+
+/* *************************************** ExpiringQuery **************************************** */
+
+/// Query parameters for `GET /reports/expiring`.
+///
+/// See [`get_expiring_report`](crate::controller::get_expiring_report) for a description of each
+/// parameter.
+#[derive(Clone, Debug, Default, Eq, FromForm, PartialEq)]
+pub struct ExpiringQuery {
+    /// How far ahead to look for expiring videos, e.g. `7d`.
+    pub within: Optional<HumanDuration>,
+    /// Report format, either `json` or `csv`.
+    pub format: Optional<ReportFormat>,
+}
+
+impl QueryParameters for ExpiringQuery {
+    const NAMES: &'static [&'static str] = &["within", "format"];
+}
+
+impl ExpiringQuery {
+    /// Get how far ahead to look for expiring videos, 7 days by default.
+    pub fn within(&self) -> Duration {
+        self.within
+            .0
+            .map_or(DEFAULT_EXPIRING_WITHIN, |within| within.0)
+    }
+
+    /// Get report format, JSON by default.
+    pub fn format(&self) -> ReportFormat {
+        self.format.0.unwrap_or_default()
+    }
+}
+
+/* **************************************** FieldsQuery ***************************************** */
+
+/// Query parameters for routes which only support sparse fieldsets, such as
+/// `GET /images/<image_id>`.
+///
+/// - `fields`: See [`Fields`].
+#[derive(Clone, Debug, Eq, FromForm, PartialEq)]
+pub struct FieldsQuery<T: Selectable + Send> {
+    /// Fields to respond with.
+    pub fields: Optional<Fields<T>>,
+}
+
+impl<T: Selectable + Send> QueryParameters for FieldsQuery<T> {
+    const NAMES: &'static [&'static str] = &["fields"];
+}
+
+impl<T: Selectable + Send> FieldsQuery<T> {
+    /// Get selected fields.
+    pub fn fields(&self) -> Option<Fields<T>> {
+        self.fields.0.clone()
+    }
+}
+
+/* **************************************** VideosQuery ***************************************** */
+
+/// Query parameters for `GET /containers/<container_id>/videos`.
+///
+/// See [`get_videos`](crate::controller::get_videos) for a description of each parameter.
+#[derive(Clone, Debug, Default, Eq, FromForm, PartialEq)]
+pub struct VideosQuery {
+    /// See [`PageQuery::limit`].
+    pub limit: Optional<usize>,
+    /// See [`PageQuery::cursor`].
+    pub cursor: Optional<usize>,
+    /// See [`VideoSorting::sort`].
+    pub sort: Optional<VideoSort>,
+    /// See [`VideoSorting::order`].
+    pub order: Optional<SortOrder>,
+    /// See [`VideoFilter::types`].
+    #[field(name = "type")]
+    pub video_types: Optional<CommaSeparated<VideoType>>,
+    /// See [`VideoFilter::expires_before`].
+    #[field(name = "expiresBefore")]
+    pub expires_before: Optional<Timestamp>,
+    /// See [`VideoFilter::expires_after`].
+    #[field(name = "expiresAfter")]
+    pub expires_after: Optional<Timestamp>,
+    /// See [`VideoFilter::include_expired`].
+    #[field(name = "includeExpired")]
+    pub include_expired: Optional<bool>,
+    /// See [`VideoFilter::title`].
+    pub q: Optional<String>,
+    /// Objects to expand, e.g. `assets`.
+    pub expand: Optional<Expand>,
+    /// Fields of each video to respond with.
+    pub fields: Optional<Fields<Video>>,
+}
+
+impl QueryParameters for VideosQuery {
+    const NAMES: &'static [&'static str] = &[
+        "limit",
+        "cursor",
+        "sort",
+        "order",
+        "type",
+        "expiresBefore",
+        "expiresAfter",
+        "includeExpired",
+        "q",
+        "expand",
+        "fields",
+    ];
+}
+
+impl VideosQuery {
+    /// Check if asset references should be expanded.
+    pub fn expand_assets(&self) -> bool {
+        self.expand.0 == Some(Expand::Assets)
+    }
+
+    /// Get selected fields.
+    pub fn fields(&self) -> Option<Fields<Video>> {
+        self.fields.0.clone()
+    }
+
+    /// Get video filter.
+    pub fn filter(&self) -> VideoFilter {
+        video_filter(
+            &self.video_types,
+            &self.expires_before,
+            &self.expires_after,
+            &self.include_expired,
+            &self.q,
+        )
+    }
+
+    /// Get pagination parameters.
+    pub fn page(&self) -> PageQuery {
+        PageQuery {
+            limit: self.limit.0,
+            cursor: self.cursor.0,
+        }
+    }
+
+    /// Get video sorting.
+    pub fn sorting(&self) -> VideoSorting {
+        VideoSorting {
+            sort: self.sort.0.unwrap_or_default(),
+            order: self.order.0.unwrap_or_default(),
+        }
+    }
+}
+
+/* ***************************************** VideoQuery ***************************************** */
+
+/// Query parameters for `GET /videos/<video_id>`.
+///
+/// See [`get_video`](crate::controller::get_video) for a description of each parameter.
+#[derive(Clone, Debug, Default, Eq, FromForm, PartialEq)]
+pub struct VideoQuery {
+    /// See [`VideoFilter::include_expired`].
+    #[field(name = "includeExpired")]
+    pub include_expired: Optional<bool>,
+    /// Objects to expand, e.g. `assets`.
+    pub expand: Optional<Expand>,
+    /// Fields of the video to respond with.
+    pub fields: Optional<Fields<Video>>,
+}
+
+impl QueryParameters for VideoQuery {
+    const NAMES: &'static [&'static str] = &["includeExpired", "expand", "fields"];
+}
+
+impl VideoQuery {
+    /// Check if asset references should be expanded.
+    pub fn expand_assets(&self) -> bool {
+        self.expand.0 == Some(Expand::Assets)
+    }
+
+    /// Get selected fields.
+    pub fn fields(&self) -> Option<Fields<Video>> {
+        self.fields.0.clone()
+    }
+}
+
+/* ************************************* VideoSortingQuery ************************************** */
+
+/// Nested query parameters for sorting videos inside of containers, e.g. `videos.sort=title`.
+#[derive(Clone, Debug, Default, Eq, FromForm, PartialEq)]
+pub struct VideoSortingQuery {
+    /// See [`VideoSorting::sort`].
+    pub sort: Optional<VideoSort>,
+    /// See [`VideoSorting::order`].
+    pub order: Optional<SortOrder>,
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Validate a strictly parsed query string.
+///
+/// # Returns
+///
+/// - `Ok(query)` if the query string was parsed.
+/// - `Err(`[`Error::BadRequest`]`)` describing every unknown parameter and malformed value
+///   otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket::form::{Errors, Strict};
+/// use rocket_container::{
+///     controller::{
+///         query::{validate, CollectionQuery},
+///         Error,
+///     },
+///     service::image::Image,
+/// };
+///
+/// fn page(query: Result<Strict<CollectionQuery<Image>>, Errors<'_>>) -> Result<usize, Error> {
+///     let query: CollectionQuery<Image> = validate(query)?;
+///
+///     Ok(query.limit.into_inner().unwrap_or(10))
+/// }
+/// ```
+pub fn validate<T>(query: Result<Strict<T>, Errors<'_>>) -> Result<T, Error> {
+    match query {
+        Ok(query) => Ok(query.into_inner()),
+        Err(errors) => {
+            let message: String = errors
+                .iter()
+                .map(|error| match &error.name {
+                    Some(name) => format!("{}: {}", name, error),
+                    None => error.to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join("; ");
+
+            Err(Error::BadRequest(Json(ErrorResponse {
+                message: format!("Invalid query parameters: {}", message),
+            })))
+        }
+    }
+}
+
+/// Build a [`VideoSorting`] from nested query parameters.
+fn video_sorting(videos: &Optional<VideoSortingQuery>) -> VideoSorting {
+    match &videos.0 {
+        Some(videos) => VideoSorting {
+            sort: videos.sort.0.unwrap_or_default(),
+            order: videos.order.0.unwrap_or_default(),
+        },
+        None => VideoSorting::default(),
+    }
+}
+
+/// Build a [`VideoFilter`] from query parameters.
+fn video_filter(
+    video_types: &Optional<CommaSeparated<VideoType>>,
+    expires_before: &Optional<Timestamp>,
+    expires_after: &Optional<Timestamp>,
+    include_expired: &Optional<bool>,
+    q: &Optional<String>,
+) -> VideoFilter {
+    VideoFilter {
+        types: video_types
+            .0
+            .as_ref()
+            .map_or_else(Vec::new, |video_types| video_types.0.clone()),
+        expires_before: expires_before.0.map(|timestamp| timestamp.0),
+        expires_after: expires_after.0.map(|timestamp| timestamp.0),
+        include_expired: include_expired.0.unwrap_or(false),
+        title: q.0.clone(),
+    }
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use rocket::form::{Errors, Form, Strict};
+
+    use rocket::serde::json::json;
+
+    use crate::{
+        service::{
+            image::Image,
+            video::{VideoFilter, VideoSort},
+        },
+        types::{parse_timestamp, AssetType, SortOrder, VideoType},
+    };
+
+    use super::{
+        validate, AssetsQuery, CollectionQuery, ContainersQuery, Expand, ExpiringQuery,
+        ReportFormat, VideosQuery,
+    };
+
+    fn parse<'r, T>(query: &'r str) -> Result<Strict<T>, Errors<'r>>
+    where
+        T: rocket::form::FromForm<'r>,
+    {
+        Form::<Strict<T>>::parse(query)
+    }
+
+    #[test]
+    fn parse_containers_query() {
+        // Given
+        let query: &str = "type=MOVIE,EPISODE&hasAds=true&q=family&videos.sort=title&limit=5";
+
+        // When
+        let result = validate(parse::<ContainersQuery>(query));
+
+        // Then
+        match result {
+            Ok(actual) => {
+                let filter = actual.filter();
+
+                assert_eq!(
+                    filter.videos.types,
+                    vec![VideoType::Movie, VideoType::Episode]
+                );
+                assert_eq!(filter.has_ads, Some(true));
+                assert_eq!(filter.has_images, None);
+                assert_eq!(filter.videos.title, Some("family".to_string()));
+                assert_eq!(actual.sorting().videos.sort, VideoSort::Title);
+                assert_eq!(actual.page().limit, Some(5));
+            }
+            Err(err) => panic!("Failed to parse query with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn parse_empty_query() {
+        // Given
+        let query: &str = "";
+
+        // When
+        let result = validate(parse::<VideosQuery>(query));
+
+        // Then
+        match result {
+            Ok(actual) => {
+                assert_eq!(actual, VideosQuery::default());
+                assert_eq!(actual.sorting().order, SortOrder::Asc);
+                assert!(actual.filter().is_empty());
+            }
+            Err(err) => panic!("Failed to parse query with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn reject_unknown_parameter() {
+        // Given
+        let query: &str = "sort=title&colour=blue";
+
+        // When
+        let result = validate(parse::<VideosQuery>(query));
+
+        // Then
+        assert!(result.is_err(), "Unknown parameter should be rejected");
+    }
+
+    #[test]
+    fn reject_malformed_value() {
+        // Given
+        let query: &str = "type=MOVIE,TRAILER";
+
+        // When
+        let result = validate(parse::<ContainersQuery>(query));
+
+        // Then
+        assert!(result.is_err(), "Malformed value should be rejected");
+    }
+
+    #[test]
+    fn parse_expiration_query() {
+        // Given
+        let query: &str = "expiresAfter=2022-03-23&includeExpired=true";
+
+        // When
+        let result = validate(parse::<VideosQuery>(query));
+
+        // Then
+        match result {
+            Ok(actual) => {
+                let filter: VideoFilter = actual.filter();
+
+                assert!(filter.include_expired);
+                assert_eq!(filter.expires_after, parse_timestamp("2022-03-23"));
+            }
+            Err(err) => panic!("Failed to parse query with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn reject_malformed_timestamp() {
+        // Given
+        let query: &str = "expiresBefore=next-week";
+
+        // When
+        let result = validate(parse::<VideosQuery>(query));
+
+        // Then
+        assert!(result.is_err(), "Malformed timestamp should be rejected");
+    }
+
+    #[test]
+    fn parse_assets_query() {
+        // Given
+        let query: &str = "assetType=IMAGE&expand=assets";
+
+        // When
+        let result = validate(parse::<AssetsQuery>(query));
+
+        // Then
+        match result {
+            Ok(actual) => {
+                assert_eq!(actual.expand.0, Some(Expand::Assets));
+                assert_eq!(actual.asset_type.into_inner(), Some(AssetType::Image));
+            }
+            Err(err) => panic!("Failed to parse query with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn parse_expiring_query() {
+        // Given
+        let query: &str = "within=2d&format=csv";
+
+        // When
+        let result = validate(parse::<ExpiringQuery>(query));
+
+        // Then
+        match result {
+            Ok(actual) => {
+                assert_eq!(actual.within().as_secs(), 2 * 24 * 60 * 60);
+                assert_eq!(actual.format(), ReportFormat::Csv);
+            }
+            Err(err) => panic!("Failed to parse query with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn reject_malformed_duration() {
+        // Given
+        let query: &str = "within=soon";
+
+        // When
+        let result = validate(parse::<ExpiringQuery>(query));
+
+        // Then
+        assert!(result.is_err(), "Malformed duration should be rejected");
+    }
+
+    #[test]
+    fn parse_fields_query() {
+        // Given
+        let query: &str = "fields=id,title,videos.id,videos.playbackUrl&limit=2";
+
+        // When
+        let result = validate(parse::<ContainersQuery>(query));
+
+        // Then
+        match result {
+            Ok(actual) => {
+                let fields = actual.fields().unwrap();
+
+                assert_eq!(
+                    fields.project(json!({"id": 1, "featured": true, "videos": [{"id": 2}]})),
+                    json!({"id": 1, "videos": [{"id": 2}]})
+                );
+            }
+            Err(err) => panic!("Failed to parse query with error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn reject_unknown_field() {
+        // Given
+        let query: &str = "fields=id,playbackUrl";
+
+        // When
+        let result = validate(parse::<CollectionQuery<Image>>(query));
+
+        // Then
+        assert!(result.is_err(), "Unknown field should be rejected");
+    }
+}
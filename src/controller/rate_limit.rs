@@ -0,0 +1,496 @@
+//! Per-client rate limiting.
+//!
+//! Each client gets a token bucket per route: a request takes a token, and tokens are refilled at
+//! a steady rate up to the bucket's capacity. Clients are identified by the name of their API key
+//! (see [`auth`][1]), or by their IP address if they don't have a known key. Limits are configured
+//! per route, by the route's path such as `/containers/<container_id>/ads`, with a default for
+//! routes without a limit of their own (see [`RateLimitConfig`][2]). Every API version of a route
+//! shares its limit and buckets, since paths don't include the version prefix.
+//!
+//! Routes are rate limited by taking a [`RateLimit`] request guard. Requests over the limit fail
+//! with `429 Too Many Requests` from the [`too_many_requests`] catcher. The [`rate_limit_headers`]
+//! fairing adds `RateLimit-Limit`, `RateLimit-Remaining`, and `RateLimit-Reset` headers to every
+//! rate limited response, and a `Retry-After` header to rejected ones.
+//!
+//! Buckets are kept in memory, so each instance of Rocket Container limits clients separately. A
+//! bounded number of buckets is kept, dropping the least recently used ones first.
+//!
+//! [1]: crate::controller::auth
+//! [2]: crate::config::RateLimitConfig
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use log::warn;
+use lru::LruCache;
+use rocket::{
+    catch,
+    fairing::AdHoc,
+    http::{Header, Status},
+    request::{FromRequest, Outcome},
+    serde::json::Json,
+    Request,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::controller::{
+    auth::{presented_key, ApiKeys},
+    Error, ErrorResponse,
+};
+
+/// Number of buckets kept before the least recently used ones are dropped.
+const MAX_BUCKETS: usize = 10_000;
+
+/* ******************************************* Limit ******************************************** */
+
+/// Rate limit for a route.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::rate_limit::Limit;
+///
+/// // Bursts of up to 10 requests, then 1 request every 2 seconds.
+/// let limit: Limit = Limit {
+///     burst: 10,
+///     per_minute: 30,
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Limit {
+    /// Number of requests a client can make at once, which is the capacity of its bucket.
+    pub burst: u32,
+    /// Number of requests a client can make per minute after a burst, which is the rate its bucket
+    /// is refilled at.
+    pub per_minute: u32,
+}
+
+impl Limit {
+    /// Get the number of tokens refilled per second.
+    fn rate(&self) -> f64 {
+        f64::from(self.per_minute.max(1)) / 60.0
+    }
+}
+
+/* ****************************************** Decision ****************************************** */
+
+/// Result of checking a request against its rate limit.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Decision {
+    /// Whether the request is allowed.
+    pub allowed: bool,
+    /// Capacity of the client's bucket.
+    pub limit: u32,
+    /// Number of requests the client can still make at once.
+    pub remaining: u32,
+    /// Time until the client's bucket is full again.
+    pub reset: Duration,
+    /// Time until the client can make another request, if the request isn't allowed.
+    pub retry_after: Option<Duration>,
+}
+
+/* ******************************************* Bucket ******************************************* */
+
+/// Token bucket of a client for a route.
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    /// Number of tokens left.
+    tokens: f64,
+    /// When tokens were last refilled.
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Refill tokens for the time passed since they were last refilled.
+    fn refill(&mut self, limit: &Limit, now: Instant) {
+        let elapsed: f64 = now.saturating_duration_since(self.updated).as_secs_f64();
+
+        self.tokens = (self.tokens + elapsed * limit.rate()).min(f64::from(limit.burst));
+        self.updated = now;
+    }
+}
+
+/* **************************************** RateLimiter ***************************************** */
+
+/// Token buckets of every client, managed as [`rocket::State`] for the [`RateLimit`] guard.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{collections::HashMap, time::Instant};
+///
+/// use rocket_container::controller::rate_limit::{Limit, RateLimiter};
+///
+/// let limiter: RateLimiter = RateLimiter::new(
+///     None,
+///     HashMap::from([(
+///         "/containers".to_string(),
+///         Limit {
+///             burst: 1,
+///             per_minute: 6,
+///         },
+///     )]),
+/// );
+/// let now: Instant = Instant::now();
+///
+/// assert!(limiter.check("ip:127.0.0.1", "/containers", now).unwrap().allowed);
+/// assert!(!limiter.check("ip:127.0.0.1", "/containers", now).unwrap().allowed);
+/// assert!(limiter.check("ip:127.0.0.1", "/videos/<video_id>", now).is_none());
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Limit for routes without a limit of their own, if any.
+    default: Option<Limit>,
+    /// Limits by route path.
+    routes: HashMap<String, Limit>,
+    /// Most recently used buckets by client and route path.
+    buckets: Mutex<LruCache<(String, String), Bucket>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(None, HashMap::new())
+    }
+}
+
+impl RateLimiter {
+    /// Create a new [`RateLimiter`] with a default limit and limits by route path.
+    pub fn new(default: Option<Limit>, routes: HashMap<String, Limit>) -> Self {
+        RateLimiter {
+            default,
+            routes,
+            buckets: Mutex::new(LruCache::new(MAX_BUCKETS)),
+        }
+    }
+
+    /// Check if rate limiting is enabled, which it is once any limit is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.default.is_some() || !self.routes.is_empty()
+    }
+
+    /// Take a token from a client's bucket for a route at `now`.
+    ///
+    /// # Returns
+    ///
+    /// - `Some(decision)` with whether the request is allowed.
+    /// - [None] if the route isn't rate limited.
+    pub fn check(&self, client: &str, route: &str, now: Instant) -> Option<Decision> {
+        let limit: Limit = self.limit(route)?;
+        let key: (String, String) = (client.to_string(), route.to_string());
+        let mut buckets = self.buckets.lock().unwrap();
+        let mut bucket: Bucket = buckets.pop(&key).unwrap_or(Bucket {
+            tokens: f64::from(limit.burst),
+            updated: now,
+        });
+
+        bucket.refill(&limit, now);
+
+        let allowed: bool = bucket.tokens >= 1.0;
+        let retry_after: Option<Duration> = match allowed {
+            true => {
+                bucket.tokens -= 1.0;
+                None
+            }
+            false => Some(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / limit.rate(),
+            )),
+        };
+
+        buckets.put(key, bucket);
+
+        Some(Decision {
+            allowed,
+            limit: limit.burst,
+            remaining: bucket.tokens.floor() as u32,
+            reset: Duration::from_secs_f64(
+                (f64::from(limit.burst) - bucket.tokens).max(0.0) / limit.rate(),
+            ),
+            retry_after,
+        })
+    }
+
+    /* ****************************** Private utility function ****************************** */
+
+    /// Get the limit for a route path.
+    fn limit(&self, route: &str) -> Option<Limit> {
+        self.routes.get(route).or(self.default.as_ref()).copied()
+    }
+}
+
+/* ***************************************** RateLimit ****************************************** */
+
+/// Request guard for rate limited routes.
+///
+/// Requests are always allowed if no [`RateLimiter`] is managed.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::controller::rate_limit::RateLimit;
+///
+/// #[get("/containers")]
+/// fn containers(_limit: RateLimit) -> &'static str {
+///     "containers"
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimit;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RateLimit {
+    type Error = String;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let limiter: &RateLimiter = match request.rocket().state::<RateLimiter>() {
+            Some(limiter) if limiter.is_enabled() => limiter,
+            _ => return Outcome::Success(RateLimit),
+        };
+        let route: String = match request.route() {
+            Some(route) => route.uri.unmounted_origin.path().to_string(),
+            None => return Outcome::Success(RateLimit),
+        };
+        let client: String = client(request);
+
+        match limiter.check(&client, &route, Instant::now()) {
+            Some(decision) => {
+                request.local_cache(|| Some(decision));
+
+                match decision.allowed {
+                    true => Outcome::Success(RateLimit),
+                    false => {
+                        warn!("Rate limited {} for {}", client, route);
+
+                        Outcome::Error((Status::TooManyRequests, "Rate limit exceeded".to_string()))
+                    }
+                }
+            }
+            None => Outcome::Success(RateLimit),
+        }
+    }
+}
+
+/* ****************************************** Catchers ****************************************** */
+
+/// Catcher for `429 Too Many Requests`.
+#[catch(429)]
+pub fn too_many_requests(request: &Request<'_>) -> Error {
+    let message: String = match decision(request).and_then(|decision| decision.retry_after) {
+        Some(retry_after) => format!(
+            "Rate limit exceeded, retry in {} seconds",
+            seconds(retry_after)
+        ),
+        None => "Rate limit exceeded".to_string(),
+    };
+
+    Error::TooManyRequests(Json(ErrorResponse { message }))
+}
+
+/* ****************************************** Fairing ******************************************* */
+
+/// Fairing which adds rate limit headers to responses of rate limited routes.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::controller::rate_limit::rate_limit_headers;
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build().attach(rate_limit_headers())
+/// }
+/// ```
+pub fn rate_limit_headers() -> AdHoc {
+    AdHoc::on_response("Rate limit headers", |request, response| {
+        let decision: Option<Decision> = decision(request);
+
+        Box::pin(async move {
+            if let Some(decision) = decision {
+                response.set_header(Header::new("RateLimit-Limit", decision.limit.to_string()));
+                response.set_header(Header::new(
+                    "RateLimit-Remaining",
+                    decision.remaining.to_string(),
+                ));
+                response.set_header(Header::new(
+                    "RateLimit-Reset",
+                    seconds(decision.reset).to_string(),
+                ));
+
+                if let Some(retry_after) = decision.retry_after {
+                    response
+                        .set_header(Header::new("Retry-After", seconds(retry_after).to_string()));
+                }
+            }
+        })
+    })
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Identify the client which made a request, by API key name or IP address.
+fn client(request: &Request<'_>) -> String {
+    let key_name: Option<String> = request
+        .rocket()
+        .state::<ApiKeys>()
+        .zip(presented_key(request))
+        .and_then(|(keys, key)| keys.find(key))
+        .map(|key| key.name.clone());
+
+    match (key_name, request.client_ip()) {
+        (Some(name), _) => format!("key:{}", name),
+        (None, Some(ip)) => format!("ip:{}", ip),
+        (None, None) => "unknown".to_string(),
+    }
+}
+
+/// Get the rate limit decision for a request, if it was rate limited.
+fn decision(request: &Request<'_>) -> Option<Decision> {
+    *request.local_cache(|| None::<Decision>)
+}
+
+/// Round a duration up to whole seconds.
+fn seconds(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        time::{Duration, Instant},
+    };
+
+    use rocket::{
+        catchers, get,
+        http::Status,
+        local::blocking::{Client, LocalResponse},
+        routes, Build, Rocket,
+    };
+
+    use super::{
+        rate_limit_headers, too_many_requests, Decision, Limit, RateLimit, RateLimiter, MAX_BUCKETS,
+    };
+
+    #[get("/containers")]
+    fn containers(_limit: RateLimit) {}
+
+    #[get("/containers/<_id>/ads")]
+    fn ads(_id: u32, _limit: RateLimit) {}
+
+    fn limiter() -> RateLimiter {
+        RateLimiter::new(
+            Some(Limit {
+                burst: 10,
+                per_minute: 600,
+            }),
+            HashMap::from([(
+                "/containers".to_string(),
+                Limit {
+                    burst: 2,
+                    per_minute: 30,
+                },
+            )]),
+        )
+    }
+
+    fn rocket() -> Rocket<Build> {
+        rocket::build()
+            .attach(rate_limit_headers())
+            .manage(limiter())
+            .mount("/", routes![ads, containers])
+            .register("/", catchers![too_many_requests])
+    }
+
+    #[test]
+    fn check_refills_bucket() {
+        // Given
+        let limiter: RateLimiter = limiter();
+        let now: Instant = Instant::now();
+        let check = |now: Instant| limiter.check("ip:127.0.0.1", "/containers", now).unwrap();
+
+        // When
+        let decisions: Vec<Decision> = vec![
+            check(now),
+            check(now),
+            check(now),
+            check(now + Duration::from_secs(2)),
+        ];
+
+        // Then
+        assert_eq!(
+            decisions
+                .iter()
+                .map(|decision| decision.allowed)
+                .collect::<Vec<bool>>(),
+            vec![true, true, false, true]
+        );
+        assert_eq!(decisions[2].retry_after, Some(Duration::from_secs(2)));
+        assert_eq!(decisions[2].reset, Duration::from_secs(4));
+    }
+
+    #[test]
+    fn check_limits_clients_separately() {
+        // Given
+        let limiter: RateLimiter = limiter();
+        let now: Instant = Instant::now();
+        limiter.check("ip:127.0.0.1", "/containers", now);
+        limiter.check("ip:127.0.0.1", "/containers", now);
+
+        // When
+        let other_client: Option<Decision> = limiter.check("ip:127.0.0.2", "/containers", now);
+        let other_route: Option<Decision> =
+            limiter.check("ip:127.0.0.1", "/containers/<_id>/ads", now);
+
+        // Then
+        assert!(other_client.unwrap().allowed);
+        assert_eq!(other_route.unwrap().remaining, 9);
+    }
+
+    #[test]
+    fn check_drops_least_recently_used_buckets() {
+        // Given
+        let limiter: RateLimiter = limiter();
+        let now: Instant = Instant::now();
+        limiter.check("ip:127.0.0.1", "/containers", now);
+        limiter.check("ip:127.0.0.1", "/containers", now);
+
+        // When
+        for client in 0..MAX_BUCKETS {
+            limiter.check(&format!("key:{}", client), "/containers", now);
+        }
+        let dropped: Option<Decision> = limiter.check("ip:127.0.0.1", "/containers", now);
+
+        // Then
+        assert_eq!(limiter.buckets.lock().unwrap().len(), MAX_BUCKETS);
+        assert!(dropped.unwrap().allowed);
+    }
+
+    #[test]
+    fn reject_requests_over_limit() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+        client.get("/containers").dispatch();
+        client.get("/containers").dispatch();
+
+        // When
+        let limited: LocalResponse = client.get("/containers").dispatch();
+        let other_route: Status = client.get("/containers/1/ads").dispatch().status();
+
+        // Then
+        assert_eq!(limited.status(), Status::TooManyRequests);
+        assert_eq!(limited.headers().get_one("Retry-After"), Some("2"));
+        assert_eq!(limited.headers().get_one("RateLimit-Limit"), Some("2"));
+        assert_eq!(limited.headers().get_one("RateLimit-Remaining"), Some("0"));
+        assert_eq!(other_route, Status::Ok);
+    }
+}
@@ -0,0 +1,537 @@
+//! API versions.
+//!
+//! Every route is mounted under `/v1` and `/v2`, and at its unversioned path for compatibility
+//! with clients from before versioning. Version 1 responds with bare objects and arrays. Version 2
+//! wraps every response in an [`Envelope`] with `data`, `meta`, `links`, and `warnings`, so that
+//! metadata can be added without breaking clients.
+//!
+//! Requests to unversioned paths are version 1, unless their `Accept` header asks for
+//! [`V2_MEDIA_TYPE`]. Error responses have the same [`ErrorResponse`][1] body in every version.
+//!
+//! [1]: crate::controller::ErrorResponse
+
+use std::fmt::{Display, Formatter};
+
+use chrono::Utc;
+use rocket::{
+    http::Header,
+    response::{self, Responder},
+    serde::json::Json,
+    Request,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{
+    controller::page::Page,
+    service::{
+        advertisement::Advertisement,
+        audit::AuditReport,
+        catalog::CatalogSnapshot,
+        container::{Container, ContainerBatch},
+        image::Image,
+        metadata::ContainerMetadata,
+        mirror::MirrorUsage,
+        report::ExpiringVideo,
+        video::Video,
+        video::VideoAsset,
+    },
+};
+
+/// Media type which selects version 1 on unversioned paths.
+pub const V1_MEDIA_TYPE: &str = "application/vnd.rocket-container.v1+json";
+
+/// Media type which selects version 2 on unversioned paths.
+pub const V2_MEDIA_TYPE: &str = "application/vnd.rocket-container.v2+json";
+
+/* ***************************************** ApiVersion ***************************************** */
+
+/// Version of the API.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::controller::version::ApiVersion;
+///
+/// assert_eq!(ApiVersion::from_prefix("/v2"), Some(ApiVersion::V2));
+/// assert_eq!(ApiVersion::from_media_type("application/json"), None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ApiVersion {
+    /// Bare objects and arrays.
+    #[default]
+    V1,
+    /// Objects and arrays wrapped in an [`Envelope`].
+    V2,
+}
+
+impl ApiVersion {
+    /// Every version, oldest first.
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+
+    /// Get the path prefix routes of the version are mounted under, such as `/v1`.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/v1",
+            ApiVersion::V2 => "/v2",
+        }
+    }
+
+    /// Get the version routes mounted under `prefix` serve, or [None] if `prefix` isn't a
+    /// version's prefix.
+    pub fn from_prefix(prefix: &str) -> Option<Self> {
+        ApiVersion::ALL
+            .into_iter()
+            .find(|version| version.prefix() == prefix)
+    }
+
+    /// Get the media type which selects the version on unversioned paths.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => V1_MEDIA_TYPE,
+            ApiVersion::V2 => V2_MEDIA_TYPE,
+        }
+    }
+
+    /// Get the version a media type selects, or [None] if it doesn't select one.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        if media_type.eq_ignore_ascii_case(V1_MEDIA_TYPE) {
+            Some(ApiVersion::V1)
+        } else if media_type.eq_ignore_ascii_case(V2_MEDIA_TYPE) {
+            Some(ApiVersion::V2)
+        } else {
+            None
+        }
+    }
+
+    /// Get the version a request was made with, from the prefix of its route, or else from its
+    /// `Accept` header.
+    pub fn of(request: &Request<'_>) -> Self {
+        mounted_version(request)
+            .or_else(|| negotiated_version(request))
+            .unwrap_or_default()
+    }
+}
+
+impl Display for ApiVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiVersion::V1 => write!(f, "v1"),
+            ApiVersion::V2 => write!(f, "v2"),
+        }
+    }
+}
+
+/* ****************************************** Envelope ****************************************** */
+
+/// Version 2 response body.
+#[derive(Clone, Debug, Eq, JsonSchema, PartialEq, Serialize)]
+pub struct Envelope<T> {
+    /// Requested object or array.
+    pub data: T,
+    /// Information about the response.
+    pub meta: Meta,
+    /// Links to this response and related responses.
+    pub links: Links,
+    /// Problems with the data which didn't fail the request, such as dangling asset references.
+    pub warnings: Vec<String>,
+}
+
+/// Information about a version 2 response.
+#[derive(Clone, Debug, Default, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    /// API version of the response, always `2`.
+    pub version: u8,
+    /// Number of items in `data`, if it is an array.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    /// Age in seconds of the oldest data served from the local mirror, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mirror_age: Option<u64>,
+}
+
+/// Links of a version 2 response.
+#[derive(Clone, Debug, Default, Eq, JsonSchema, PartialEq, Serialize)]
+pub struct Links {
+    /// Link to this response.
+    #[serde(rename = "self")]
+    pub this: String,
+    /// Link to the next page, if `data` is a page which isn't the last.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
+}
+
+/* ***************************************** Enveloped ****************************************** */
+
+/// Response data which can be wrapped in an [`Envelope`].
+pub trait Enveloped: Serialize {
+    /// Type of the envelope's `data`.
+    type Data: Serialize;
+
+    /// Convert into the envelope's `data`.
+    fn into_data(self) -> Self::Data;
+
+    /// Get the number of items, if the data is a collection.
+    fn count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Get the link to the next page, if the data is a page which isn't the last.
+    fn next(&self) -> Option<String> {
+        None
+    }
+
+    /// Get warnings about the data.
+    fn warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Implement [`Enveloped`] for types which are their own envelope data, without warnings.
+macro_rules! enveloped {
+    ($($data:ty),+) => {
+        $(
+            impl Enveloped for $data {
+                type Data = Self;
+
+                fn into_data(self) -> Self::Data {
+                    self
+                }
+            }
+        )+
+    };
+}
+
+enveloped!(
+    Advertisement,
+    AuditReport,
+    CatalogSnapshot,
+    ContainerMetadata,
+    ExpiringVideo,
+    Image
+);
+
+impl Enveloped for Container {
+    type Data = Self;
+
+    fn into_data(self) -> Self::Data {
+        self
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.videos().iter().flat_map(Video::warnings).collect()
+    }
+}
+
+impl Enveloped for Video {
+    type Data = Self;
+
+    fn into_data(self) -> Self::Data {
+        self
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.assets()
+            .iter()
+            .filter(|asset| asset.is_dangling())
+            .map(|asset| {
+                format!(
+                    "Asset {} {} of video {} doesn't exist",
+                    asset.asset_type(),
+                    asset.asset_id(),
+                    self.id()
+                )
+            })
+            .collect()
+    }
+}
+
+impl Enveloped for VideoAsset {
+    type Data = Self;
+
+    fn into_data(self) -> Self::Data {
+        self
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        match self.is_dangling() {
+            true => vec![format!(
+                "Asset {} {} doesn't exist",
+                self.asset_type(),
+                self.asset_id()
+            )],
+            false => Vec::new(),
+        }
+    }
+}
+
+impl<T: Enveloped> Enveloped for Vec<T> {
+    type Data = Self;
+
+    fn into_data(self) -> Self::Data {
+        self
+    }
+
+    fn count(&self) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.iter().flat_map(T::warnings).collect()
+    }
+}
+
+impl<T: Enveloped> Enveloped for ContainerBatch<T> {
+    type Data = Self;
+
+    fn into_data(self) -> Self::Data {
+        self
+    }
+
+    fn count(&self) -> Option<usize> {
+        Some(self.containers.len())
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.containers.iter().flat_map(T::warnings).collect()
+    }
+}
+
+impl<T: Enveloped> Enveloped for Page<T> {
+    type Data = Vec<T>;
+
+    fn into_data(self) -> Self::Data {
+        self.items
+    }
+
+    fn count(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+
+    fn next(&self) -> Option<String> {
+        self.next.clone()
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        self.items.iter().flat_map(T::warnings).collect()
+    }
+}
+
+/* ***************************************** Versioned ****************************************** */
+
+/// JSON responder in the shape of the request's [`ApiVersion`]: bare in version 1, and wrapped
+/// in an [`Envelope`] in version 2.
+///
+/// Responses to unversioned paths vary by the `Accept` header, and have the media type of the
+/// version it selects as their `Content-Type`, if it selects one.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::{
+///     controller::version::Versioned,
+///     service::advertisement::Advertisement,
+///     types::AdvertisementId,
+/// };
+///
+/// #[get("/advertisement")]
+/// fn advertisement() -> Versioned<Advertisement> {
+///     Versioned(Advertisement::new(
+///         AdvertisementId(1),
+///         "Advertisement".to_string(),
+///         "https://example.com/ad".to_string(),
+///     ))
+/// }
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build()
+///         .mount("/v1", routes![advertisement])
+///         .mount("/v2", routes![advertisement])
+/// }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Versioned<T>(pub T);
+
+impl<'r, T: Enveloped> Responder<'r, 'static> for Versioned<T> {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = match ApiVersion::of(request) {
+            ApiVersion::V1 => Json(self.0).respond_to(request)?,
+            ApiVersion::V2 => Json(envelope(self.0, request)).respond_to(request)?,
+        };
+
+        if mounted_version(request).is_none() {
+            if let Some(version) = negotiated_version(request) {
+                response.set_header(Header::new("Content-Type", version.media_type()));
+            }
+
+            response.adjoin_header(Header::new("Vary", "Accept"));
+        }
+
+        Ok(response)
+    }
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Get the version the `Accept` header of a request selects, or [None] if it doesn't select one.
+fn negotiated_version(request: &Request<'_>) -> Option<ApiVersion> {
+    request.accept().and_then(|accept| {
+        accept.iter().find_map(|media_type| {
+            ApiVersion::from_media_type(&format!("{}/{}", media_type.top(), media_type.sub()))
+        })
+    })
+}
+
+/// Wrap the data of a response to `request` in an [`Envelope`].
+fn envelope<T: Enveloped>(data: T, request: &Request<'_>) -> Envelope<T::Data> {
+    let mirror_age: Option<u64> = MirrorUsage::of(request)
+        .age(Utc::now())
+        .map(|age| age.as_secs());
+    let mut warnings: Vec<String> = data.warnings();
+
+    if let Some(age) = mirror_age {
+        warnings.push(format!(
+            "Served from the local mirror, with data up to {} seconds old",
+            age
+        ));
+    }
+
+    Envelope {
+        meta: Meta {
+            version: 2,
+            count: data.count(),
+            mirror_age,
+        },
+        links: Links {
+            this: request.uri().to_string(),
+            next: data.next(),
+        },
+        warnings,
+        data: data.into_data(),
+    }
+}
+
+/// Get the version of the prefix a request's route is mounted under, or [None] if it's mounted at
+/// an unversioned path.
+fn mounted_version(request: &Request<'_>) -> Option<ApiVersion> {
+    request
+        .route()
+        .and_then(|route| ApiVersion::from_prefix(route.uri.base()))
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use rocket::{
+        get,
+        http::Header,
+        local::blocking::Client,
+        routes,
+        serde::json::{json, Value},
+        Build, Rocket,
+    };
+
+    use crate::{
+        controller::page::Page,
+        service::video::{AssetReference, Video},
+        types::{AssetId, AssetType, VideoId, VideoType},
+    };
+
+    use super::{Versioned, V2_MEDIA_TYPE};
+
+    #[get("/videos")]
+    fn videos() -> Versioned<Page<Video>> {
+        let video: Video = Video::builder(VideoId(1))
+            .asset(AssetReference::new(AssetId(2), AssetType::Image))
+            .description("A rocket launch".to_string())
+            .playback_url("https://example.com/launch.m3u8".to_string())
+            .title("Launch".to_string())
+            .r#type(VideoType::Clip)
+            .build();
+
+        Versioned(Page {
+            items: vec![video],
+            next: Some("/v2/videos?limit=1&cursor=1".to_string()),
+        })
+    }
+
+    fn rocket() -> Rocket<Build> {
+        rocket::build()
+            .mount("/", routes![videos])
+            .mount("/v1", routes![videos])
+            .mount("/v2", routes![videos])
+    }
+
+    fn get(client: &Client, uri: &'static str, accept: Option<&'static str>) -> Value {
+        let mut request = client.get(uri);
+
+        if let Some(accept) = accept {
+            request = request.header(Header::new("Accept", accept));
+        }
+
+        request.dispatch().into_json().unwrap()
+    }
+
+    #[test]
+    fn respond_bare_in_v1() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let v1: Value = get(&client, "/v1/videos", None);
+        let unversioned: Value = get(&client, "/videos", None);
+
+        // Then
+        assert_eq!(v1["items"][0]["id"], json!(1));
+        assert_eq!(v1["next"], json!("/v2/videos?limit=1&cursor=1"));
+        assert_eq!(unversioned, v1);
+    }
+
+    #[test]
+    fn respond_with_envelope_in_v2() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let actual: Value = get(&client, "/v2/videos", None);
+
+        // Then
+        assert_eq!(actual["data"][0]["id"], json!(1));
+        assert_eq!(actual["meta"], json!({"version": 2, "count": 1}));
+        assert_eq!(
+            actual["links"],
+            json!({"self": "/v2/videos", "next": "/v2/videos?limit=1&cursor=1"})
+        );
+        assert_eq!(actual["warnings"], json!([]));
+    }
+
+    #[test]
+    fn select_version_with_accept_header() {
+        // Given
+        let client: Client = Client::tracked(rocket()).unwrap();
+
+        // When
+        let response = client
+            .get("/videos")
+            .header(Header::new("Accept", V2_MEDIA_TYPE))
+            .dispatch();
+
+        // Then
+        assert_eq!(response.headers().get_one("Vary"), Some("Accept"));
+        assert_eq!(
+            response.headers().get_one("Content-Type"),
+            Some(V2_MEDIA_TYPE)
+        );
+        assert_eq!(
+            response.into_json::<Value>().unwrap()["meta"]["version"],
+            json!(2)
+        );
+        assert!(get(&client, "/v1/videos", Some(V2_MEDIA_TYPE))["items"].is_array());
+    }
+}
@@ -5,8 +5,8 @@
 #![deny(rustdoc::invalid_rust_codeblocks)]
 #![deny(rustdoc::missing_crate_level_docs)]
 #![warn(rustdoc::missing_doc_code_examples)]
-#![feature(proc_macro_hygiene, decl_macro)]
 
+pub mod config;
 pub mod controller;
 pub mod repository;
 pub mod service;
@@ -1,62 +1,247 @@
 //! A solution for Bottle Rocket Studio's Rocket Container coding challenge.
+//!
+//! # Flags
+//!
+//! - `--mirror-only`: Serve data from the local mirror only, without calling Rocket Container's
+//!   dependencies. Requires `mirror.path` to be configured.
+//!
+//! # Subcommands
+//!
+//! - `openapi`: Print the OpenAPI document of every route, then exit. The published copy is
+//!   `openapi.json` at the root of the repository.
+//! - `snapshot export <path>`: Export every container as a catalog snapshot to `path`, gzip
+//!   compressed if `path` ends in `.gz`, then exit.
+//! - `snapshot import <path>`: Launch the server with containers served from the catalog snapshot
+//!   at `path`.
 
 #[macro_use]
 extern crate rocket;
 
-use std::sync::Arc;
+use std::{env, path::Path, process, sync::Arc};
 
+use rocket::{fairing::AdHoc, Build, Rocket};
 use rocket_container::{
-    controller::{get_advertisements, get_container, get_images, get_videos, list_containers},
-    repository::{
-        advertisement::AdvertisementRepository, client::Client, image::ImageRepository,
-        video::VideoRepository,
+    config::Config,
+    controller::{
+        auth::{forbidden, unauthorized, ApiKeys},
+        compression::compression,
+        cors::cors,
+        get_openapi,
+        openapi::{openapi, openapi_document},
+        rate_limit::{rate_limit_headers, too_many_requests},
+        routes,
+        version::ApiVersion,
     },
     service::{
-        advertisement::AdvertisementService, container::ContainerService, image::ImageService,
-        video::VideoService,
+        catalog::CatalogSnapshot,
+        container::ContainerService,
+        mirror::{mirror_age_header, track_usage, Mirror},
+        snapshot::expiry_sweeper,
     },
 };
 
-/// Main function for a Rocket application.
-#[launch]
-pub fn rocket() -> _ {
-    let container_service: ContainerService = get_container_service();
-
-    rocket::build().manage(container_service).mount(
-        "/",
-        routes![
-            get_advertisements,
-            get_container,
-            get_images,
-            get_videos,
-            list_containers
-        ],
+/// Flag to serve data from the local mirror only.
+const MIRROR_ONLY_FLAG: &str = "--mirror-only";
+
+/// Usage of the command line.
+const USAGE: &str =
+    "Usage: rocket_container [--mirror-only] [openapi | snapshot (export|import) <path>]";
+
+/// Main function, which runs a subcommand if one is given, or launches the server.
+#[rocket::main]
+async fn main() {
+    let args: Vec<String> = env::args()
+        .skip(1)
+        .filter(|arg| arg != MIRROR_ONLY_FLAG)
+        .collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    if let Err(message) = run(&args).await {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+}
+
+/// Run the subcommand `args` name, or launch the server if there is none.
+async fn run(args: &[&str]) -> Result<(), String> {
+    match args {
+        [] => launch(rocket()?).await,
+        ["openapi"] => print_openapi(),
+        ["snapshot", "export", path] => export_catalog(Path::new(path)).await,
+        ["snapshot", "import", path] => import_catalog(Path::new(path)).await,
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// Build the Rocket application from the default configuration sources.
+///
+/// # Returns
+///
+/// - `Ok(rocket)` if the configuration is valid.
+/// - `Err(message)` if the configuration is malformed, or its services can't be built.
+pub fn rocket() -> Result<Rocket<Build>, String> {
+    build(rocket::build())
+}
+
+/// Build the Rocket application from the configuration of `rocket`.
+fn build(rocket: Rocket<Build>) -> Result<Rocket<Build>, String> {
+    let config: Config = rocket
+        .figment()
+        .extract()
+        .map_err(|err| format!("Invalid configuration: {}", err))?;
+    let mirror: Arc<Mirror> = config
+        .mirror
+        .mirror(env::args().any(|arg| arg == MIRROR_ONLY_FLAG))
+        .map_err(|err| err.message)?;
+    let container_service: ContainerService = config
+        .container_service(mirror.clone())
+        .map_err(|err| err.message)?;
+
+    let api_keys: ApiKeys = config.auth.api_keys();
+
+    if api_keys.is_disabled() {
+        warn!("Authentication is disabled, every route is public");
+    } else if !api_keys.is_enabled() {
+        warn!("No API keys are configured, catalog routes are public and admin routes forbidden");
+    }
+
+    Ok(mount(
+        rocket
+            .attach(AdHoc::config::<Config>())
+            .attach(expiry_sweeper())
+            .attach(mirror_age_header())
+            .attach(rate_limit_headers())
+            .attach(cors(config.cors.clone()))
+            .attach(compression(config.compression.clone()))
+            .attach(openapi_document())
+            .manage(api_keys)
+            .manage(container_service)
+            .manage(config.rate_limit.limiter())
+            .register("/", catchers![forbidden, too_many_requests, unauthorized]),
+    ))
+}
+
+/// Mount every route: versioned routes under each version's prefix and at their unversioned
+/// paths, and the OpenAPI document.
+fn mount(rocket: Rocket<Build>) -> Rocket<Build> {
+    ApiVersion::ALL.into_iter().fold(
+        rocket
+            .mount("/", routes![get_openapi])
+            .mount("/", track_usage(routes())),
+        |rocket, version| rocket.mount(version.prefix(), track_usage(routes())),
     )
 }
 
-fn get_container_service() -> ContainerService {
-    let client: Arc<Client> = Arc::new(Client::default());
-    let advertisement_service: AdvertisementService =
-        AdvertisementService::new(AdvertisementRepository::new(client.clone()));
-    let image_service: ImageService = ImageService::new(ImageRepository::new(client.clone()));
-    let video_service: VideoService = VideoService::new(VideoRepository::new(client));
+/// Launch a Rocket application.
+async fn launch(rocket: Rocket<Build>) -> Result<(), String> {
+    rocket
+        .launch()
+        .await
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// Export every container as a catalog snapshot to `path`.
+async fn export_catalog(path: &Path) -> Result<(), String> {
+    let rocket: Rocket<Build> = rocket()?;
+    let snapshot: CatalogSnapshot = container_service(&rocket)
+        .export_catalog()
+        .await
+        .map_err(|err| err.message)?;
+
+    snapshot.write(path).map_err(|err| err.message)?;
+
+    println!(
+        "Exported {} containers to {}",
+        snapshot.containers().len(),
+        path.display()
+    );
+
+    Ok(())
+}
+
+/// Launch a Rocket application with containers served from the catalog snapshot at `path`.
+async fn import_catalog(path: &Path) -> Result<(), String> {
+    let snapshot: CatalogSnapshot = CatalogSnapshot::read(path).map_err(|err| err.message)?;
+    let rocket: Rocket<Build> = rocket()?;
+
+    container_service(&rocket).import_catalog(snapshot);
+
+    launch(rocket).await
+}
+
+/// Print the OpenAPI document of every route.
+fn print_openapi() -> Result<(), String> {
+    let document: String = serde_json::to_string_pretty(&openapi(mount(rocket::build()).routes()))
+        .map_err(|err| err.to_string())?;
+
+    println!("{}", document);
 
-    ContainerService::new(advertisement_service, image_service, video_service)
+    Ok(())
+}
+
+/// Get the container service managed by a Rocket application.
+fn container_service(rocket: &Rocket<Build>) -> &ContainerService {
+    rocket
+        .state::<ContainerService>()
+        .expect("ContainerService is managed")
 }
 
 /* ******************************************* Tests ******************************************** */
 
 #[cfg(test)]
 mod test {
-    use rocket::http::Status;
+    use chrono::Utc;
+    use rocket::http::{ContentType, Status};
     use rocket::local::blocking::Client;
+    use rocket::serde::json::Value;
+    use rocket_container::{
+        service::{
+            advertisement::Advertisement, catalog::CatalogSnapshot, container::Container,
+            video::Video,
+        },
+        types::{AdvertisementId, ContainerId, VideoId, VideoType},
+    };
+
+    use super::{build, rocket};
+
+    /// Get a client for a Rocket application with authentication disabled, to call admin routes.
+    fn admin_client() -> Client {
+        let figment = rocket::Config::figment().merge(("auth.disabled", true));
+
+        Client::tracked(build(rocket::custom(figment)).unwrap()).expect("valid rocket instance")
+    }
+
+    /// Get a catalog snapshot of `count` containers, each with an advertisement and a video.
+    fn catalog(count: u32) -> CatalogSnapshot {
+        let containers: Vec<Container> = (0..count)
+            .map(|id| {
+                Container::new(
+                    vec![Advertisement::new(
+                        AdvertisementId(id),
+                        format!("Advertisement {}", id),
+                        format!("https://example.com/ads/{}", id),
+                    )],
+                    ContainerId(id),
+                    Vec::new(),
+                    format!("Container {}", id),
+                    vec![Video::builder(VideoId(id))
+                        .description(format!("Description of video {}", id))
+                        .playback_url(format!("/path/to/test{}.m3u8", id))
+                        .title(format!("Video {}", id))
+                        .r#type(VideoType::Clip)
+                        .build()],
+                )
+            })
+            .collect();
 
-    use super::rocket;
+        CatalogSnapshot::new(containers, Utc::now())
+    }
 
     #[test]
     fn list_container() {
         // Given
-        let client = Client::tracked(rocket()).expect("valid rocket instance");
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
 
         // When
         let response = client.get("/containers").dispatch();
@@ -68,7 +253,7 @@ mod test {
     #[test]
     fn get_container() {
         // Given
-        let client = Client::tracked(rocket()).expect("valid rocket instance");
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
 
         // When
         let response = client.get("/containers/0").dispatch();
@@ -80,7 +265,7 @@ mod test {
     #[test]
     fn get_advertisements() {
         // Given
-        let client = Client::tracked(rocket()).expect("valid rocket instance");
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
 
         // When
         let response = client.get("/containers/0/ads").dispatch();
@@ -92,7 +277,7 @@ mod test {
     #[test]
     fn get_images() {
         // Given
-        let client = Client::tracked(rocket()).expect("valid rocket instance");
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
 
         // When
         let response = client.get("/containers/0/images").dispatch();
@@ -104,7 +289,7 @@ mod test {
     #[test]
     fn get_videos() {
         // Given
-        let client = Client::tracked(rocket()).expect("valid rocket instance");
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
 
         // When
         let response = client.get("/containers/0/videos").dispatch();
@@ -112,4 +297,237 @@ mod test {
         // Then
         assert_eq!(response.status(), Status::Ok);
     }
+
+    #[test]
+    fn get_video_assets_invalid_type() {
+        // Given
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
+
+        // When
+        let response = client.get("/videos/0/assets?assetType=VIDEO").dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn get_expiring_report_invalid_within() {
+        // Given
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
+
+        // When
+        let response = client.get("/reports/expiring?within=soon").dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn container_metadata_crud() {
+        // Given
+        let client = admin_client();
+        let uri: &str = "/admin/containers/0/metadata";
+
+        // When
+        let put = client
+            .put(uri)
+            .header(ContentType::JSON)
+            .body(r#"{"title": "My Family", "featured": true}"#)
+            .dispatch()
+            .status();
+        let get = client.get(uri).dispatch().into_string();
+        let delete = client.delete(uri).dispatch().status();
+        let get_deleted = client.get(uri).dispatch().status();
+
+        // Then
+        assert_eq!(put, Status::Ok);
+        assert!(get.is_some_and(|body| body.contains("\"title\":\"My Family\"")));
+        assert_eq!(delete, Status::NoContent);
+        assert_eq!(get_deleted, Status::NotFound);
+    }
+
+    #[test]
+    fn admin_routes_forbidden_without_keys() {
+        // Given
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
+
+        // When
+        let response = client.get("/admin/snapshot").dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[test]
+    fn import_snapshot() {
+        // Given
+        let client = admin_client();
+        let snapshot: &str =
+            r#"{"version": 1, "exportedAt": "2022-03-23T00:00:00Z", "containers": []}"#;
+
+        // When
+        let import = client
+            .put("/admin/snapshot")
+            .body(snapshot)
+            .dispatch()
+            .status();
+        let list = client.get("/containers").dispatch().status();
+
+        // Then
+        assert_eq!(import, Status::NoContent);
+        assert_eq!(list, Status::Ok);
+    }
+
+    #[test]
+    fn import_exported_snapshot() {
+        // Given
+        let client = admin_client();
+        let snapshot: Vec<u8> = catalog(500).to_bytes(false).unwrap();
+        client.put("/admin/snapshot").body(snapshot).dispatch();
+
+        // When
+        let exported: String = client
+            .get("/admin/snapshot")
+            .dispatch()
+            .into_string()
+            .unwrap();
+        let import = client
+            .put("/admin/snapshot")
+            .body(exported.clone())
+            .dispatch()
+            .status();
+        let reexported: String = client
+            .get("/admin/snapshot")
+            .dispatch()
+            .into_string()
+            .unwrap();
+
+        // Then
+        let containers =
+            |body: &str| serde_json::from_str::<Value>(body).unwrap()["containers"].take();
+        assert!(exported.len() > 8 * 1024);
+        assert_eq!(import, Status::NoContent);
+        assert_eq!(containers(&reexported), containers(&exported));
+        assert_eq!(containers(&exported).as_array().map(Vec::len), Some(500));
+    }
+
+    #[test]
+    fn import_snapshot_too_large() {
+        // Given
+        let client = Client::tracked(
+            build(rocket::custom(
+                rocket::Config::figment()
+                    .merge(("auth.disabled", true))
+                    .merge(("limits.snapshot", "1 KiB")),
+            ))
+            .unwrap(),
+        )
+        .expect("valid rocket instance");
+        let snapshot: Vec<u8> = catalog(100).to_bytes(false).unwrap();
+
+        // When
+        let response = client.put("/admin/snapshot").body(snapshot).dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+    }
+
+    #[test]
+    fn import_snapshot_unsupported_version() {
+        // Given
+        let client = admin_client();
+        let snapshot: &str =
+            r#"{"version": 99, "exportedAt": "2022-03-23T00:00:00Z", "containers": []}"#;
+
+        // When
+        let response = client.put("/admin/snapshot").body(snapshot).dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn put_container_metadata_invalid_body() {
+        // Given
+        let client = admin_client();
+
+        // When
+        let response = client
+            .put("/admin/containers/0/metadata")
+            .header(ContentType::JSON)
+            .body(r#"{"featured": "yes"}"#)
+            .dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn batch_get_containers_reports_missing() {
+        // Given
+        let client = admin_client();
+        let snapshot: &str =
+            r#"{"version": 1, "exportedAt": "2022-03-23T00:00:00Z", "containers": []}"#;
+
+        client.put("/admin/snapshot").body(snapshot).dispatch();
+
+        // When
+        let response = client
+            .post("/containers:batchGet")
+            .header(ContentType::JSON)
+            .body(r#"{"ids": [5, 1, 5]}"#)
+            .dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.into_string().as_deref(),
+            Some(r#"{"containers":[],"missing":[5,1]}"#)
+        );
+    }
+
+    #[test]
+    fn batch_get_containers_invalid_body() {
+        // Given
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
+
+        // When
+        let response = client
+            .post("/containers:batchGet")
+            .header(ContentType::JSON)
+            .body(r#"{"ids": "1,5,9"}"#)
+            .dispatch();
+
+        // Then
+        assert_eq!(response.status(), Status::BadRequest);
+    }
+
+    #[test]
+    fn openapi_matches_published_document() {
+        // Given
+        let client = Client::tracked(rocket().unwrap()).expect("valid rocket instance");
+        let expected: Value = serde_json::from_str(include_str!("../openapi.json")).unwrap();
+
+        // When
+        let actual: Value = client.get("/openapi.json").dispatch().into_json().unwrap();
+
+        // Then
+        assert!(
+            actual == expected,
+            "openapi.json has drifted from the routes, regenerate it with \
+            `cargo run -- openapi > openapi.json`"
+        );
+    }
+
+    #[test]
+    fn rocket_rejects_malformed_config() {
+        // Given
+        let figment = rocket::Config::figment().merge(("pagination.default_page_size", "many"));
+
+        // When
+        let result = build(rocket::custom(figment));
+
+        // Then
+        assert!(result.is_err_and(|message| message.starts_with("Invalid configuration")));
+    }
 }
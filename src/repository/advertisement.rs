@@ -25,7 +25,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     repository::client::Client,
     service::advertisement::Advertisement,
-    types::{array_to_string, Result},
+    types::{array_to_string, deserialize_id, serialize_id, AdvertisementId, ContainerId, Result},
 };
 
 /// Endpoint for Rocket Advertisement service.
@@ -54,9 +54,11 @@ const CONTAINER_ID: &str = "containerId";
 #[serde(rename_all = "camelCase")]
 pub struct AdvertisementDto {
     /// Parent container e.g. show/series identifier.
-    container_id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    container_id: ContainerId,
     /// Unique advertisement identifier.
-    id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    id: AdvertisementId,
     /// Name of advertisement.
     name: String,
     /// Advertisement playback url.
@@ -65,8 +67,8 @@ pub struct AdvertisementDto {
 
 impl AdvertisementDto {
     /// Get container ID.
-    pub fn container_id(&self) -> &str {
-        &self.container_id
+    pub fn container_id(&self) -> ContainerId {
+        self.container_id
     }
 }
 
@@ -74,7 +76,7 @@ impl From<AdvertisementDto> for Advertisement {
     /// Get an [`Advertisement`] from an [`AdvertisementDto`].
     fn from(advertisement_dto: AdvertisementDto) -> Self {
         Advertisement::new(
-            advertisement_dto.id.parse().unwrap(),
+            advertisement_dto.id,
             advertisement_dto.name,
             advertisement_dto.url,
         )
@@ -134,7 +136,7 @@ pub struct AdvertisementRepository {
     client: Arc<Client>,
 }
 
-impl<'a> AdvertisementRepository {
+impl AdvertisementRepository {
     /// Create a new [`AdvertisementRepository`].
     pub fn new(client: Arc<Client>) -> Self {
         AdvertisementRepository { client }
@@ -169,9 +171,12 @@ impl<'a> AdvertisementRepository {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::repository::advertisement::{AdvertisementDto, AdvertisementRepository};
+    /// use rocket_container::{
+    ///     repository::advertisement::{AdvertisementDto, AdvertisementRepository},
+    ///     types::ContainerId,
+    /// };
     ///
-    /// let container_id: u32 = 1;
+    /// let container_id: ContainerId = ContainerId(1);
     /// let repository: AdvertisementRepository = AdvertisementRepository::default();
     /// let advertisements: Vec<AdvertisementDto> = repository
     ///     .list_advertisements_by_container(container_id)
@@ -179,7 +184,7 @@ impl<'a> AdvertisementRepository {
     /// ```
     pub async fn list_advertisements_by_container(
         &self,
-        container_id: u32,
+        container_id: ContainerId,
     ) -> Result<Vec<AdvertisementDto>> {
         trace!(
             "AdvertisementRepository::list_advertisements_by_container {}",
@@ -188,7 +193,7 @@ impl<'a> AdvertisementRepository {
 
         let advertisements: Vec<AdvertisementDto> = self
             .client
-            .get::<AdvertisementsDto, [(&str, u32); 1]>(
+            .get::<AdvertisementsDto, [(&str, ContainerId); 1]>(
                 ADVERTISEMENT_ENDPOINT,
                 Some([(CONTAINER_ID, container_id)]),
             )
@@ -205,7 +210,7 @@ impl<'a> AdvertisementRepository {
 
 #[cfg(test)]
 mod test {
-    use crate::types::Result;
+    use crate::types::{AdvertisementId, ContainerId, Result};
 
     use super::{AdvertisementDto, AdvertisementRepository, AdvertisementsDto};
 
@@ -222,8 +227,8 @@ mod test {
         "#;
 
         let expected: AdvertisementDto = AdvertisementDto {
-            container_id: 0.to_string(),
-            id: 0.to_string(),
+            container_id: ContainerId(0),
+            id: AdvertisementId(0),
             name: "Advertisement".to_string(),
             url: "https://advertisement.com".to_string(),
         };
@@ -256,8 +261,8 @@ mod test {
 
         let expected: AdvertisementsDto = AdvertisementsDto {
             advertisements: Vec::from([AdvertisementDto {
-                container_id: 0.to_string(),
-                id: 0.to_string(),
+                container_id: ContainerId(0),
+                id: AdvertisementId(0),
                 name: "Advertisement".to_string(),
                 url: "https://advertisement.com".to_string(),
             }]),
@@ -277,8 +282,8 @@ mod test {
     fn serialize_advertisement() {
         // Given
         let data: AdvertisementDto = AdvertisementDto {
-            container_id: 0.to_string(),
-            id: 0.to_string(),
+            container_id: ContainerId(0),
+            id: AdvertisementId(0),
             name: "Advertisement".to_string(),
             url: "https://advertisement.com".to_string(),
         };
@@ -307,8 +312,8 @@ mod test {
         // Given
         let data: AdvertisementsDto = AdvertisementsDto {
             advertisements: Vec::from([AdvertisementDto {
-                container_id: 0.to_string(),
-                id: 0.to_string(),
+                container_id: ContainerId(0),
+                id: AdvertisementId(0),
                 name: "Advertisement".to_string(),
                 url: "https://advertisement.com".to_string(),
             }]),
@@ -356,7 +361,7 @@ mod test {
     async fn test_list_advertisements_by_container() {
         // Given
         let repository = AdvertisementRepository::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
         let result: Result<Vec<AdvertisementDto>> = repository
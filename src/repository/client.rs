@@ -54,6 +54,7 @@ impl Client {
     ///
     /// - **200 - OK:**                     `Ok(response)`
     /// - **400 - Bad Request:**            `Err(`[`ErrorKind::Permanent`]`)`
+    /// - **404 - Not Found:**              `Err(`[`ErrorKind::NotFound`]`)`
     /// - **500 - Internal Server Error:**  `Err(`[`ErrorKind::Transient`]`)`
     /// - **Everything else:**              `Err(`[`ErrorKind::Permanent`]`)`
     ///
@@ -118,8 +119,8 @@ impl Client {
     ///
     /// Takes an operation which returns [`Result`][1]<T, [`Error`][2]>. If the operations returns [Ok]
     /// then this function returns the same value. If the operation returns [Err] of
-    /// [`ErrorKind::Permanent`] then the error is returned. However if the operation returns [Err] of
-    /// [`ErrorKind::Transient`] then the operation is retried up to [`MAX_ATTEMPTS`] times.
+    /// [`ErrorKind::Transient`] then the operation is retried up to [`MAX_ATTEMPTS`] times. Any
+    /// other [Err] is returned immediately.
     ///  
     /// [1]: crate::types::Result
     /// [2]: crate::types::Error
@@ -134,7 +135,7 @@ impl Client {
             match f().await {
                 Ok(data) => return Ok(data),
                 Err(err) => {
-                    if err.kind == ErrorKind::Permanent {
+                    if err.kind != ErrorKind::Transient {
                         error!("Attempt #{} returned with un-retryable error {}", i, err);
 
                         return Err(err);
@@ -148,7 +149,7 @@ impl Client {
             thread::sleep(Duration::from_millis(backoff));
         }
 
-        return f().await;
+        f().await
     }
 
     /// Make a GET request.
@@ -159,6 +160,7 @@ impl Client {
     ///
     /// - **200 - OK:** `Ok(response)`
     /// - **400 - Bad Request:** `Err(ErrorKind::Permanent)`
+    /// - **404 - Not Found:** `Err(ErrorKind::NotFound)`
     /// - **500 - Internal Server Error:** `Err(ErrorKind::Transient)`
     /// - **Everything else** - `Err(ErrorKind::Permanent)`
     async fn send(request_builder: RequestBuilder) -> Result<Response> {
@@ -168,7 +170,7 @@ impl Client {
                     Ok(response)
                 } else if response.status() == StatusCode::NOT_FOUND {
                     Err(Error {
-                        kind: ErrorKind::Permanent,
+                        kind: ErrorKind::NotFound,
                         message: "Resource not found".to_string(),
                     })
                 } else if response.status() == StatusCode::INTERNAL_SERVER_ERROR {
@@ -195,12 +197,15 @@ impl Client {
 
 #[cfg(test)]
 mod test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
     use serde::Deserialize;
 
-    use crate::types::Result;
+    use crate::types::{Error, ErrorKind, Result};
 
     use super::Client;
 
+    #[allow(dead_code)]
     #[derive(Deserialize)]
     struct CatFact {
         fact: String,
@@ -221,4 +226,28 @@ mod test {
         // Then
         assert!(result.is_ok(), "Result should be Ok");
     }
+
+    #[tokio::test]
+    async fn retry_not_found() {
+        // Given
+        let attempts: AtomicU32 = AtomicU32::new(0);
+        let op = || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+
+            Err::<(), Error>(Error {
+                kind: ErrorKind::NotFound,
+                message: "Resource not found".to_string(),
+            })
+        };
+
+        // When
+        let result: Result<()> = Client::retry(op).await;
+
+        // Then
+        match result {
+            Ok(_) => panic!("Retry should return the error"),
+            Err(err) => assert_eq!(err.kind, ErrorKind::NotFound),
+        }
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
 }
@@ -22,7 +22,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     repository::client::Client,
     service::image::Image,
-    types::{array_to_string, Result},
+    types::{array_to_string, deserialize_id, serialize_id, ContainerId, ImageId, Result},
 };
 
 /// Container ID query parameter.
@@ -47,9 +47,11 @@ const IMAGE_ENDPOINT: &str = "http://images.rocket-stream.bottlerocketservices.c
 #[serde(rename_all = "camelCase")]
 pub struct ImageDto {
     /// Parent container e.g. show/series identifier.
-    container_id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    container_id: ContainerId,
     /// Unique image identifier.
-    id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    id: ImageId,
     /// Name of image.
     name: String,
     /// Image URL.
@@ -58,15 +60,15 @@ pub struct ImageDto {
 
 impl ImageDto {
     /// Get container ID.
-    pub fn container_id(&self) -> &str {
-        &self.container_id
+    pub fn container_id(&self) -> ContainerId {
+        self.container_id
     }
 }
 
 impl From<ImageDto> for Image {
     /// Get an [`Image`] from an [`ImageDto`].
     fn from(image_dto: ImageDto) -> Self {
-        Image::new(image_dto.id.parse().unwrap(), image_dto.name, image_dto.url)
+        Image::new(image_dto.id, image_dto.name, image_dto.url)
     }
 }
 
@@ -150,18 +152,24 @@ impl ImageRepository {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::repository::image::{ImageDto, ImageRepository};
+    /// use rocket_container::{
+    ///     repository::image::{ImageDto, ImageRepository},
+    ///     types::ContainerId,
+    /// };
     ///
-    /// let container_id: u32 = 1;
+    /// let container_id: ContainerId = ContainerId(1);
     /// let repository: ImageRepository = ImageRepository::default();
     /// let images: Vec<ImageDto> = repository.list_images_by_container(container_id).await?;
     /// ```
-    pub async fn list_images_by_container(&self, container_id: u32) -> Result<Vec<ImageDto>> {
+    pub async fn list_images_by_container(
+        &self,
+        container_id: ContainerId,
+    ) -> Result<Vec<ImageDto>> {
         trace!("Listing images for container {}", container_id);
 
         let images: Vec<ImageDto> = self
             .client
-            .get::<ImagesDto, [(&str, u32); 1]>(
+            .get::<ImagesDto, [(&str, ContainerId); 1]>(
                 IMAGE_ENDPOINT,
                 Some([(CONTAINER_ID, container_id)]),
             )
@@ -174,7 +182,7 @@ impl ImageRepository {
 
 #[cfg(test)]
 mod test {
-    use crate::types::Result;
+    use crate::types::{ContainerId, ImageId, Result};
 
     use super::{ImageDto, ImageRepository, ImagesDto};
 
@@ -191,8 +199,8 @@ mod test {
         "#;
 
         let expected: ImageDto = ImageDto {
-            container_id: 0.to_string(),
-            id: 0.to_string(),
+            container_id: ContainerId(0),
+            id: ImageId(0),
             name: "Image".to_string(),
             url: "https://image.com".to_string(),
         };
@@ -225,8 +233,8 @@ mod test {
 
         let expected: ImagesDto = ImagesDto {
             images: Vec::from([ImageDto {
-                container_id: 0.to_string(),
-                id: 0.to_string(),
+                container_id: ContainerId(0),
+                id: ImageId(0),
                 name: "Image".to_string(),
                 url: "https://image.com".to_string(),
             }]),
@@ -246,8 +254,8 @@ mod test {
     fn serialize_image() {
         // Given
         let data: ImageDto = ImageDto {
-            container_id: 0.to_string(),
-            id: 0.to_string(),
+            container_id: ContainerId(0),
+            id: ImageId(0),
             name: "Image".to_string(),
             url: "https://image.com".to_string(),
         };
@@ -270,8 +278,8 @@ mod test {
         // Given
         let data: ImagesDto = ImagesDto {
             images: Vec::from([ImageDto {
-                container_id: 0.to_string(),
-                id: 0.to_string(),
+                container_id: ContainerId(0),
+                id: ImageId(0),
                 name: "Image".to_string(),
                 url: "https://image.com".to_string(),
             }]),
@@ -309,7 +317,7 @@ mod test {
     async fn test_list_images_by_container() {
         // Given
         let repository = ImageRepository::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
         let result: Result<Vec<ImageDto>> = repository.list_images_by_container(container_id).await;
@@ -9,7 +9,10 @@ use serde::{Deserialize, Serialize};
 use crate::{
     repository::client::Client,
     service::video::{AssetReference, Video, VideoBuilder},
-    types::{array_to_string, AssetType, Result, VideoType},
+    types::{
+        array_to_string, deserialize_id, parse_timestamp, serialize_id, AssetId, AssetType,
+        ContainerId, Result, VideoId, VideoType,
+    },
 };
 
 /// Asset reference endpoint suffix.
@@ -37,9 +40,12 @@ const VIDEO_TYPE: &str = "type";
 /// # Examples
 ///
 /// ```rust
-/// use rocket_container::repository::video::{AssetReferenceDto, VideoRepository};
+/// use rocket_container::{
+///     repository::video::{AssetReferenceDto, VideoRepository},
+///     types::VideoId,
+/// };
 ///
-/// let video_id: u32 = 1;
+/// let video_id: VideoId = VideoId(1);
 /// let repository: VideoRepository = VideoRepository::default();
 /// let advertisements: Vec<AssetReferenceDto> = repository.list_asset_references(video_id).await?;
 /// ```
@@ -47,11 +53,13 @@ const VIDEO_TYPE: &str = "type";
 #[serde(rename_all = "camelCase")]
 pub struct AssetReferenceDto {
     /// Unique identifier for referenced asset.
-    asset_id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    asset_id: AssetId,
     /// Type of asset.
     asset_type: AssetType,
     /// Unique identifier for referenced video.
-    video_id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    video_id: VideoId,
 }
 
 impl AssetReferenceDto {
@@ -61,13 +69,18 @@ impl AssetReferenceDto {
     ///
     /// ```rust
     /// ```
-    pub fn new(asset_id: String, asset_type: AssetType, video_id: String) -> Self {
+    pub fn new(asset_id: AssetId, asset_type: AssetType, video_id: VideoId) -> Self {
         AssetReferenceDto {
             asset_id,
             asset_type,
             video_id,
         }
     }
+
+    /// Get type of asset.
+    pub fn asset_type(&self) -> &AssetType {
+        &self.asset_type
+    }
 }
 
 impl Display for AssetReferenceDto {
@@ -88,10 +101,7 @@ impl From<AssetReferenceDto> for AssetReference {
     /// ```rust
     /// ```
     fn from(asset_reference_dto: AssetReferenceDto) -> Self {
-        AssetReference::new(
-            asset_reference_dto.asset_id.parse().unwrap(),
-            asset_reference_dto.asset_type,
-        )
+        AssetReference::new(asset_reference_dto.asset_id, asset_reference_dto.asset_type)
     }
 }
 
@@ -111,13 +121,15 @@ impl From<AssetReferenceDto> for AssetReference {
 #[serde(rename_all = "camelCase")]
 pub struct VideoDto {
     /// Parent container e.g. show/series identifier.
-    container_id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    container_id: ContainerId,
     /// Brief description of the video.
     description: String,
-    /// Expiration date for video in ISO-8601 format.
+    /// Expiration date for video in ISO-8601 format, or empty if the video never expires.
     expiration_date: String,
     /// Unique video identifier.
-    id: String,
+    #[serde(deserialize_with = "deserialize_id", serialize_with = "serialize_id")]
+    id: VideoId,
     /// URL for video playback.
     playback_url: String,
     /// Video title.
@@ -134,10 +146,10 @@ impl VideoDto {
     /// ```rust
     /// ```
     pub fn new(
-        container_id: String,
+        container_id: ContainerId,
         description: String,
         expiration_date: String,
-        id: String,
+        id: VideoId,
         playback_url: String,
         title: String,
         r#type: VideoType,
@@ -154,8 +166,8 @@ impl VideoDto {
     }
 
     /// Get container ID.
-    pub fn container_id(&self) -> &str {
-        &self.container_id
+    pub fn container_id(&self) -> ContainerId {
+        self.container_id
     }
 
     /// Get Video ID.
@@ -164,8 +176,13 @@ impl VideoDto {
     ///
     /// ```rust
     /// ```
-    pub fn id(&self) -> &str {
-        &self.id
+    pub fn id(&self) -> VideoId {
+        self.id
+    }
+
+    /// Get type of video.
+    pub fn r#type(&self) -> &VideoType {
+        &self.r#type
     }
 }
 
@@ -182,14 +199,17 @@ impl Display for VideoDto {
 impl From<VideoDto> for VideoBuilder {
     /// Get a [Video] from a [VideoDto].
     ///
+    /// The expiration date is parsed with [parse_timestamp], so empty or invalid dates become a
+    /// video which never expires.
+    ///
     /// # Examples
     ///
     /// ```rust
     /// ```
     fn from(video_dto: VideoDto) -> VideoBuilder {
-        Video::builder(video_dto.id.parse().unwrap())
+        Video::builder(video_dto.id)
             .description(video_dto.description)
-            .expiration_date(video_dto.expiration_date)
+            .expires_at(parse_timestamp(&video_dto.expiration_date))
             .playback_url(video_dto.playback_url)
             .title(video_dto.title)
             .r#type(video_dto.r#type)
@@ -271,13 +291,16 @@ impl VideoRepository {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::repository::video::{VideoDto, VideoRepository};
+    /// use rocket_container::{
+    ///     repository::video::{VideoDto, VideoRepository},
+    ///     types::VideoId,
+    /// };
     ///
-    /// let video_id: u32 = 1;
+    /// let video_id: VideoId = VideoId(1);
     /// let repository: VideoRepository = VideoRepository::default();
     /// let advertisements: VideoDto = repository.get_video(video_id).await?;
     /// ```
-    pub async fn get_video(&self, video_id: u32) -> Result<VideoDto> {
+    pub async fn get_video(&self, video_id: VideoId) -> Result<VideoDto> {
         trace!("VideoRepository::get_video {}", video_id);
 
         self.client
@@ -290,14 +313,17 @@ impl VideoRepository {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::repository::video::{AssetReferenceDto, VideoRepository};
+    /// use rocket_container::{
+    ///     repository::video::{AssetReferenceDto, VideoRepository},
+    ///     types::VideoId,
+    /// };
     ///
-    /// let video_id: u32 = 1;
+    /// let video_id: VideoId = VideoId(1);
     /// let repository: VideoRepository = VideoRepository::default();
     /// let advertisements: Vec<AssetReferenceDto> =
     ///     repository.list_asset_references(video_id).await?;
     /// ```
-    pub async fn list_asset_references(&self, video_id: u32) -> Result<Vec<AssetReferenceDto>> {
+    pub async fn list_asset_references(&self, video_id: VideoId) -> Result<Vec<AssetReferenceDto>> {
         trace!("VideoRepository::list_asset_references {}", video_id);
 
         let asset_references: Vec<AssetReferenceDto> = self
@@ -319,10 +345,10 @@ impl VideoRepository {
     /// ```rust
     /// use rocket_container::{
     ///     repository::video::{AssetReferenceDto, VideoRepository},
-    ///     types::AssetType,
+    ///     types::{AssetType, VideoId},
     /// };
     ///
-    /// let video_id: u32 = 1;
+    /// let video_id: VideoId = VideoId(1);
     /// let asset_type: AssetType = AssetType::Image;
     /// let repository: VideoRepository = VideoRepository::default();
     /// let advertisements: Vec<AssetReferenceDto> =
@@ -330,7 +356,7 @@ impl VideoRepository {
     /// ```
     pub async fn list_asset_references_by_type(
         &self,
-        video_id: u32,
+        video_id: VideoId,
         asset_type: AssetType,
     ) -> Result<Vec<AssetReferenceDto>> {
         trace!(
@@ -379,12 +405,15 @@ impl VideoRepository {
     ///
     /// ```rust
     /// ```
-    pub async fn list_videos_by_container(&self, container_id: u32) -> Result<Vec<VideoDto>> {
+    pub async fn list_videos_by_container(
+        &self,
+        container_id: ContainerId,
+    ) -> Result<Vec<VideoDto>> {
         trace!("VideoRepository::list_videos_by_container {}", container_id);
 
         let videos: Vec<VideoDto> = self
             .client
-            .get::<VideosDto, [(&str, u32); 1]>(
+            .get::<VideosDto, [(&str, ContainerId); 1]>(
                 VIDEO_ENDPOINT,
                 Some([(CONTAINER_ID, container_id)]),
             )
@@ -423,7 +452,7 @@ impl VideoRepository {
     /// ```
     pub async fn list_videos_by_container_and_type(
         &self,
-        container_id: u32,
+        container_id: ContainerId,
         video_type: VideoType,
     ) -> Result<Vec<VideoDto>> {
         trace!(
@@ -452,7 +481,7 @@ impl VideoRepository {
 
 #[cfg(test)]
 mod test {
-    use crate::types::{AssetType, Result, VideoType};
+    use crate::types::{AssetId, AssetType, ContainerId, Result, VideoId, VideoType};
 
     use super::{AssetReferenceDto, VideoDto, VideoRepository, VideosDto};
 
@@ -468,9 +497,9 @@ mod test {
         "#;
 
         let expected: AssetReferenceDto = AssetReferenceDto {
-            asset_id: 0.to_string(),
+            asset_id: AssetId(0),
             asset_type: AssetType::Ad,
-            video_id: 0.to_string(),
+            video_id: VideoId(0),
         };
 
         // When
@@ -499,10 +528,10 @@ mod test {
         "#;
 
         let expected: VideoDto = VideoDto {
-            container_id: 0.to_string(),
+            container_id: ContainerId(0),
             description: "A short video clip".to_string(),
             expiration_date: "2022-03-23".to_string(),
-            id: 0.to_string(),
+            id: VideoId(0),
             playback_url: "https://www.youtube.com/watch?v=00000000000".to_string(),
             title: "Video".to_string(),
             r#type: VideoType::Clip,
@@ -518,6 +547,60 @@ mod test {
         }
     }
 
+    #[test]
+    fn deserialize_unknown_video_type() {
+        // Given
+        let data: &str = r#"
+            {
+                "videos": [
+                    {
+                        "containerId": "0",
+                        "description": "A trailer",
+                        "expirationDate": "",
+                        "id": "0",
+                        "playbackUrl": "https://www.youtube.com/watch?v=00000000000",
+                        "title": "Trailer",
+                        "type": "TRAILER"
+                    }
+                ]
+            }
+        "#;
+
+        // When
+        let result: serde_json::Result<VideosDto> = serde_json::from_str(data);
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(
+                actual.videos[0].r#type(),
+                &VideoType::Unknown("TRAILER".to_string())
+            ),
+            Err(err) => panic!("Failed to deserialize with error: {}", err),
+        }
+    }
+
+    #[test]
+    fn deserialize_video_invalid_id() {
+        // Given
+        let data: &str = r#"
+            {
+                "containerId": "0",
+                "description": "A short video clip",
+                "expirationDate": "2022-03-23",
+                "id": "video-0",
+                "playbackUrl": "https://www.youtube.com/watch?v=00000000000",
+                "title": "Video",
+                "type": "CLIP"
+            }
+        "#;
+
+        // When
+        let result: serde_json::Result<VideoDto> = serde_json::from_str(data);
+
+        // Then
+        assert!(result.is_err(), "Invalid video ID should not deserialize");
+    }
+
     #[test]
     fn deserialize_videos() {
         // Given
@@ -539,10 +622,10 @@ mod test {
 
         let expected: VideosDto = VideosDto {
             videos: Vec::from([VideoDto {
-                container_id: 0.to_string(),
+                container_id: ContainerId(0),
                 description: "A short video clip".to_string(),
                 expiration_date: "2022-03-23".to_string(),
-                id: 0.to_string(),
+                id: VideoId(0),
                 playback_url: "https://www.youtube.com/watch?v=00000000000".to_string(),
                 title: "Video".to_string(),
                 r#type: VideoType::Clip,
@@ -563,9 +646,9 @@ mod test {
     fn serialize_asset_reference() {
         // Given
         let data: AssetReferenceDto = AssetReferenceDto {
-            asset_id: 0.to_string(),
+            asset_id: AssetId(0),
             asset_type: AssetType::Ad,
-            video_id: 0.to_string(),
+            video_id: VideoId(0),
         };
 
         let expected: &str = r#"{"assetId":"0","assetType":"AD","videoId":"0"}"#;
@@ -584,10 +667,10 @@ mod test {
     fn serialize_video() {
         // Given
         let data: VideoDto = VideoDto {
-            container_id: 0.to_string(),
+            container_id: ContainerId(0),
             description: "A short video clip".to_string(),
             expiration_date: "2022-03-23".to_string(),
-            id: 0.to_string(),
+            id: VideoId(0),
             playback_url: "https://www.youtube.com/watch?v=00000000000".to_string(),
             title: "Video".to_string(),
             r#type: VideoType::Clip,
@@ -620,10 +703,10 @@ mod test {
         // Given
         let data: VideosDto = VideosDto {
             videos: Vec::from([VideoDto {
-                container_id: 0.to_string(),
+                container_id: ContainerId(0),
                 description: "A short video clip".to_string(),
                 expiration_date: "2022-03-23".to_string(),
-                id: 0.to_string(),
+                id: VideoId(0),
                 playback_url: "https://www.youtube.com/watch?v=00000000000".to_string(),
                 title: "Video".to_string(),
                 r#type: VideoType::Clip,
@@ -660,14 +743,14 @@ mod test {
     async fn test_get_video() {
         // Given
         let repository = VideoRepository::default();
-        let video_id: u32 = 1301;
+        let video_id: VideoId = VideoId(1301);
 
         // When
         let result: Result<VideoDto> = repository.get_video(video_id).await;
 
         // Then
         match result {
-            Ok(actual) => assert_eq!(video_id.to_string(), actual.id()),
+            Ok(actual) => assert_eq!(video_id, actual.id()),
             Err(err) => panic!("Failed to list videos with error: {}", err),
         }
     }
@@ -676,7 +759,7 @@ mod test {
     async fn test_list_asset_references() {
         // Given
         let repository = VideoRepository::default();
-        let video_id: u32 = 1404;
+        let video_id: VideoId = VideoId(1404);
 
         // When
         let result: Result<Vec<AssetReferenceDto>> =
@@ -694,7 +777,7 @@ mod test {
         // Given
         let repository = VideoRepository::default();
         let asset_type: AssetType = AssetType::Image;
-        let video_id: u32 = 1404;
+        let video_id: VideoId = VideoId(1404);
 
         // When
         let result: Result<Vec<AssetReferenceDto>> = repository
@@ -725,7 +808,7 @@ mod test {
     async fn test_list_videos_by_container() {
         // Given
         let repository = VideoRepository::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
         let result: Result<Vec<VideoDto>> = repository.list_videos_by_container(container_id).await;
@@ -757,7 +840,7 @@ mod test {
     async fn test_list_videos_by_container_and_type() {
         // Given
         let repository = VideoRepository::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
         let video_type: VideoType = VideoType::Movie;
 
         // When
@@ -2,13 +2,17 @@
 
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use log::trace;
+use lru::LruCache;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::repository::advertisement::AdvertisementRepository;
-use crate::service::group;
-use crate::types::Result;
+use crate::service::{group, mirror::Mirror, ABSENT_TTL, CACHE_CAPACITY, CACHE_TTL};
+use crate::types::{AdvertisementId, ContainerId, Error, ErrorKind, Result};
 
 /* *************************************** Advertisement **************************************** */
 
@@ -22,11 +26,11 @@ use crate::types::Result;
 /// [1]: [crate::repository::types::advertisement::AdvertisementDto]
 /// [2]: [crate::repository::advertisement]
 ///
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Advertisement {
     /// Unique advertisement identifier.
-    id: u32,
+    id: AdvertisementId,
     /// Name of advertisement.
     name: String,
     /// Advertisement playback url.
@@ -35,9 +39,24 @@ pub struct Advertisement {
 
 impl Advertisement {
     /// Construct a new Advertisement.
-    pub fn new(id: u32, name: String, url: String) -> Self {
+    pub fn new(id: AdvertisementId, name: String, url: String) -> Self {
         Advertisement { id, name, url }
     }
+
+    /// Get advertisement ID.
+    pub fn id(&self) -> AdvertisementId {
+        self.id
+    }
+
+    /// Get name of advertisement.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get advertisement playback url.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
 }
 
 impl Display for Advertisement {
@@ -52,7 +71,7 @@ impl Display for Advertisement {
 
 /* ************************************** AdvertisementMap ************************************** */
 
-/// Type alias for a [`HashMap`] of [`u32`] to [`Vec`]`<`[`Advertisement`]`>`.
+/// Type alias for a [`HashMap`] of [`ContainerId`] to [`Vec`]`<`[`Advertisement`]`>`.
 ///
 /// # Examples
 ///
@@ -62,7 +81,7 @@ impl Display for Advertisement {
 /// let service: AdvertisementService = AdvertisementService::default();
 /// let advertisement_map: AdvertisementMap = service.list_advertisements().await?;
 /// ```
-pub type AdvertisementMap = HashMap<u32, Vec<Advertisement>>;
+pub type AdvertisementMap = HashMap<ContainerId, Vec<Advertisement>>;
 
 /* ************************************ AdvertisementService ************************************ */
 
@@ -70,16 +89,158 @@ pub type AdvertisementMap = HashMap<u32, Vec<Advertisement>>;
 ///
 /// [`AdvertisementService`] is the service layer wrapper for [`AdvertisementRepository`]. It
 /// transforms DTO types into domain types.
-#[derive(Default)]
+///
+/// Rocket Advertisement can't look up an advertisement by ID, so every advertisement returned
+/// from a listing is cached by ID for [`AdvertisementService::get_advertisements_by_id`] for a few
+/// minutes, and IDs which a listing didn't return are remembered as absent for a short while.
+/// Listings are fetched through a [`Mirror`].
 pub struct AdvertisementService {
+    /// When each advertisement ID was found absent from a listing.
+    absent: Mutex<LruCache<AdvertisementId, Instant>>,
+    /// Advertisements by ID, with when each expires.
+    cache: Mutex<LruCache<AdvertisementId, (Instant, Advertisement)>>,
+    /// Local mirror of Rocket Advertisement.
+    mirror: Arc<Mirror>,
     /// Repository layer that the service calls.
     repository: AdvertisementRepository,
 }
 
+impl Default for AdvertisementService {
+    fn default() -> Self {
+        Self::new(AdvertisementRepository::default())
+    }
+}
+
 impl AdvertisementService {
     /// Create a new [`AdvertisementService`].
     pub fn new(repository: AdvertisementRepository) -> Self {
-        Self { repository }
+        Self {
+            absent: Mutex::new(LruCache::new(CACHE_CAPACITY)),
+            cache: Mutex::new(LruCache::new(CACHE_CAPACITY)),
+            mirror: Arc::new(Mirror::default()),
+            repository,
+        }
+    }
+
+    /// Set the local mirror which advertisements are fetched through.
+    pub fn mirror(mut self, mirror: Arc<Mirror>) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// Get advertisement by ID.
+    ///
+    /// See [`AdvertisementService::get_advertisements_by_id`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(advertisement)` if the advertisement exists.
+    /// - `Err(`[`ErrorKind::NotFound`]`)` if there is no advertisement with ID `advertisement_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::{
+    ///     service::advertisement::{Advertisement, AdvertisementService},
+    ///     types::{AdvertisementId, Result},
+    /// };
+    ///
+    /// async fn get_advertisement(service: &AdvertisementService) -> Result<Advertisement> {
+    ///     service.get_advertisement(AdvertisementId(1)).await
+    /// }
+    /// ```
+    pub async fn get_advertisement(
+        &self,
+        advertisement_id: AdvertisementId,
+    ) -> Result<Advertisement> {
+        trace!(
+            "AdvertisementService::get_advertisement {}",
+            advertisement_id
+        );
+
+        self.get_advertisements_by_id(&[advertisement_id])
+            .await?
+            .remove(&advertisement_id)
+            .ok_or_else(|| Error {
+                kind: ErrorKind::NotFound,
+                message: format!("Advertisement {} not found", advertisement_id),
+            })
+    }
+
+    /// Get advertisements by ID.
+    ///
+    /// Returns cached advertisements if they are all recently cached or recently absent, otherwise
+    /// lists all advertisements from Rocket Advertisement once. IDs which don't exist are missing
+    /// from the returned map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use rocket_container::{
+    ///     service::advertisement::{Advertisement, AdvertisementService},
+    ///     types::{AdvertisementId, Result},
+    /// };
+    ///
+    /// async fn get_advertisements(
+    ///     service: &AdvertisementService,
+    /// ) -> Result<HashMap<AdvertisementId, Advertisement>> {
+    ///     service
+    ///         .get_advertisements_by_id(&[AdvertisementId(1), AdvertisementId(2)])
+    ///         .await
+    /// }
+    /// ```
+    pub async fn get_advertisements_by_id(
+        &self,
+        advertisement_ids: &[AdvertisementId],
+    ) -> Result<HashMap<AdvertisementId, Advertisement>> {
+        trace!(
+            "AdvertisementService::get_advertisements_by_id {:?}",
+            advertisement_ids
+        );
+
+        let mut advertisements: HashMap<AdvertisementId, Advertisement> =
+            HashMap::with_capacity(advertisement_ids.len());
+        let mut missing: bool = false;
+
+        {
+            let mut absent = self.absent.lock().unwrap();
+            let mut cache = self.cache.lock().unwrap();
+
+            for advertisement_id in advertisement_ids {
+                match cache.get(advertisement_id) {
+                    Some((expires_at, advertisement)) if Instant::now() < *expires_at => {
+                        advertisements.insert(*advertisement_id, advertisement.clone());
+                    }
+                    _ if absent
+                        .get(advertisement_id)
+                        .is_some_and(|listed_at| listed_at.elapsed() < ABSENT_TTL) => {}
+                    _ => missing = true,
+                }
+            }
+        }
+
+        if missing {
+            for advertisement in self.list_advertisements().await?.into_values().flatten() {
+                if advertisement_ids.contains(&advertisement.id) {
+                    advertisements.insert(advertisement.id, advertisement);
+                }
+            }
+
+            let mut absent = self.absent.lock().unwrap();
+            let mut cache = self.cache.lock().unwrap();
+            let listed_at: Instant = Instant::now();
+
+            for advertisement_id in advertisement_ids {
+                if !advertisements.contains_key(advertisement_id) {
+                    cache.pop(advertisement_id);
+                    absent.put(*advertisement_id, listed_at);
+                }
+            }
+        }
+
+        Ok(advertisements)
     }
 
     /// List all advertisements from Rocket Advertisement.
@@ -95,19 +256,26 @@ impl AdvertisementService {
     pub async fn list_advertisements(&self) -> Result<AdvertisementMap> {
         trace!("AdvertisementService::list_advertisements");
 
-        let advertisements = self
-            .repository
-            .list_advertisements()
+        let advertisements: Vec<(ContainerId, Advertisement)> = self
+            .mirror
+            .fetch("advertisements", self.repository.list_advertisements())
             .await?
             .into_iter()
             .map(|advertisement| {
                 (
-                    advertisement.container_id().parse().unwrap(),
+                    advertisement.container_id(),
                     Advertisement::from(advertisement),
                 )
-            });
+            })
+            .collect();
+
+        self.cache(
+            advertisements
+                .iter()
+                .map(|(_, advertisement)| advertisement),
+        );
 
-        Ok(group(advertisements))
+        Ok(group(advertisements.into_iter()))
     }
 
     /// List advertisements for a container from Rocket Advertisement.
@@ -115,16 +283,19 @@ impl AdvertisementService {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::service::advertisement::{Advertisement, AdvertisementService};
+    /// use rocket_container::{
+    ///     service::advertisement::{Advertisement, AdvertisementService},
+    ///     types::ContainerId,
+    /// };
     ///
-    /// let container_id: u32 = 1;
+    /// let container_id: ContainerId = ContainerId(1);
     /// let service: AdvertisementService = AdvertisementService::default();
     /// let advertisements: Vec<Advertisement> =
     ///     service.list_advertisements_by_container(container_id).await?;
     /// ```
     pub async fn list_advertisements_by_container(
         &self,
-        container_id: u32,
+        container_id: ContainerId,
     ) -> Result<Vec<Advertisement>> {
         trace!(
             "AdvertisementService::list_advertisements_by_container {}",
@@ -132,25 +303,155 @@ impl AdvertisementService {
         );
 
         let advertisements: Vec<Advertisement> = self
-            .repository
-            .list_advertisements_by_container(container_id)
+            .mirror
+            .fetch(
+                &format!("advertisements.container-{}", container_id),
+                self.repository
+                    .list_advertisements_by_container(container_id),
+            )
             .await?
             .into_iter()
             .map(Advertisement::from)
             .collect();
 
+        self.cache(advertisements.iter());
+
         Ok(advertisements)
     }
+
+    /* ****************************** Private utility function ****************************** */
+
+    /// Add advertisements to the cache, and forget that they were absent.
+    fn cache<'a, I: Iterator<Item = &'a Advertisement>>(&self, advertisements: I) {
+        let mut absent = self.absent.lock().unwrap();
+        let mut cache = self.cache.lock().unwrap();
+        let expires_at: Instant = Instant::now() + CACHE_TTL;
+
+        for advertisement in advertisements {
+            absent.pop(&advertisement.id);
+            cache.put(advertisement.id, (expires_at, advertisement.clone()));
+        }
+    }
 }
 
 /* ******************************************* Tests ******************************************** */
 
 #[cfg(test)]
 mod test {
-    use crate::types::Result;
+    use std::{env, fs, path::PathBuf, sync::Arc, time::Instant};
+
+    use serde_json::json;
+
+    use crate::{
+        repository::advertisement::AdvertisementDto,
+        service::mirror::Mirror,
+        types::{AdvertisementId, ContainerId, ErrorKind, Result},
+    };
 
     use super::{Advertisement, AdvertisementMap, AdvertisementService};
 
+    #[tokio::test]
+    async fn get_advertisement_cached() {
+        // Given
+        let service = AdvertisementService::default();
+        let expected: Advertisement = Advertisement::new(
+            AdvertisementId(7),
+            "Advertisement".to_string(),
+            "https://ad.com".to_string(),
+        );
+        service.cache([expected.clone()].iter());
+
+        // When
+        let result: Result<Advertisement> = service.get_advertisement(AdvertisementId(7)).await;
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual, expected),
+            Err(err) => panic!("Failed to get advertisement with error: {}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_advertisement_absent() {
+        // Given
+        let path: PathBuf = env::temp_dir().join(format!(
+            "advertisement-absent-{}.sqlite3",
+            std::process::id()
+        ));
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+        mirror
+            .fetch("advertisements", async {
+                Ok(Vec::<AdvertisementDto>::new())
+            })
+            .await
+            .unwrap();
+        let service = AdvertisementService::default()
+            .mirror(Arc::new(Mirror::open(&path).unwrap().offline(true)));
+
+        // When
+        let listed: Result<Advertisement> = service.get_advertisement(AdvertisementId(8)).await;
+        mirror
+            .fetch("advertisements", async {
+                Ok(serde_json::from_value::<Vec<AdvertisementDto>>(json!([{
+                    "containerId": "0",
+                    "id": "8",
+                    "name": "Advertisement",
+                    "url": "https://advertisement.com"
+                }]))
+                .unwrap())
+            })
+            .await
+            .unwrap();
+        let remembered: Result<Advertisement> = service.get_advertisement(AdvertisementId(8)).await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(listed.is_err_and(|err| err.kind == ErrorKind::NotFound));
+        assert!(remembered.is_err_and(|err| err.kind == ErrorKind::NotFound));
+    }
+
+    #[tokio::test]
+    async fn get_advertisement_expired() {
+        // Given
+        let path: PathBuf = env::temp_dir().join(format!(
+            "advertisement-expired-{}.sqlite3",
+            std::process::id()
+        ));
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+        mirror
+            .fetch("advertisements", async {
+                Ok(Vec::<AdvertisementDto>::new())
+            })
+            .await
+            .unwrap();
+        let service = AdvertisementService::default()
+            .mirror(Arc::new(Mirror::open(&path).unwrap().offline(true)));
+        service.cache.lock().unwrap().put(
+            AdvertisementId(9),
+            (
+                Instant::now(),
+                Advertisement::new(
+                    AdvertisementId(9),
+                    "Advertisement".to_string(),
+                    "https://ad.com".to_string(),
+                ),
+            ),
+        );
+
+        // When
+        let result: Result<Advertisement> = service.get_advertisement(AdvertisementId(9)).await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(result.is_err_and(|err| err.kind == ErrorKind::NotFound));
+        assert!(service
+            .cache
+            .lock()
+            .unwrap()
+            .peek(&AdvertisementId(9))
+            .is_none());
+    }
+
     #[tokio::test]
     async fn test_list_advertisements() {
         // Given
@@ -170,7 +471,7 @@ mod test {
     async fn test_list_advertisements_by_container() {
         // Given
         let service = AdvertisementService::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
         let result: Result<Vec<Advertisement>> =
@@ -0,0 +1,512 @@
+//! Data-consistency audit of Rocket Container's dependencies.
+//!
+//! Rocket Advertisement, Rocket Image, and Rocket Video don't check each other, so they can
+//! disagree. [`audit`] cross-checks their catalogs and reports every [`AuditIssue`] it finds:
+//!
+//! - Asset references to advertisements or images which don't exist.
+//! - Advertisements and images for containers without videos, which are served as `upcoming`
+//!   containers until their videos are added.
+//! - Advertisements, images, or videos with the same ID.
+//! - Videos which are listed differently from how they are returned by ID, or not returned by ID
+//!   at all.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Display, Formatter},
+    hash::Hash,
+};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    service::{advertisement::AdvertisementMap, image::ImageMap, video::Video, video::VideoMap},
+    types::{AdvertisementId, AssetId, AssetType, ContainerId, ImageId, VideoId},
+};
+
+/* ***************************************** AuditIssue ***************************************** */
+
+/// Inconsistency between Rocket Container's dependencies.
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum AuditIssue {
+    /// A video references an advertisement or image which doesn't exist.
+    MissingAsset {
+        /// Video with the reference.
+        video_id: VideoId,
+        /// Referenced asset ID.
+        asset_id: AssetId,
+        /// Referenced asset type.
+        asset_type: AssetType,
+    },
+    /// Advertisements belong to a container without videos.
+    OrphanedAdvertisements {
+        /// Container without videos.
+        container_id: ContainerId,
+        /// Advertisements which belong to the container.
+        advertisement_ids: Vec<AdvertisementId>,
+    },
+    /// Images belong to a container without videos.
+    OrphanedImages {
+        /// Container without videos.
+        container_id: ContainerId,
+        /// Images which belong to the container.
+        image_ids: Vec<ImageId>,
+    },
+    /// More than one advertisement has the same ID.
+    DuplicateAdvertisement {
+        /// Duplicated ID.
+        advertisement_id: AdvertisementId,
+        /// Number of advertisements with the ID.
+        count: usize,
+    },
+    /// More than one image has the same ID.
+    DuplicateImage {
+        /// Duplicated ID.
+        image_id: ImageId,
+        /// Number of images with the ID.
+        count: usize,
+    },
+    /// More than one video has the same ID.
+    DuplicateVideo {
+        /// Duplicated ID.
+        video_id: VideoId,
+        /// Number of videos with the ID.
+        count: usize,
+    },
+    /// A listed video isn't returned by ID.
+    UnlistedVideo {
+        /// Listed video.
+        video_id: VideoId,
+    },
+    /// A video is listed differently from how it is returned by ID.
+    VideoMismatch {
+        /// Video which differs.
+        video_id: VideoId,
+        /// Fields which differ.
+        fields: Vec<String>,
+    },
+}
+
+impl Display for AuditIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditIssue::MissingAsset {
+                video_id,
+                asset_id,
+                asset_type,
+            } => write!(
+                f,
+                "Video {} references missing {} asset {}",
+                video_id, asset_type, asset_id
+            ),
+            AuditIssue::OrphanedAdvertisements {
+                container_id,
+                advertisement_ids,
+            } => write!(
+                f,
+                "Container {} has {} advertisements but no videos",
+                container_id,
+                advertisement_ids.len()
+            ),
+            AuditIssue::OrphanedImages {
+                container_id,
+                image_ids,
+            } => write!(
+                f,
+                "Container {} has {} images but no videos",
+                container_id,
+                image_ids.len()
+            ),
+            AuditIssue::DuplicateAdvertisement {
+                advertisement_id,
+                count,
+            } => write!(
+                f,
+                "Advertisement {} is listed {} times",
+                advertisement_id, count
+            ),
+            AuditIssue::DuplicateImage { image_id, count } => {
+                write!(f, "Image {} is listed {} times", image_id, count)
+            }
+            AuditIssue::DuplicateVideo { video_id, count } => {
+                write!(f, "Video {} is listed {} times", video_id, count)
+            }
+            AuditIssue::UnlistedVideo { video_id } => {
+                write!(f, "Video {} is listed but not returned by ID", video_id)
+            }
+            AuditIssue::VideoMismatch { video_id, fields } => write!(
+                f,
+                "Video {} is listed with different {}",
+                video_id,
+                fields.join(", ")
+            ),
+        }
+    }
+}
+
+/* **************************************** AuditReport ***************************************** */
+
+/// Result of auditing Rocket Container's dependencies.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use chrono::Utc;
+/// use rocket_container::service::audit::{audit, AuditReport};
+///
+/// let report: AuditReport = audit(
+///     &HashMap::new(),
+///     &HashMap::new(),
+///     &HashMap::new(),
+///     &HashMap::new(),
+///     Utc::now(),
+/// );
+///
+/// assert!(report.is_consistent());
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditReport {
+    /// When the audit ran.
+    audited_at: DateTime<Utc>,
+    /// Number of advertisements audited.
+    advertisements: usize,
+    /// Number of images audited.
+    images: usize,
+    /// Number of videos audited.
+    videos: usize,
+    /// Inconsistencies found.
+    issues: Vec<AuditIssue>,
+}
+
+impl AuditReport {
+    /// Get inconsistencies found.
+    pub fn issues(&self) -> &Vec<AuditIssue> {
+        &self.issues
+    }
+
+    /// Check if no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Cross-check the catalogs of Rocket Container's dependencies.
+///
+/// `details` has the videos returned by ID, for every listed video which was returned. A video
+/// listed more than once is only checked once.
+/// Issues are reported in a stable order: by kind, then by ID.
+pub fn audit(
+    advertisements: &AdvertisementMap,
+    images: &ImageMap,
+    videos: &VideoMap,
+    details: &HashMap<VideoId, Video>,
+    audited_at: DateTime<Utc>,
+) -> AuditReport {
+    let advertisement_ids: Vec<AdvertisementId> = advertisements
+        .values()
+        .flatten()
+        .map(|advertisement| advertisement.id())
+        .collect();
+    let image_ids: Vec<ImageId> = images.values().flatten().map(|image| image.id()).collect();
+    let listed: Vec<&Video> = sorted(videos).into_iter().flat_map(|(_, v)| v).collect();
+    let mut seen: HashSet<VideoId> = HashSet::new();
+    let unique: Vec<&Video> = listed
+        .iter()
+        .copied()
+        .filter(|video| seen.insert(video.id()))
+        .collect();
+
+    let mut issues: Vec<AuditIssue> = Vec::new();
+
+    let known_advertisements: HashSet<AdvertisementId> =
+        advertisement_ids.iter().copied().collect();
+    let known_images: HashSet<ImageId> = image_ids.iter().copied().collect();
+
+    for video in &unique {
+        for asset in video.assets() {
+            let missing: bool = match asset.asset_type() {
+                AssetType::Ad => !known_advertisements.contains(&asset.asset_id().into()),
+                AssetType::Image => !known_images.contains(&asset.asset_id().into()),
+                AssetType::Unknown(_) => false,
+            };
+
+            if missing {
+                issues.push(AuditIssue::MissingAsset {
+                    video_id: video.id(),
+                    asset_id: asset.asset_id(),
+                    asset_type: asset.asset_type(),
+                });
+            }
+        }
+    }
+
+    let has_videos = |container_id: &ContainerId| {
+        videos
+            .get(container_id)
+            .is_some_and(|videos| !videos.is_empty())
+    };
+
+    for (container_id, advertisements) in sorted(advertisements) {
+        if !advertisements.is_empty() && !has_videos(container_id) {
+            issues.push(AuditIssue::OrphanedAdvertisements {
+                container_id: *container_id,
+                advertisement_ids: advertisements.iter().map(|ad| ad.id()).collect(),
+            });
+        }
+    }
+
+    for (container_id, images) in sorted(images) {
+        if !images.is_empty() && !has_videos(container_id) {
+            issues.push(AuditIssue::OrphanedImages {
+                container_id: *container_id,
+                image_ids: images.iter().map(|image| image.id()).collect(),
+            });
+        }
+    }
+
+    for (advertisement_id, count) in duplicates(&advertisement_ids) {
+        issues.push(AuditIssue::DuplicateAdvertisement {
+            advertisement_id,
+            count,
+        });
+    }
+
+    for (image_id, count) in duplicates(&image_ids) {
+        issues.push(AuditIssue::DuplicateImage { image_id, count });
+    }
+
+    let video_ids: Vec<VideoId> = listed.iter().map(|video| video.id()).collect();
+
+    for (video_id, count) in duplicates(&video_ids) {
+        issues.push(AuditIssue::DuplicateVideo { video_id, count });
+    }
+
+    for video in &unique {
+        match details.get(&video.id()) {
+            Some(detail) => {
+                let fields: Vec<String> = differences(video, detail);
+
+                if !fields.is_empty() {
+                    issues.push(AuditIssue::VideoMismatch {
+                        video_id: video.id(),
+                        fields,
+                    });
+                }
+            }
+            None => issues.push(AuditIssue::UnlistedVideo {
+                video_id: video.id(),
+            }),
+        }
+    }
+
+    AuditReport {
+        audited_at,
+        advertisements: advertisement_ids.len(),
+        images: image_ids.len(),
+        videos: listed.len(),
+        issues,
+    }
+}
+
+/// Get the entries of a map sorted by key.
+fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> Vec<(&K, &V)> {
+    let mut entries: Vec<(&K, &V)> = map.iter().collect();
+
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    entries
+}
+
+/// Get IDs which appear more than once, with how many times they appear, sorted by ID.
+fn duplicates<T: Copy + Eq + Hash + Ord>(ids: &[T]) -> Vec<(T, usize)> {
+    let mut counts: HashMap<T, usize> = HashMap::new();
+
+    for id in ids {
+        *counts.entry(*id).or_default() += 1;
+    }
+
+    let mut duplicates: Vec<(T, usize)> =
+        counts.into_iter().filter(|(_, count)| *count > 1).collect();
+
+    duplicates.sort();
+
+    duplicates
+}
+
+/// Get the names of the fields which differ between a listed video and the video returned by ID.
+fn differences(listed: &Video, detail: &Video) -> Vec<String> {
+    [
+        ("assets", listed.assets() != detail.assets()),
+        ("description", listed.description() != detail.description()),
+        ("expiresAt", listed.expires_at() != detail.expires_at()),
+        (
+            "playbackUrl",
+            listed.playback_url() != detail.playback_url(),
+        ),
+        ("title", listed.title() != detail.title()),
+        ("type", listed.r#type() != detail.r#type()),
+    ]
+    .into_iter()
+    .filter(|(_, differs)| *differs)
+    .map(|(field, _)| field.to_string())
+    .collect()
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+
+    use crate::{
+        service::{
+            advertisement::{Advertisement, AdvertisementMap},
+            image::{Image, ImageMap},
+            video::{AssetReference, Video, VideoMap},
+        },
+        types::{AdvertisementId, AssetId, AssetType, ContainerId, ImageId, VideoId, VideoType},
+    };
+
+    use super::{audit, AuditIssue, AuditReport};
+
+    fn advertisement(id: u32) -> Advertisement {
+        Advertisement::new(
+            AdvertisementId(id),
+            format!("Advertisement {}", id),
+            format!("https://ad.com/{}", id),
+        )
+    }
+
+    fn image(id: u32) -> Image {
+        Image::new(
+            ImageId(id),
+            format!("Image {}", id),
+            format!("https://image.com/{}", id),
+        )
+    }
+
+    fn video(id: u32, title: &str) -> Video {
+        Video::builder(VideoId(id))
+            .asset(AssetReference::new(AssetId(1), AssetType::Ad))
+            .asset(AssetReference::new(AssetId(9), AssetType::Image))
+            .description(String::new())
+            .playback_url(format!("/path/to/test{}.m3u8", id))
+            .title(title.to_string())
+            .r#type(VideoType::Clip)
+            .build()
+    }
+
+    fn details(videos: &[Video]) -> HashMap<VideoId, Video> {
+        videos
+            .iter()
+            .map(|video| (video.id(), video.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn audit_consistent_catalog() {
+        // Given
+        let advertisements: AdvertisementMap =
+            HashMap::from([(ContainerId(0), vec![advertisement(1)])]);
+        let images: ImageMap = HashMap::from([(ContainerId(0), vec![image(9)])]);
+        let videos: VideoMap = HashMap::from([(ContainerId(0), vec![video(5, "Pilot")])]);
+
+        // When
+        let actual: AuditReport = audit(
+            &advertisements,
+            &images,
+            &videos,
+            &details(&videos[&ContainerId(0)]),
+            Utc::now(),
+        );
+
+        // Then
+        assert!(actual.is_consistent());
+    }
+
+    #[test]
+    fn audit_inconsistent_catalog() {
+        // Given
+        let advertisements: AdvertisementMap = HashMap::from([
+            (ContainerId(0), vec![advertisement(1), advertisement(1)]),
+            (ContainerId(3), vec![advertisement(2)]),
+        ]);
+        let images: ImageMap = HashMap::from([(ContainerId(3), vec![image(4)])]);
+        let videos: VideoMap = HashMap::from([
+            (ContainerId(0), vec![video(5, "Pilot"), video(6, "Finale")]),
+            (ContainerId(1), vec![video(6, "Finale")]),
+        ]);
+        let details: HashMap<VideoId, Video> = details(&[video(5, "Pilot (Extended)")]);
+
+        // When
+        let actual: AuditReport = audit(&advertisements, &images, &videos, &details, Utc::now());
+
+        // Then
+        assert_eq!(
+            actual.issues(),
+            &vec![
+                AuditIssue::MissingAsset {
+                    video_id: VideoId(5),
+                    asset_id: AssetId(9),
+                    asset_type: AssetType::Image,
+                },
+                AuditIssue::MissingAsset {
+                    video_id: VideoId(6),
+                    asset_id: AssetId(9),
+                    asset_type: AssetType::Image,
+                },
+                AuditIssue::OrphanedAdvertisements {
+                    container_id: ContainerId(3),
+                    advertisement_ids: vec![AdvertisementId(2)],
+                },
+                AuditIssue::OrphanedImages {
+                    container_id: ContainerId(3),
+                    image_ids: vec![ImageId(4)],
+                },
+                AuditIssue::DuplicateAdvertisement {
+                    advertisement_id: AdvertisementId(1),
+                    count: 2,
+                },
+                AuditIssue::DuplicateVideo {
+                    video_id: VideoId(6),
+                    count: 2,
+                },
+                AuditIssue::VideoMismatch {
+                    video_id: VideoId(5),
+                    fields: vec!["title".to_string()],
+                },
+                AuditIssue::UnlistedVideo {
+                    video_id: VideoId(6),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize_issue() {
+        // Given
+        let issue: AuditIssue = AuditIssue::DuplicateImage {
+            image_id: ImageId(4),
+            count: 2,
+        };
+
+        // When
+        let actual: String = serde_json::to_string(&issue).unwrap();
+
+        // Then
+        assert_eq!(actual, r#"{"kind":"duplicateImage","imageId":4,"count":2}"#);
+    }
+}
@@ -0,0 +1,221 @@
+//! Catalog snapshot export and import.
+//!
+//! A [`CatalogSnapshot`] is every container, as aggregated by [`ContainerService`][1], written to a
+//! versioned JSON file. Importing a catalog snapshot makes it Rocket Container's source of
+//! containers instead of its dependencies, e.g. for reproducible QA environments or to seed a cold
+//! start.
+//!
+//! Files whose name ends in `.gz` are gzip compressed. Gzip compressed files are detected by their
+//! contents when they are read, regardless of their name.
+//!
+//! [1]: crate::service::container::ContainerService
+
+use std::{
+    fs,
+    io::{Read, Write},
+    path::Path,
+};
+
+use chrono::{DateTime, Utc};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    service::container::Container,
+    types::{Error, ErrorKind, Result},
+};
+
+/// Version of the catalog snapshot format written by this release.
+pub const CATALOG_VERSION: u32 = 1;
+
+/// First bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/* ************************************** CatalogSnapshot *************************************** */
+
+/// Every container at a point in time.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::Utc;
+/// use rocket_container::service::catalog::CatalogSnapshot;
+///
+/// let snapshot: CatalogSnapshot = CatalogSnapshot::new(Vec::new(), Utc::now());
+/// let bytes: Vec<u8> = snapshot.to_bytes(true).unwrap();
+///
+/// assert_eq!(CatalogSnapshot::from_bytes(&bytes).unwrap(), snapshot);
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CatalogSnapshot {
+    /// Version of the catalog snapshot format.
+    version: u32,
+    /// When the containers were exported.
+    exported_at: DateTime<Utc>,
+    /// Every container.
+    containers: Vec<Container>,
+}
+
+impl CatalogSnapshot {
+    /// Create a new [`CatalogSnapshot`] of the current format version.
+    pub fn new(containers: Vec<Container>, exported_at: DateTime<Utc>) -> Self {
+        CatalogSnapshot {
+            version: CATALOG_VERSION,
+            exported_at,
+            containers,
+        }
+    }
+
+    /// Get every container.
+    pub fn containers(&self) -> &Vec<Container> {
+        &self.containers
+    }
+
+    /// Get when the containers were exported.
+    pub fn exported_at(&self) -> DateTime<Utc> {
+        self.exported_at
+    }
+
+    /// Get version of the catalog snapshot format.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Serialize the catalog snapshot as JSON, gzip compressed if `gzip` is set.
+    pub fn to_bytes(&self, gzip: bool) -> Result<Vec<u8>> {
+        let json: Vec<u8> = serde_json::to_vec(self).map_err(|err| error(err.to_string()))?;
+
+        match gzip {
+            true => {
+                let mut encoder: GzEncoder<Vec<u8>> =
+                    GzEncoder::new(Vec::new(), Compression::default());
+
+                encoder
+                    .write_all(&json)
+                    .and_then(|_| encoder.finish())
+                    .map_err(|err| error(err.to_string()))
+            }
+            false => Ok(json),
+        }
+    }
+
+    /// Deserialize a catalog snapshot from JSON, which may be gzip compressed.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(snapshot)` if the catalog snapshot is valid.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if it can't be parsed, or its format version isn't
+    ///   supported.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut json: Vec<u8> = Vec::new();
+        let json: &[u8] = match bytes.starts_with(&GZIP_MAGIC) {
+            true => {
+                GzDecoder::new(bytes)
+                    .read_to_end(&mut json)
+                    .map_err(|err| error(err.to_string()))?;
+
+                &json
+            }
+            false => bytes,
+        };
+        let snapshot: CatalogSnapshot =
+            serde_json::from_slice(json).map_err(|err| error(err.to_string()))?;
+
+        match snapshot.version {
+            CATALOG_VERSION => Ok(snapshot),
+            version => Err(error(format!(
+                "unsupported version {}, expected {}",
+                version, CATALOG_VERSION
+            ))),
+        }
+    }
+
+    /// Read a catalog snapshot from a file.
+    ///
+    /// See [`CatalogSnapshot::from_bytes`].
+    pub fn read(path: &Path) -> Result<Self> {
+        let bytes: Vec<u8> = fs::read(path).map_err(|err| error(err.to_string()))?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Write the catalog snapshot to a file, gzip compressed if its name ends in `.gz`.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let gzip: bool = path.extension().is_some_and(|extension| extension == "gz");
+
+        fs::write(path, self.to_bytes(gzip)?).map_err(|err| error(err.to_string()))
+    }
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Error for a catalog snapshot which can't be read or written.
+fn error(message: String) -> Error {
+    Error {
+        kind: ErrorKind::Permanent,
+        message: format!("Invalid catalog snapshot: {}", message),
+    }
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+
+    use crate::{
+        service::{container::Container, video::Video},
+        types::{parse_timestamp, ContainerId, VideoId, VideoType},
+    };
+
+    use super::CatalogSnapshot;
+
+    fn snapshot() -> CatalogSnapshot {
+        let video: Video = Video::builder(VideoId(1))
+            .description("Pilot".to_string())
+            .expires_at(parse_timestamp("2022-03-23"))
+            .playback_url("/path/to/test1.m3u8".to_string())
+            .title("My Family".to_string())
+            .r#type(VideoType::Episode)
+            .build();
+
+        CatalogSnapshot::new(
+            vec![Container::new(
+                Vec::new(),
+                ContainerId(0),
+                Vec::new(),
+                "container-0_videos".to_string(),
+                vec![video],
+            )],
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn round_trip_json() {
+        // Given
+        let expected: CatalogSnapshot = snapshot();
+
+        // When
+        let actual: CatalogSnapshot =
+            CatalogSnapshot::from_bytes(&expected.to_bytes(false).unwrap()).unwrap();
+
+        // Then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn reject_unsupported_version() {
+        // Given
+        let json: &str =
+            r#"{"version": 2, "exportedAt": "2022-03-23T00:00:00Z", "containers": []}"#;
+
+        // When
+        let actual = CatalogSnapshot::from_bytes(json.as_bytes());
+
+        // Then
+        assert!(actual.is_err_and(|err| err.message.contains("unsupported version 2")));
+    }
+}
@@ -1,17 +1,36 @@
 //! Container service.
 
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
-use log::trace;
+use chrono::{DateTime, Utc};
+use futures::{stream, try_join, StreamExt, TryStreamExt};
+use log::{info, trace};
+use rocket::FromFormField;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
     service::{
         advertisement::{Advertisement, AdvertisementMap, AdvertisementService},
+        audit::{audit, AuditReport},
+        catalog::CatalogSnapshot,
         image::{Image, ImageMap, ImageService},
-        video::{Video, VideoMap, VideoService},
+        metadata::{ContainerMetadata, MetadataStore},
+        mirror::MirrorUsage,
+        report::ExpiringVideo,
+        snapshot::SnapshotCache,
+        title::{ContainerTitler, DefaultTitler},
+        video::{Video, VideoAsset, VideoFilter, VideoMap, VideoService, VideoSorting},
+    },
+    types::{
+        array_to_string, option_to_string, AdvertisementId, AssetId, AssetType, ContainerId, Error,
+        ErrorKind, ImageId, Result, SortOrder, VideoId, VideoType,
     },
-    types::Result,
 };
 
 /* ***************************************** Container ****************************************** */
@@ -25,65 +44,92 @@ use crate::{
 /// # Examples
 ///
 /// ```rust
-/// use rocket_container::service::container::{Container, ContainerService};
+/// use rocket_container::service::container::{
+///     Container, ContainerFilter, ContainerService, ContainerSorting,
+/// };
 ///
 /// let service: ContainerService = ContainerService::default();
-/// let containers: Vec<Container> = service.list_containers().await?;
+/// let containers: Vec<Container> = service
+///     .list_containers(&ContainerFilter::default(), &ContainerSorting::default())
+///     .await?;
 /// ```
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Container {
     ads: Vec<Advertisement>,
-    id: u32,
+    id: ContainerId,
     images: Vec<Image>,
     title: String,
     videos: Vec<Video>,
+    /// Editorial description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// ID of the image to feature for the container.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hero_image_id: Option<ImageId>,
+    /// Whether editors featured the container.
+    #[serde(default)]
+    featured: bool,
+    /// Editorial position of the container, see [`ContainerSort::DisplayOrder`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    display_order: Option<i32>,
+    /// Whether the container has any content yet.
+    #[serde(default)]
+    status: ContainerStatus,
 }
 
 impl Container {
     /// Construct a new Container.
     pub fn new(
         ads: Vec<Advertisement>,
-        id: u32,
+        id: ContainerId,
         images: Vec<Image>,
         title: String,
         videos: Vec<Video>,
     ) -> Self {
-        Container {
+        let mut container: Container = Container {
             ads,
             id,
             images,
             title,
             videos,
-        }
+            description: None,
+            hero_image_id: None,
+            featured: false,
+            display_order: None,
+            status: ContainerStatus::default(),
+        };
+
+        container.update_status();
+
+        container
     }
 
-    /// Create a container from a list of advertisements, images, and videos.
+    /// Create a container from a list of advertisements, images, and videos, titled by `titler`.
+    ///
+    /// Advertisements, images, and videos are sorted by ID so that containers have a stable
+    /// ordering regardless of the order returned by Rocket Container's dependencies.
     pub fn from(
-        container_id: u32,
+        container_id: ContainerId,
         advertisements: &[Advertisement],
         images: &[Image],
         videos: &[Video],
+        titler: &dyn ContainerTitler,
     ) -> Self {
-        let title_ads: String = match advertisements.is_empty() {
-            false => "_ads".to_string(),
-            true => String::new(),
-        };
-        let title_images: String = match images.is_empty() {
-            false => "_images".to_string(),
-            true => String::new(),
-        };
-        let title: String = format!(
-            "container-{}{}${}_videos",
-            container_id, title_ads, title_images
-        );
+        let mut advertisements: Vec<Advertisement> = advertisements.to_vec();
+        let mut images: Vec<Image> = images.to_vec();
+        let mut videos: Vec<Video> = videos.to_vec();
 
-        Container::new(
-            advertisements.to_vec(),
-            container_id,
-            images.to_vec(),
-            title,
-            videos.to_vec(),
-        )
+        advertisements.sort_by_key(Advertisement::id);
+        images.sort_by_key(Image::id);
+        videos.sort_by_key(Video::id);
+
+        let mut container: Container =
+            Container::new(advertisements, container_id, images, String::new(), videos);
+
+        container.title = titler.title(&container);
+
+        container
     }
 
     /// Get advertisements.
@@ -91,8 +137,28 @@ impl Container {
         &self.ads
     }
 
+    /// Get editorial description.
+    pub fn description(&self) -> Option<&String> {
+        self.description.as_ref()
+    }
+
+    /// Get editorial display order.
+    pub fn display_order(&self) -> Option<i32> {
+        self.display_order
+    }
+
+    /// Get whether editors featured the container.
+    pub fn featured(&self) -> bool {
+        self.featured
+    }
+
+    /// Get ID of the image to feature for the container.
+    pub fn hero_image_id(&self) -> Option<ImageId> {
+        self.hero_image_id
+    }
+
     /// Get container ID.
-    pub fn id(&self) -> u32 {
+    pub fn id(&self) -> ContainerId {
         self.id
     }
 
@@ -101,6 +167,14 @@ impl Container {
         &self.images
     }
 
+    /// Get whether the container has any content yet.
+    ///
+    /// The status reflects the container's data in Rocket Container's dependencies, regardless of
+    /// which videos were filtered out or have expired since.
+    pub fn status(&self) -> ContainerStatus {
+        self.status
+    }
+
     /// Get title.
     pub fn title(&self) -> &String {
         &self.title
@@ -110,6 +184,55 @@ impl Container {
     pub fn videos(&self) -> &Vec<Video> {
         &self.videos
     }
+
+    /// Merge editorial metadata into the container.
+    ///
+    /// A metadata title replaces the generated title.
+    pub fn apply_metadata(&mut self, metadata: &ContainerMetadata) {
+        if let Some(title) = &metadata.title {
+            self.title = title.clone();
+        }
+
+        self.description = metadata.description.clone();
+        self.hero_image_id = metadata.hero_image_id;
+        self.featured = metadata.featured;
+        self.display_order = metadata.display_order;
+    }
+
+    /// Remove videos which do not match a filter.
+    pub fn filter_videos(&mut self, filter: &VideoFilter) {
+        self.videos.retain(|video| filter.matches(video));
+    }
+
+    /// Remove videos which have expired at `now`.
+    ///
+    /// Returns the number of videos removed.
+    pub fn remove_expired_videos(&mut self, now: DateTime<Utc>) -> usize {
+        let count: usize = self.videos.len();
+
+        self.videos.retain(|video| !video.is_expired(now));
+
+        count - self.videos.len()
+    }
+
+    /// Sort videos in place.
+    pub fn sort_videos(&mut self, sorting: &VideoSorting) {
+        sorting.sort(&mut self.videos);
+    }
+
+    /* ****************************** Private utility function ****************************** */
+
+    /// Set the status from the container's advertisements, images, and videos.
+    fn update_status(&mut self) {
+        self.status = match (
+            self.videos.is_empty(),
+            self.ads.is_empty() && self.images.is_empty(),
+        ) {
+            (false, _) => ContainerStatus::Active,
+            (true, false) => ContainerStatus::Upcoming,
+            (true, true) => ContainerStatus::Empty,
+        };
+    }
 }
 
 impl Display for Container {
@@ -118,22 +241,343 @@ impl Display for Container {
     }
 }
 
+/* ************************************** ContainerStatus *************************************** */
+
+/// Whether a container has any content yet.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::service::container::ContainerStatus;
+///
+/// let status: ContainerStatus = "upcoming".parse().unwrap();
+///
+/// assert_eq!(status, ContainerStatus::Upcoming);
+/// ```
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Eq,
+    JsonSchema,
+    Hash,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerStatus {
+    /// Container has no advertisements, images, or videos.
+    #[default]
+    Empty,
+    /// Container has advertisements or images, but no videos yet.
+    Upcoming,
+    /// Container has videos.
+    Active,
+}
+
+impl Display for ContainerStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContainerStatus::Empty => write!(f, "empty"),
+            ContainerStatus::Upcoming => write!(f, "upcoming"),
+            ContainerStatus::Active => write!(f, "active"),
+        }
+    }
+}
+
+impl FromStr for ContainerStatus {
+    type Err = Error;
+
+    /// Parse a [ContainerStatus] from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "empty" => Ok(ContainerStatus::Empty),
+            "upcoming" => Ok(ContainerStatus::Upcoming),
+            "active" => Ok(ContainerStatus::Active),
+            _ => Err(Error {
+                kind: ErrorKind::Permanent,
+                message: format!("Unknown container status {}", s),
+            }),
+        }
+    }
+}
+
+/* *************************************** ContainerBatch *************************************** */
+
+/// Containers requested by ID, and the requested IDs without a container.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{service::container::ContainerBatch, types::ContainerId};
+///
+/// let batch: ContainerBatch = ContainerBatch {
+///     containers: Vec::new(),
+///     missing: vec![ContainerId(9)],
+/// };
+/// let ids: ContainerBatch<ContainerId> = batch.map(|container| container.id());
+///
+/// assert_eq!(ids.missing, vec![ContainerId(9)]);
+/// ```
+#[derive(Clone, Debug, Eq, JsonSchema, PartialEq, Serialize)]
+pub struct ContainerBatch<T = Container> {
+    /// Requested containers, in the order they were requested.
+    pub containers: Vec<T>,
+    /// Requested container IDs without a container, or whose container is hidden, in the order
+    /// they were requested.
+    pub missing: Vec<ContainerId>,
+}
+
+impl<T> ContainerBatch<T> {
+    /// Convert each container of the batch, keeping its missing IDs.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> ContainerBatch<U> {
+        ContainerBatch {
+            containers: self.containers.into_iter().map(f).collect(),
+            missing: self.missing,
+        }
+    }
+}
+
+/* ************************************** ContainerFilter *************************************** */
+
+/// Criteria which containers must match to be listed.
+///
+/// Criteria which are not set match every container. When [`ContainerFilter::videos`] is not empty
+/// it is applied to the videos inside of each container, and containers without any matching
+/// videos are excluded. When [`ContainerFilter::statuses`] is not empty only containers with one of
+/// those statuses are included, e.g. only [`ContainerStatus::Active`] to exclude incomplete
+/// containers.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     service::{container::ContainerFilter, video::VideoFilter},
+///     types::VideoType,
+/// };
+///
+/// let filter: ContainerFilter = ContainerFilter {
+///     has_ads: Some(true),
+///     has_images: None,
+///     statuses: Vec::new(),
+///     videos: VideoFilter {
+///         types: vec![VideoType::Movie],
+///         ..VideoFilter::default()
+///     },
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ContainerFilter {
+    /// Container must (or must not) have advertisements.
+    pub has_ads: Option<bool>,
+    /// Container must (or must not) have images.
+    pub has_images: Option<bool>,
+    /// Container must have one of these statuses, computed from its videos before they are
+    /// filtered.
+    pub statuses: Vec<ContainerStatus>,
+    /// Criteria for videos inside of the container.
+    pub videos: VideoFilter,
+}
+
+impl ContainerFilter {
+    /// Apply the filter to a container.
+    ///
+    /// Returns the container with non-matching videos removed, or [None] if the container does not
+    /// match. Containers are only excluded for having no videos left if the video filter has
+    /// criteria other than hiding expired videos.
+    pub fn apply(&self, mut container: Container) -> Option<Container> {
+        let has_ads: bool = !container.ads.is_empty();
+        let has_images: bool = !container.images.is_empty();
+
+        if self.has_ads.is_some_and(|expected| expected != has_ads)
+            || self
+                .has_images
+                .is_some_and(|expected| expected != has_images)
+        {
+            return None;
+        }
+
+        container.filter_videos(&self.videos);
+
+        if !self.videos.is_empty() && container.videos.is_empty() {
+            return None;
+        }
+
+        if !self.statuses.is_empty() && !self.statuses.contains(&container.status) {
+            return None;
+        }
+
+        Some(container)
+    }
+}
+
+impl Display for ContainerFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ContainerFilter {{ has_ads: {}, has_images: {}, statuses: {}, videos: {} }}",
+            option_to_string(&self.has_ads),
+            option_to_string(&self.has_images),
+            array_to_string(&self.statuses),
+            self.videos
+        )
+    }
+}
+
+/* *************************************** ContainerSort **************************************** */
+
+/// Field which containers are sorted by.
+///
+/// Every sort falls back to sorting by ID so that containers with equal values have a stable
+/// order.
+#[derive(Clone, Copy, Debug, Default, Eq, FromFormField, PartialEq)]
+pub enum ContainerSort {
+    /// Sort by [Container::id].
+    #[default]
+    #[field(value = "id")]
+    Id,
+    /// Sort by [Container::title].
+    #[field(value = "title")]
+    Title,
+    /// Sort by the number of videos in a container.
+    #[field(value = "videoCount")]
+    VideoCount,
+    /// Sort by [Container::display_order], with containers without a display order last.
+    #[field(value = "displayOrder")]
+    DisplayOrder,
+}
+
+impl Display for ContainerSort {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ContainerSort::Id => write!(f, "id"),
+            ContainerSort::Title => write!(f, "title"),
+            ContainerSort::VideoCount => write!(f, "videoCount"),
+            ContainerSort::DisplayOrder => write!(f, "displayOrder"),
+        }
+    }
+}
+
+/* ************************************** ContainerSorting ************************************** */
+
+/// How to sort a list of containers and the videos inside of them.
+///
+/// The default sorting is by ID in ascending order, for both containers and videos.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     service::{
+///         container::{ContainerSort, ContainerSorting},
+///         video::{VideoSort, VideoSorting},
+///     },
+///     types::SortOrder,
+/// };
+///
+/// let sorting: ContainerSorting = ContainerSorting {
+///     sort: ContainerSort::VideoCount,
+///     order: SortOrder::Desc,
+///     videos: VideoSorting {
+///         sort: VideoSort::Title,
+///         order: SortOrder::Asc,
+///     },
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ContainerSorting {
+    /// Field to sort containers by.
+    pub sort: ContainerSort,
+    /// Container sort order.
+    pub order: SortOrder,
+    /// How to sort videos inside of each container.
+    pub videos: VideoSorting,
+}
+
+impl ContainerSorting {
+    /// Sort a list of containers, and the videos inside of them, in place.
+    pub fn sort(&self, containers: &mut [Container]) {
+        for container in containers.iter_mut() {
+            container.sort_videos(&self.videos);
+        }
+
+        containers.sort_by(|a, b| self.order.apply(self.compare(a, b)));
+    }
+
+    /// Compare two containers in ascending order.
+    fn compare(&self, a: &Container, b: &Container) -> Ordering {
+        let ordering: Ordering = match self.sort {
+            ContainerSort::Id => Ordering::Equal,
+            ContainerSort::Title => a.title.cmp(&b.title),
+            ContainerSort::VideoCount => a.videos.len().cmp(&b.videos.len()),
+            ContainerSort::DisplayOrder => (a.display_order.is_none(), a.display_order)
+                .cmp(&(b.display_order.is_none(), b.display_order)),
+        };
+
+        ordering.then(a.id.cmp(&b.id))
+    }
+}
+
+impl Display for ContainerSorting {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ContainerSorting {{ sort: {}, order: {}, videos: {} }}",
+            self.sort, self.order, self.videos
+        )
+    }
+}
+
 /* ************************************** ContainerService ************************************** */
 
+/// Default number of videos an audit fetches at once.
+const DEFAULT_AUDIT_CONCURRENCY: usize = 16;
+
 /// Container service.
 ///
 /// Container service aggregates data from [`AdvertisementService`], [`ImageService`], and
 /// [`VideoService`] into containers by container ID.
-#[derive(Default)]
+///
+/// Container listings are served from a [`SnapshotCache`] of every container, except when
+/// expired videos are requested, since the snapshot doesn't hold them. Containers are titled by a
+/// [`ContainerTitler`], [`DefaultTitler`] unless another is set, and editorial metadata from a
+/// [`MetadataStore`] is merged into containers as they are served.
+///
+/// Once a [`CatalogSnapshot`] is imported, containers are served from it instead of from
+/// Rocket Container's dependencies.
 pub struct ContainerService {
     /// Advertisement service.
     advertisement_service: AdvertisementService,
+    /// Number of videos an audit fetches at once.
+    audit_concurrency: usize,
     /// Image service.
     image_service: ImageService,
+    /// Containers from an imported catalog snapshot, if any.
+    imported: RwLock<Option<Vec<Container>>>,
+    /// Editorial metadata by container ID.
+    metadata: Arc<MetadataStore>,
+    /// Snapshot of every container.
+    snapshot: Arc<SnapshotCache>,
+    /// Container title strategy.
+    titler: Box<dyn ContainerTitler>,
     /// Video service.
     video_service: VideoService,
 }
 
+impl Default for ContainerService {
+    fn default() -> Self {
+        Self::new(
+            AdvertisementService::default(),
+            ImageService::default(),
+            VideoService::default(),
+        )
+    }
+}
+
 impl ContainerService {
     /// Create a new container service.
     pub fn new(
@@ -143,22 +587,524 @@ impl ContainerService {
     ) -> Self {
         ContainerService {
             advertisement_service,
+            audit_concurrency: DEFAULT_AUDIT_CONCURRENCY,
             image_service,
+            imported: RwLock::new(None),
+            metadata: Arc::new(MetadataStore::default()),
+            snapshot: Arc::new(SnapshotCache::default()),
+            titler: Box::new(DefaultTitler),
             video_service,
         }
     }
 
-    /// List all advertisements for a container.
-    pub async fn get_advertisements(&self, container_id: u32) -> Result<Vec<Advertisement>> {
+    /// Set the number of videos an audit fetches at once.
+    pub fn audit_concurrency(mut self, concurrency: usize) -> Self {
+        self.audit_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the container title strategy.
+    pub fn titler(mut self, titler: Box<dyn ContainerTitler>) -> Self {
+        self.titler = titler;
+        self
+    }
+
+    /// Set the editorial metadata store.
+    pub fn metadata_store(mut self, metadata: MetadataStore) -> Self {
+        self.metadata = Arc::new(metadata);
+        self
+    }
+
+    /// Set the maximum age of the container snapshot.
+    pub fn snapshot_max_age(mut self, max_age: Duration) -> Self {
+        self.snapshot = Arc::new(SnapshotCache::new(max_age));
+        self
+    }
+
+    /// Get the container snapshot cache, e.g. to sweep expired videos from it.
+    pub fn snapshot(&self) -> Arc<SnapshotCache> {
+        self.snapshot.clone()
+    }
+
+    /// Get advertisement by ID.
+    ///
+    /// See [`AdvertisementService::get_advertisement`].
+    pub async fn get_advertisement(
+        &self,
+        advertisement_id: AdvertisementId,
+    ) -> Result<Advertisement> {
+        trace!("get_advertisement: {}", advertisement_id);
+
         self.advertisement_service
-            .list_advertisements_by_container(container_id)
+            .get_advertisement(advertisement_id)
             .await
     }
 
-    /// Get container by ID.
-    pub async fn get_container(&self, container_id: u32) -> Result<Container> {
-        trace!("get_container: {}", container_id);
+    /// List all advertisements for a container, sorted by ID.
+    pub async fn get_advertisements(
+        &self,
+        container_id: ContainerId,
+    ) -> Result<Vec<Advertisement>> {
+        let mut advertisements: Vec<Advertisement> = self
+            .advertisement_service
+            .list_advertisements_by_container(container_id)
+            .await?;
+
+        advertisements.sort_by_key(Advertisement::id);
+
+        Ok(advertisements)
+    }
+
+    /// Get container by ID, with videos which match `filter` sorted by `sorting`.
+    ///
+    /// Containers which editors hid aren't found, as in [`ContainerService::get_containers`].
+    pub async fn get_container(
+        &self,
+        container_id: ContainerId,
+        filter: &VideoFilter,
+        sorting: &VideoSorting,
+    ) -> Result<Container> {
+        trace!("get_container: ({}, {}, {})", container_id, filter, sorting);
+
+        let mut container: Container = match self.imported_containers() {
+            Some(containers) => containers
+                .into_iter()
+                .find(|container| container.id == container_id)
+                .ok_or_else(|| Error {
+                    kind: ErrorKind::NotFound,
+                    message: format!("Container {} is not in the imported catalog", container_id),
+                })?,
+            None => self.fetch_container(container_id).await?,
+        };
+
+        if let Some(metadata) = self.metadata.get(container_id) {
+            if metadata.hidden {
+                return Err(Error {
+                    kind: ErrorKind::NotFound,
+                    message: format!("Container {} is hidden", container_id),
+                });
+            }
+
+            container.apply_metadata(&metadata);
+        }
+
+        container.filter_videos(filter);
+        container.sort_videos(sorting);
+
+        Ok(container)
+    }
+
+    /// Get containers by ID, with videos which match `filter` sorted by `sorting`.
+    ///
+    /// Containers are resolved in a single pass over the containers
+    /// [`ContainerService::list_containers`] serves, instead of fetching each container from
+    /// Rocket Container's dependencies. Duplicate IDs are ignored, and containers which editors hid
+    /// are reported as missing.
+    pub async fn get_containers(
+        &self,
+        container_ids: &[ContainerId],
+        filter: &VideoFilter,
+        sorting: &VideoSorting,
+    ) -> Result<ContainerBatch> {
+        trace!(
+            "get_containers: ({}, {}, {})",
+            array_to_string(container_ids),
+            filter,
+            sorting
+        );
+
+        let containers: Vec<Container> = match filter.include_expired {
+            true => match self.imported_containers() {
+                Some(containers) => containers,
+                None => self.fetch_containers(&[]).await?,
+            },
+            false => self.snapshot_containers().await?,
+        };
+        let mut containers: HashMap<ContainerId, Container> = containers
+            .into_iter()
+            .map(|container| (container.id, container))
+            .collect();
+        let mut requested: BTreeSet<ContainerId> = BTreeSet::new();
+        let mut batch: ContainerBatch = ContainerBatch {
+            containers: Vec::with_capacity(container_ids.len()),
+            missing: Vec::new(),
+        };
+
+        for &container_id in container_ids {
+            if !requested.insert(container_id) {
+                continue;
+            }
+
+            let metadata: Option<ContainerMetadata> = self.metadata.get(container_id);
+
+            match containers.remove(&container_id) {
+                Some(_) if metadata.as_ref().is_some_and(|metadata| metadata.hidden) => {
+                    batch.missing.push(container_id)
+                }
+                Some(mut container) => {
+                    if let Some(metadata) = metadata {
+                        container.apply_metadata(&metadata);
+                    }
+
+                    container.filter_videos(filter);
+                    container.sort_videos(sorting);
+                    batch.containers.push(container);
+                }
+                None => batch.missing.push(container_id),
+            }
+        }
+
+        Ok(batch)
+    }
+
+    /// Export every container, including expired videos, as a catalog snapshot.
+    pub async fn export_catalog(&self) -> Result<CatalogSnapshot> {
+        trace!("export_catalog");
+
+        let filter: ContainerFilter = ContainerFilter {
+            videos: VideoFilter {
+                include_expired: true,
+                ..VideoFilter::default()
+            },
+            ..ContainerFilter::default()
+        };
+        let containers: Vec<Container> = self
+            .list_containers(&filter, &ContainerSorting::default())
+            .await?;
+
+        Ok(CatalogSnapshot::new(containers, Utc::now()))
+    }
+
+    /// Serve containers from a catalog snapshot instead of Rocket Container's dependencies.
+    pub fn import_catalog(&self, catalog: CatalogSnapshot) {
+        info!(
+            "Importing {} containers exported at {}",
+            catalog.containers().len(),
+            catalog.exported_at()
+        );
+
+        let mut containers: Vec<Container> = catalog.containers().clone();
+
+        // Catalog snapshots exported before containers had a status don't have one.
+        for container in containers.iter_mut() {
+            container.update_status();
+        }
+
+        *self.imported.write().unwrap() = Some(containers);
+    }
+
+    /// Audit the catalogs of Rocket Container's dependencies for inconsistencies.
+    ///
+    /// Always reads from the dependencies, bypassing the container snapshot and any imported
+    /// catalog. Every listed video is also fetched by ID, to compare it with its listing, at most
+    /// [`ContainerService::audit_concurrency`] at once.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(report)` with every inconsistency found.
+    /// - `Err(error)` if a dependency fails. A listed video which isn't found by ID is reported
+    ///   as an inconsistency rather than an error.
+    pub async fn audit(&self) -> Result<AuditReport> {
+        trace!("audit");
+
+        let (advertisements, images, videos): (AdvertisementMap, ImageMap, VideoMap) = try_join!(
+            self.advertisement_service.list_advertisements(),
+            self.image_service.list_images(),
+            self.video_service.list_videos(),
+        )?;
+        let mut video_ids: Vec<VideoId> = videos.values().flatten().map(Video::id).collect();
+
+        video_ids.sort();
+        video_ids.dedup();
+
+        let details: HashMap<VideoId, Video> = stream::iter(video_ids)
+            .map(|video_id| async move {
+                match self.video_service.get_video(video_id).await {
+                    Ok(video) => Ok(Some((video_id, video))),
+                    Err(error) if error.kind == ErrorKind::NotFound => Ok(None),
+                    Err(error) => Err(error),
+                }
+            })
+            .buffer_unordered(self.audit_concurrency)
+            .try_collect::<Vec<Option<(VideoId, Video)>>>()
+            .await?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok(audit(
+            &advertisements,
+            &images,
+            &videos,
+            &details,
+            Utc::now(),
+        ))
+    }
+
+    /// Get editorial metadata for a container.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(metadata)` if the container has metadata.
+    /// - `Err(`[`ErrorKind::NotFound`]`)` if it doesn't.
+    pub fn get_container_metadata(&self, container_id: ContainerId) -> Result<ContainerMetadata> {
+        trace!("get_container_metadata: {}", container_id);
+
+        self.metadata.get(container_id).ok_or_else(|| Error {
+            kind: ErrorKind::NotFound,
+            message: format!("Container {} has no metadata", container_id),
+        })
+    }
+
+    /// Set editorial metadata for a container, replacing any metadata it already has.
+    ///
+    /// See [`MetadataStore::put`].
+    pub async fn set_container_metadata(
+        &self,
+        container_id: ContainerId,
+        metadata: ContainerMetadata,
+    ) -> Result<()> {
+        trace!("set_container_metadata: ({}, {})", container_id, metadata);
+
+        self.metadata.put(container_id, metadata).await
+    }
+
+    /// Remove editorial metadata for a container.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the metadata was removed.
+    /// - `Err(`[`ErrorKind::NotFound`]`)` if the container has no metadata.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if the metadata can't be persisted.
+    pub async fn delete_container_metadata(&self, container_id: ContainerId) -> Result<()> {
+        trace!("delete_container_metadata: {}", container_id);
+
+        match self.metadata.delete(container_id).await? {
+            true => Ok(()),
+            false => Err(Error {
+                kind: ErrorKind::NotFound,
+                message: format!("Container {} has no metadata", container_id),
+            }),
+        }
+    }
+
+    /// Get image by ID.
+    ///
+    /// See [`ImageService::get_image`].
+    pub async fn get_image(&self, image_id: ImageId) -> Result<Image> {
+        trace!("get_image: {}", image_id);
+
+        self.image_service.get_image(image_id).await
+    }
+
+    /// List all images for a container, sorted by ID.
+    pub async fn get_images(&self, container_id: ContainerId) -> Result<Vec<Image>> {
+        let mut images: Vec<Image> = self
+            .image_service
+            .list_images_by_container(container_id)
+            .await?;
 
+        images.sort_by_key(Image::id);
+
+        Ok(images)
+    }
+
+    /// Replace asset references with the advertisements and images they reference.
+    ///
+    /// See [`VideoAsset::resolve`].
+    pub async fn expand_assets(&self, assets: &mut [VideoAsset]) -> Result<()> {
+        self.resolve_assets(assets.iter_mut().collect()).await
+    }
+
+    /// Replace the asset references of each container's videos with the advertisements and
+    /// images they reference.
+    ///
+    /// See [`VideoAsset::resolve`].
+    pub async fn expand_container_assets(&self, containers: &mut [Container]) -> Result<()> {
+        self.resolve_assets(
+            containers
+                .iter_mut()
+                .flat_map(|container| container.videos.iter_mut())
+                .flat_map(|video| video.assets_mut().iter_mut())
+                .collect(),
+        )
+        .await
+    }
+
+    /// Replace the asset references of each video with the advertisements and images they
+    /// reference.
+    ///
+    /// See [`VideoAsset::resolve`].
+    pub async fn expand_video_assets(&self, videos: &mut [Video]) -> Result<()> {
+        self.resolve_assets(
+            videos
+                .iter_mut()
+                .flat_map(|video| video.assets_mut().iter_mut())
+                .collect(),
+        )
+        .await
+    }
+
+    /// Get video by ID.
+    ///
+    /// Expired videos are reported as [`ErrorKind::NotFound`] unless `include_expired` is set. See
+    /// [`VideoService::get_video`].
+    pub async fn get_video(&self, video_id: VideoId, include_expired: bool) -> Result<Video> {
+        trace!("get_video: ({}, {})", video_id, include_expired);
+
+        let video: Video = self.video_service.get_video(video_id).await?;
+
+        match include_expired || !video.is_expired(Utc::now()) {
+            true => Ok(video),
+            false => Err(Error {
+                kind: ErrorKind::NotFound,
+                message: format!("Video {} has expired", video_id),
+            }),
+        }
+    }
+
+    /// List assets for a video, optionally only those of `asset_type`.
+    ///
+    /// The video is fetched rather than only its assets, so that an unknown `video_id` is reported
+    /// as [`ErrorKind::NotFound`](crate::types::ErrorKind::NotFound) instead of an empty list.
+    pub async fn get_video_assets(
+        &self,
+        video_id: VideoId,
+        asset_type: Option<AssetType>,
+    ) -> Result<Vec<VideoAsset>> {
+        trace!(
+            "get_video_assets: ({}, {})",
+            video_id,
+            option_to_string(&asset_type)
+        );
+
+        let assets: Vec<VideoAsset> = self
+            .video_service
+            .get_video(video_id)
+            .await?
+            .assets()
+            .iter()
+            .filter(|asset| {
+                asset_type
+                    .as_ref()
+                    .is_none_or(|asset_type| asset.asset_type() == *asset_type)
+            })
+            .cloned()
+            .collect();
+
+        Ok(assets)
+    }
+
+    /// List all videos for a container which match `filter`, sorted by `sorting`.
+    ///
+    /// If `filter` has video types then only videos of those types are requested from
+    /// [`VideoService`].
+    pub async fn get_videos(
+        &self,
+        container_id: ContainerId,
+        filter: &VideoFilter,
+        sorting: &VideoSorting,
+    ) -> Result<Vec<Video>> {
+        trace!("get_videos: ({}, {}, {})", container_id, filter, sorting);
+
+        let mut videos: Vec<Video> = match filter.types.is_empty() {
+            true => {
+                self.video_service
+                    .list_videos_by_container(container_id)
+                    .await?
+            }
+            false => {
+                self.video_service
+                    .list_videos_by_container_and_types(container_id, &filter.types)
+                    .await?
+            }
+        };
+
+        videos.retain(|video| filter.matches(video));
+        sorting.sort(&mut videos);
+
+        Ok(videos)
+    }
+
+    /// Get all containers which match `filter`, sorted by `sorting`.
+    ///
+    /// Containers come from the snapshot, unless `filter` includes expired videos. Then they are
+    /// fetched, and if `filter` has video types only videos of those types are requested from
+    /// [`VideoService`]. Containers which editors hid are left out.
+    pub async fn list_containers(
+        &self,
+        filter: &ContainerFilter,
+        sorting: &ContainerSorting,
+    ) -> Result<Vec<Container>> {
+        trace!("list_containers ({}, {})", filter, sorting);
+
+        let containers: Vec<Container> = match filter.videos.include_expired {
+            true => match self.imported_containers() {
+                Some(containers) => containers,
+                None => self.fetch_containers(&filter.videos.types).await?,
+            },
+            false => self.snapshot_containers().await?,
+        };
+
+        let mut containers: Vec<Container> = containers
+            .into_iter()
+            .filter_map(|mut container| {
+                if let Some(metadata) = self.metadata.get(container.id) {
+                    if metadata.hidden {
+                        return None;
+                    }
+
+                    container.apply_metadata(&metadata);
+                }
+
+                filter.apply(container)
+            })
+            .collect();
+
+        sorting.sort(&mut containers);
+
+        Ok(containers)
+    }
+
+    /// List videos which expire after `now` and no later than `within` from `now`.
+    ///
+    /// Videos are sorted by when they expire, then by container ID and video ID.
+    pub async fn list_expiring_videos(
+        &self,
+        now: DateTime<Utc>,
+        within: Duration,
+    ) -> Result<Vec<ExpiringVideo>> {
+        trace!("list_expiring_videos ({}, {:?})", now, within);
+
+        let until: DateTime<Utc> = match chrono::Duration::from_std(within) {
+            Ok(within) => now
+                .checked_add_signed(within)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+            Err(_) => DateTime::<Utc>::MAX_UTC,
+        };
+
+        let mut videos: Vec<ExpiringVideo> = self
+            .snapshot_containers()
+            .await?
+            .iter()
+            .flat_map(|container| {
+                container
+                    .videos
+                    .iter()
+                    .filter_map(|video| ExpiringVideo::from(container.id, video))
+            })
+            .filter(|video| now < video.expires_at() && video.expires_at() <= until)
+            .collect();
+
+        videos.sort_by_key(|video| (video.expires_at(), video.container_id(), video.video_id()));
+
+        Ok(videos)
+    }
+
+    /* ****************************** Private utility function ****************************** */
+
+    /// Fetch a container by ID from Rocket Container's dependencies.
+    async fn fetch_container(&self, container_id: ContainerId) -> Result<Container> {
         let advertisements: Vec<Advertisement> = self
             .advertisement_service
             .list_advertisements_by_container(container_id)
@@ -177,43 +1123,109 @@ impl ContainerService {
             &advertisements,
             &images,
             &videos,
+            &*self.titler,
         ))
     }
 
-    /// List all images for a container.
-    pub async fn get_images(&self, container_id: u32) -> Result<Vec<Image>> {
-        self.image_service
-            .list_images_by_container(container_id)
-            .await
+    /// Fetch every container from Rocket Container's dependencies.
+    ///
+    /// Containers are returned for every container ID which has advertisements, images, or
+    /// videos. If `video_types` isn't empty then only videos of those types are requested from
+    /// [`VideoService`], and only containers with those videos are returned.
+    async fn fetch_containers(&self, video_types: &[VideoType]) -> Result<Vec<Container>> {
+        let advertisements: AdvertisementMap =
+            self.advertisement_service.list_advertisements().await?;
+        let images: ImageMap = self.image_service.list_images().await?;
+        let videos: VideoMap = match video_types.is_empty() {
+            true => self.video_service.list_videos().await?,
+            false => self.video_service.list_videos_by_types(video_types).await?,
+        };
+
+        let mut container_ids: BTreeSet<ContainerId> = videos.keys().copied().collect();
+
+        if video_types.is_empty() {
+            container_ids.extend(advertisements.keys());
+            container_ids.extend(images.keys());
+        }
+
+        Ok(container_ids
+            .into_iter()
+            .map(|container_id| {
+                let videos: &[Video] = videos.get(&container_id).map_or(&[], Vec::as_slice);
+
+                self.build_container(container_id, &advertisements, &images, videos)
+            })
+            .collect())
     }
 
-    /// List all videos for a container.
-    pub async fn get_videos(&self, container_id: u32) -> Result<Vec<Video>> {
-        self.video_service
-            .list_videos_by_container(container_id)
-            .await
+    /// Get every container from the imported catalog snapshot, or [None] if none was imported.
+    fn imported_containers(&self) -> Option<Vec<Container>> {
+        self.imported.read().unwrap().clone()
     }
 
-    /// Get all containers.
-    pub async fn list_containers(&self) -> Result<Vec<Container>> {
-        trace!("list_containers");
+    /// Get every container from the snapshot, fetching a new snapshot if it is missing or stale.
+    ///
+    /// Containers from an imported catalog snapshot are returned as is, with expired videos.
+    async fn snapshot_containers(&self) -> Result<Vec<Container>> {
+        if let Some(containers) = self.imported_containers() {
+            return Ok(containers);
+        }
 
-        let advertisements: AdvertisementMap =
-            self.advertisement_service.list_advertisements().await?;
-        let images: ImageMap = self.image_service.list_images().await?;
-        let videos: VideoMap = self.video_service.list_videos().await?;
+        if let Some(containers) = self.snapshot.get(Utc::now()) {
+            return Ok(containers);
+        }
 
-        let containers: Vec<Container> = videos
-            .iter()
-            .map(|(container_id, videos)| {
-                self.build_container(*container_id, &advertisements, &images, videos)
-            })
-            .collect();
+        let usage: MirrorUsage = MirrorUsage::default();
+        let containers: Vec<Container> = usage.scope(self.fetch_containers(&[])).await?;
+
+        if let Some(fetched_at) = usage.oldest() {
+            MirrorUsage::record(fetched_at);
+        }
+
+        self.snapshot
+            .set_mirrored(containers.clone(), Utc::now(), usage.oldest());
 
         Ok(containers)
     }
 
-    /* ****************************** Private utility function ****************************** */
+    /// Resolve asset references.
+    ///
+    /// Looks up every referenced advertisement and image at once, so that each of
+    /// [`AdvertisementService`] and [`ImageService`] is called at most once.
+    async fn resolve_assets(&self, assets: Vec<&mut VideoAsset>) -> Result<()> {
+        let ids = |asset_type: AssetType| -> Vec<AssetId> {
+            assets
+                .iter()
+                .filter(|asset| asset.asset_type() == asset_type)
+                .map(|asset| asset.asset_id())
+                .collect()
+        };
+        let advertisement_ids: Vec<AdvertisementId> = ids(AssetType::Ad)
+            .into_iter()
+            .map(AdvertisementId::from)
+            .collect();
+        let image_ids: Vec<ImageId> = ids(AssetType::Image)
+            .into_iter()
+            .map(ImageId::from)
+            .collect();
+
+        trace!("resolve_assets: ({:?}, {:?})", advertisement_ids, image_ids);
+
+        let (advertisements, images): (
+            HashMap<AdvertisementId, Advertisement>,
+            HashMap<ImageId, Image>,
+        ) = try_join!(
+            self.advertisement_service
+                .get_advertisements_by_id(&advertisement_ids),
+            self.image_service.get_images_by_id(&image_ids),
+        )?;
+
+        for asset in assets {
+            asset.resolve(&advertisements, &images);
+        }
+
+        Ok(())
+    }
 
     /// Build a container from data maps.
     ///
@@ -221,7 +1233,7 @@ impl ContainerService {
     /// [`ImageMap`], and combines them with a list of videos to make a container.
     fn build_container(
         &self,
-        container_id: u32,
+        container_id: ContainerId,
         advertisements: &AdvertisementMap,
         images: &ImageMap,
         videos: &[Video],
@@ -238,7 +1250,7 @@ impl ContainerService {
             None => images_default,
         };
 
-        Container::from(container_id, advertisements, images, videos)
+        Container::from(container_id, advertisements, images, videos, &*self.titler)
     }
 }
 
@@ -246,21 +1258,461 @@ impl ContainerService {
 
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
+
+    use chrono::{DateTime, TimeDelta, Utc};
+
     use crate::service::advertisement::Advertisement;
+    use crate::service::catalog::CatalogSnapshot;
     use crate::service::image::Image;
-    use crate::service::video::Video;
-    use crate::types::Result;
+    use crate::service::metadata::{ContainerMetadata, MetadataStore};
+    use crate::service::report::ExpiringVideo;
+    use crate::service::video::{Video, VideoFilter, VideoSorting};
+    use crate::types::{
+        AdvertisementId, ContainerId, ErrorKind, ImageId, Result, SortOrder, VideoId, VideoType,
+    };
+
+    use super::{
+        Container, ContainerBatch, ContainerFilter, ContainerService, ContainerSort,
+        ContainerSorting, ContainerStatus,
+    };
+
+    fn video(id: u32) -> Video {
+        Video::builder(VideoId(id))
+            .description(String::new())
+            .playback_url(format!("/path/to/test{}.m3u8", id))
+            .title(format!("Video {}", id))
+            .r#type(VideoType::Clip)
+            .build()
+    }
+
+    fn containers() -> Vec<Container> {
+        vec![
+            Container::new(
+                Vec::new(),
+                ContainerId(2),
+                Vec::new(),
+                "b".to_string(),
+                vec![video(3)],
+            ),
+            Container::new(
+                Vec::new(),
+                ContainerId(0),
+                Vec::new(),
+                "b".to_string(),
+                vec![video(2), video(1)],
+            ),
+            Container::new(
+                Vec::new(),
+                ContainerId(1),
+                Vec::new(),
+                "a".to_string(),
+                Vec::new(),
+            ),
+        ]
+    }
+
+    fn ids(containers: &[Container]) -> Vec<u32> {
+        containers
+            .iter()
+            .map(|container| container.id().0)
+            .collect()
+    }
+
+    #[test]
+    fn filter_containers_by_videos() {
+        // Given
+        let filter: ContainerFilter = ContainerFilter {
+            videos: VideoFilter {
+                title: Some("Video 2".to_string()),
+                ..VideoFilter::default()
+            },
+            ..ContainerFilter::default()
+        };
+
+        // When
+        let actual: Vec<Container> = containers()
+            .into_iter()
+            .filter_map(|container| filter.apply(container))
+            .collect();
+
+        // Then
+        assert_eq!(ids(&actual), vec![0]);
+        assert_eq!(actual[0].videos().len(), 1);
+    }
+
+    #[test]
+    fn filter_containers_by_ads() {
+        // Given
+        let with_ads: Container = Container::new(
+            vec![Advertisement::new(
+                AdvertisementId(0),
+                "Ad".to_string(),
+                "/ad".to_string(),
+            )],
+            ContainerId(3),
+            Vec::new(),
+            "c".to_string(),
+            Vec::new(),
+        );
+        let has_ads: ContainerFilter = ContainerFilter {
+            has_ads: Some(true),
+            ..ContainerFilter::default()
+        };
+        let has_no_ads: ContainerFilter = ContainerFilter {
+            has_ads: Some(false),
+            ..ContainerFilter::default()
+        };
+
+        // When
+        let with: Option<Container> = has_ads.apply(with_ads.clone());
+        let without: Option<Container> = has_no_ads.apply(with_ads);
+
+        // Then
+        assert!(with.is_some());
+        assert!(without.is_none());
+    }
+
+    #[test]
+    fn filter_containers_by_status() {
+        // Given
+        let upcoming: Container = Container::new(
+            Vec::new(),
+            ContainerId(3),
+            vec![Image::new(
+                ImageId(0),
+                "Image".to_string(),
+                "/image".to_string(),
+            )],
+            "c".to_string(),
+            Vec::new(),
+        );
+        let filter: ContainerFilter = ContainerFilter {
+            statuses: vec![ContainerStatus::Active],
+            ..ContainerFilter::default()
+        };
+
+        // When
+        let actual: Vec<Container> = containers()
+            .into_iter()
+            .chain([upcoming.clone()])
+            .filter_map(|container| filter.apply(container))
+            .collect();
+
+        // Then
+        assert_eq!(upcoming.status(), ContainerStatus::Upcoming);
+        assert!(actual
+            .iter()
+            .all(|container| container.status() == ContainerStatus::Active));
+        assert!(!ids(&actual).contains(&3));
+    }
+
+    #[tokio::test]
+    async fn list_containers_status_with_every_video_expired() {
+        // Given
+        let now: DateTime<Utc> = Utc::now();
+        let expired: Container = Container::new(
+            Vec::new(),
+            ContainerId(4),
+            Vec::new(),
+            "d".to_string(),
+            vec![Video::builder(VideoId(5))
+                .description(String::new())
+                .expires_at(Some(now - TimeDelta::days(1)))
+                .playback_url("/path/to/test5.m3u8".to_string())
+                .title("Video 5".to_string())
+                .r#type(VideoType::Clip)
+                .build()],
+        );
+        let under_test = ContainerService::default();
+        under_test.snapshot().set(vec![expired], now);
+
+        // When
+        let result: Result<Vec<Container>> = under_test
+            .list_containers(&ContainerFilter::default(), &ContainerSorting::default())
+            .await;
+
+        // Then
+        match result {
+            Ok(actual) => {
+                assert_eq!(ids(&actual), vec![4]);
+                assert!(actual[0].videos().is_empty());
+                assert_eq!(actual[0].status(), ContainerStatus::Active);
+            }
+            Err(err) => panic!("Failed to list containers with error: {}", err),
+        }
+    }
+
+    #[test]
+    fn sort_containers_default() {
+        // Given
+        let mut actual: Vec<Container> = containers();
+
+        // When
+        ContainerSorting::default().sort(&mut actual);
+
+        // Then
+        assert_eq!(ids(&actual), vec![0, 1, 2]);
+        assert_eq!(actual[0].videos()[0].id(), VideoId(1));
+    }
+
+    #[test]
+    fn sort_containers_by_title() {
+        // Given
+        let mut actual: Vec<Container> = containers();
+        let sorting: ContainerSorting = ContainerSorting {
+            sort: ContainerSort::Title,
+            ..ContainerSorting::default()
+        };
+
+        // When
+        sorting.sort(&mut actual);
+
+        // Then
+        assert_eq!(ids(&actual), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn sort_containers_by_video_count_descending() {
+        // Given
+        let mut actual: Vec<Container> = containers();
+        let sorting: ContainerSorting = ContainerSorting {
+            sort: ContainerSort::VideoCount,
+            order: SortOrder::Desc,
+            videos: VideoSorting::default(),
+        };
+
+        // When
+        sorting.sort(&mut actual);
+
+        // Then
+        assert_eq!(ids(&actual), vec![0, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn list_expiring_videos_from_snapshot() {
+        // Given
+        let now: DateTime<Utc> = Utc::now();
+        let expiring = |id: u32, expires_in: TimeDelta| {
+            Video::builder(VideoId(id))
+                .description(String::new())
+                .expires_at(Some(now + expires_in))
+                .playback_url(format!("/path/to/test{}.m3u8", id))
+                .title(format!("Video {}", id))
+                .r#type(VideoType::Clip)
+                .build()
+        };
+        let under_test = ContainerService::default();
+        under_test.snapshot().set(
+            vec![
+                Container::new(
+                    Vec::new(),
+                    ContainerId(1),
+                    Vec::new(),
+                    "a".to_string(),
+                    vec![video(1), expiring(2, TimeDelta::days(3))],
+                ),
+                Container::new(
+                    Vec::new(),
+                    ContainerId(0),
+                    Vec::new(),
+                    "b".to_string(),
+                    vec![
+                        expiring(3, TimeDelta::days(10)),
+                        expiring(4, TimeDelta::hours(1)),
+                    ],
+                ),
+            ],
+            now,
+        );
+
+        // When
+        let result: Result<Vec<ExpiringVideo>> = under_test
+            .list_expiring_videos(now, Duration::from_secs(7 * 24 * 60 * 60))
+            .await;
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(
+                actual
+                    .iter()
+                    .map(|video| (video.container_id().0, video.video_id().0))
+                    .collect::<Vec<(u32, u32)>>(),
+                vec![(0, 4), (1, 2)]
+            ),
+            Err(err) => panic!("Failed to list expiring videos with error: {}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn list_containers_with_metadata() {
+        // Given
+        let store: MetadataStore = MetadataStore::default();
+        store
+            .put(
+                ContainerId(1),
+                ContainerMetadata {
+                    hidden: true,
+                    ..ContainerMetadata::default()
+                },
+            )
+            .await
+            .unwrap();
+        store
+            .put(
+                ContainerId(2),
+                ContainerMetadata {
+                    title: Some("Featured".to_string()),
+                    display_order: Some(0),
+                    ..ContainerMetadata::default()
+                },
+            )
+            .await
+            .unwrap();
+        let under_test = ContainerService::default().metadata_store(store);
+        under_test.snapshot().set(containers(), Utc::now());
+        let sorting: ContainerSorting = ContainerSorting {
+            sort: ContainerSort::DisplayOrder,
+            ..ContainerSorting::default()
+        };
+
+        // When
+        let result: Result<Vec<Container>> = under_test
+            .list_containers(&ContainerFilter::default(), &sorting)
+            .await;
+
+        // Then
+        match result {
+            Ok(actual) => {
+                assert_eq!(ids(&actual), vec![2, 0]);
+                assert_eq!(actual[0].title(), "Featured");
+            }
+            Err(err) => panic!("Failed to list containers with error: {}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_containers_from_snapshot() {
+        // Given
+        let under_test = ContainerService::default();
+        under_test.snapshot().set(containers(), Utc::now());
+        let container_ids: Vec<ContainerId> = vec![
+            ContainerId(2),
+            ContainerId(9),
+            ContainerId(0),
+            ContainerId(2),
+        ];
+
+        // When
+        let result: Result<ContainerBatch> = under_test
+            .get_containers(
+                &container_ids,
+                &VideoFilter::default(),
+                &VideoSorting::default(),
+            )
+            .await;
+
+        // Then
+        match result {
+            Ok(actual) => {
+                assert_eq!(ids(&actual.containers), vec![2, 0]);
+                assert_eq!(actual.missing, vec![ContainerId(9)]);
+                assert_eq!(
+                    actual.containers[1]
+                        .videos()
+                        .iter()
+                        .map(|video| video.id().0)
+                        .collect::<Vec<u32>>(),
+                    vec![1, 2]
+                );
+            }
+            Err(err) => panic!("Failed to get containers with error: {}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_containers_reports_hidden_as_missing() {
+        // Given
+        let store: MetadataStore = MetadataStore::default();
+        store
+            .put(
+                ContainerId(2),
+                ContainerMetadata {
+                    hidden: true,
+                    ..ContainerMetadata::default()
+                },
+            )
+            .await
+            .unwrap();
+        let under_test = ContainerService::default().metadata_store(store);
+        under_test.snapshot().set(containers(), Utc::now());
+
+        // When
+        let result: Result<ContainerBatch> = under_test
+            .get_containers(
+                &[ContainerId(2), ContainerId(0)],
+                &VideoFilter::default(),
+                &VideoSorting::default(),
+            )
+            .await;
 
-    use super::{Container, ContainerService};
+        // Then
+        match result {
+            Ok(actual) => {
+                assert_eq!(ids(&actual.containers), vec![0]);
+                assert_eq!(actual.missing, vec![ContainerId(2)]);
+            }
+            Err(err) => panic!("Failed to get containers with error: {}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_container_hidden_is_not_found() {
+        // Given
+        let store: MetadataStore = MetadataStore::default();
+        store
+            .put(
+                ContainerId(2),
+                ContainerMetadata {
+                    hidden: true,
+                    ..ContainerMetadata::default()
+                },
+            )
+            .await
+            .unwrap();
+        let under_test = ContainerService::default().metadata_store(store);
+        under_test.import_catalog(CatalogSnapshot::new(containers(), Utc::now()));
+
+        // When
+        let result: Result<Container> = under_test
+            .get_container(
+                ContainerId(2),
+                &VideoFilter::default(),
+                &VideoSorting::default(),
+            )
+            .await;
+
+        // Then
+        match result {
+            Ok(actual) => panic!("Got hidden container {}", actual.id()),
+            Err(err) => assert_eq!(err.kind, ErrorKind::NotFound),
+        }
+    }
 
     #[tokio::test]
     async fn test_get_container() {
         // Given
         let under_test = ContainerService::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
-        let result: Result<Container> = under_test.get_container(container_id).await;
+        let result: Result<Container> = under_test
+            .get_container(
+                container_id,
+                &VideoFilter::default(),
+                &VideoSorting::default(),
+            )
+            .await;
 
         // Then
         match result {
@@ -273,7 +1725,7 @@ mod test {
     async fn test_list_advertisements() {
         // Given
         let under_test = ContainerService::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
         let result: Result<Vec<Advertisement>> = under_test.get_advertisements(container_id).await;
@@ -292,7 +1744,9 @@ mod test {
         let expected: usize = 31;
 
         // When
-        let result: Result<Vec<Container>> = under_test.list_containers().await;
+        let result: Result<Vec<Container>> = under_test
+            .list_containers(&ContainerFilter::default(), &ContainerSorting::default())
+            .await;
 
         // Then
         match result {
@@ -305,7 +1759,7 @@ mod test {
     async fn test_list_images() {
         // Given
         let under_test = ContainerService::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
         let result: Result<Vec<Image>> = under_test.get_images(container_id).await;
@@ -321,10 +1775,16 @@ mod test {
     async fn test_list_videos() {
         // Given
         let under_test = ContainerService::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
-        let result: Result<Vec<Video>> = under_test.get_videos(container_id).await;
+        let result: Result<Vec<Video>> = under_test
+            .get_videos(
+                container_id,
+                &VideoFilter::default(),
+                &VideoSorting::default(),
+            )
+            .await;
 
         // Then
         match result {
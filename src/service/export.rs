@@ -0,0 +1,244 @@
+//! CSV exports of catalog collections.
+//!
+//! Each collection is flattened into one row per item, with the ID of the container the item
+//! belongs to. Containers are flattened into one row per video, and containers without videos
+//! into one row with empty video columns.
+
+use crate::{
+    service::{
+        advertisement::Advertisement, container::Container, image::Image, report::csv_field,
+        video::Video,
+    },
+    types::{ContainerId, Timestamp},
+};
+
+/// Header row of [`containers_to_csv`].
+const CONTAINER_CSV_HEADER: &str =
+    "containerId,containerTitle,status,videoId,title,type,expiresAt,playbackUrl";
+
+/// Header row of [`videos_to_csv`].
+const VIDEO_CSV_HEADER: &str = "containerId,videoId,title,type,expiresAt,playbackUrl";
+
+/// Header row of [`images_to_csv`].
+const IMAGE_CSV_HEADER: &str = "containerId,imageId,name,url";
+
+/// Header row of [`advertisements_to_csv`].
+const ADVERTISEMENT_CSV_HEADER: &str = "containerId,advertisementId,name,url";
+
+/* ************************************** Utility function ************************************** */
+
+/// Format containers as CSV, with a header row and one row per video.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::service::export::containers_to_csv;
+///
+/// assert_eq!(
+///     containers_to_csv(&[]),
+///     "containerId,containerTitle,status,videoId,title,type,expiresAt,playbackUrl\r\n"
+/// );
+/// ```
+pub fn containers_to_csv(containers: &[Container]) -> String {
+    let mut csv: String = format!("{}\r\n", CONTAINER_CSV_HEADER);
+
+    for container in containers {
+        let columns: String = format!(
+            "{},{},{}",
+            container.id(),
+            csv_field(container.title()),
+            container.status()
+        );
+
+        match container.videos().is_empty() {
+            true => csv.push_str(&format!("{},,,,,\r\n", columns)),
+            false => {
+                for video in container.videos() {
+                    csv.push_str(&format!("{},{}\r\n", columns, video_columns(video)));
+                }
+            }
+        }
+    }
+
+    csv
+}
+
+/// Format the videos of a container as CSV, with a header row.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{service::export::videos_to_csv, types::ContainerId};
+///
+/// assert_eq!(
+///     videos_to_csv(ContainerId(1), &[]),
+///     "containerId,videoId,title,type,expiresAt,playbackUrl\r\n"
+/// );
+/// ```
+pub fn videos_to_csv(container_id: ContainerId, videos: &[Video]) -> String {
+    let mut csv: String = format!("{}\r\n", VIDEO_CSV_HEADER);
+
+    for video in videos {
+        csv.push_str(&format!("{},{}\r\n", container_id, video_columns(video)));
+    }
+
+    csv
+}
+
+/// Format the images of a container as CSV, with a header row.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{service::export::images_to_csv, types::ContainerId};
+///
+/// assert_eq!(images_to_csv(ContainerId(1), &[]), "containerId,imageId,name,url\r\n");
+/// ```
+pub fn images_to_csv(container_id: ContainerId, images: &[Image]) -> String {
+    let mut csv: String = format!("{}\r\n", IMAGE_CSV_HEADER);
+
+    for image in images {
+        csv.push_str(&format!(
+            "{},{},{},{}\r\n",
+            container_id,
+            image.id(),
+            csv_field(image.name()),
+            csv_field(image.url())
+        ));
+    }
+
+    csv
+}
+
+/// Format the advertisements of a container as CSV, with a header row.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{service::export::advertisements_to_csv, types::ContainerId};
+///
+/// assert_eq!(
+///     advertisements_to_csv(ContainerId(1), &[]),
+///     "containerId,advertisementId,name,url\r\n"
+/// );
+/// ```
+pub fn advertisements_to_csv(
+    container_id: ContainerId,
+    advertisements: &[Advertisement],
+) -> String {
+    let mut csv: String = format!("{}\r\n", ADVERTISEMENT_CSV_HEADER);
+
+    for advertisement in advertisements {
+        csv.push_str(&format!(
+            "{},{},{},{}\r\n",
+            container_id,
+            advertisement.id(),
+            csv_field(advertisement.name()),
+            csv_field(advertisement.url())
+        ));
+    }
+
+    csv
+}
+
+/// Format the video columns of a row, `videoId,title,type,expiresAt,playbackUrl`.
+fn video_columns(video: &Video) -> String {
+    let expires_at: String = video
+        .expires_at()
+        .map_or_else(String::new, |expires_at| Timestamp(expires_at).to_string());
+
+    format!(
+        "{},{},{},{},{}",
+        video.id(),
+        csv_field(video.title()),
+        video.r#type(),
+        expires_at,
+        csv_field(video.playback_url())
+    )
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        service::{container::Container, image::Image, video::Video},
+        types::{parse_timestamp, ContainerId, ImageId, VideoId, VideoType},
+    };
+
+    use super::{containers_to_csv, images_to_csv, videos_to_csv};
+
+    fn video(id: u32, title: &str, expires_at: &str) -> Video {
+        Video::builder(VideoId(id))
+            .description(String::new())
+            .expires_at(parse_timestamp(expires_at))
+            .playback_url(format!("/path/to/test{}.m3u8", id))
+            .title(title.to_string())
+            .r#type(VideoType::Movie)
+            .build()
+    }
+
+    #[test]
+    fn containers_to_csv_row_per_video() {
+        // Given
+        let containers: Vec<Container> = vec![
+            Container::new(
+                Vec::new(),
+                ContainerId(1),
+                Vec::new(),
+                "Movies, Etc.".to_string(),
+                vec![video(2, "Alpha", "2022-03-23"), video(3, "Beta", "")],
+            ),
+            Container::new(
+                Vec::new(),
+                ContainerId(4),
+                Vec::new(),
+                "Empty".to_string(),
+                Vec::new(),
+            ),
+        ];
+        let expected: &str =
+            "containerId,containerTitle,status,videoId,title,type,expiresAt,playbackUrl\r\n\
+            1,\"Movies, Etc.\",active,2,Alpha,MOVIE,2022-03-23T00:00:00Z,/path/to/test2.m3u8\r\n\
+            1,\"Movies, Etc.\",active,3,Beta,MOVIE,,/path/to/test3.m3u8\r\n\
+            4,Empty,empty,,,,,\r\n";
+
+        // When
+        let actual: String = containers_to_csv(&containers);
+
+        // Then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn videos_to_csv_with_container_id() {
+        // Given
+        let videos: Vec<Video> = vec![video(2, "Say \"Hi\"", "")];
+        let expected: &str = "containerId,videoId,title,type,expiresAt,playbackUrl\r\n\
+            1,2,\"Say \"\"Hi\"\"\",MOVIE,,/path/to/test2.m3u8\r\n";
+
+        // When
+        let actual: String = videos_to_csv(ContainerId(1), &videos);
+
+        // Then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn images_to_csv_with_container_id() {
+        // Given
+        let images: Vec<Image> = vec![Image::new(
+            ImageId(5),
+            "Poster".to_string(),
+            "https://example.com/poster.png".to_string(),
+        )];
+        let expected: &str = "containerId,imageId,name,url\r\n\
+            1,5,Poster,https://example.com/poster.png\r\n";
+
+        // When
+        let actual: String = images_to_csv(ContainerId(1), &images);
+
+        // Then
+        assert_eq!(actual, expected);
+    }
+}
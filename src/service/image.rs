@@ -2,11 +2,19 @@
 
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use log::trace;
+use lru::LruCache;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::{repository::image::ImageRepository, service::group, types::Result};
+use crate::{
+    repository::image::ImageRepository,
+    service::{group, mirror::Mirror, ABSENT_TTL, CACHE_CAPACITY, CACHE_TTL},
+    types::{ContainerId, Error, ErrorKind, ImageId, Result},
+};
 
 /* ******************************************* Image ******************************************** */
 
@@ -20,11 +28,11 @@ use crate::{repository::image::ImageRepository, service::group, types::Result};
 /// [1]: [crate::repository::types::image::ImageDto]
 /// [2]: [crate::repository::image]
 ///
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Image {
     /// Unique image identifier.
-    id: u32,
+    id: ImageId,
     /// Name of image.
     name: String,
     /// Image URL.
@@ -33,9 +41,24 @@ pub struct Image {
 
 impl Image {
     /// Construct a new Image.
-    pub fn new(id: u32, name: String, url: String) -> Self {
+    pub fn new(id: ImageId, name: String, url: String) -> Self {
         Image { id, name, url }
     }
+
+    /// Get image ID.
+    pub fn id(&self) -> ImageId {
+        self.id
+    }
+
+    /// Get name of image.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get image URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
 }
 
 impl Display for Image {
@@ -50,7 +73,7 @@ impl Display for Image {
 
 /* ****************************************** ImageMap ****************************************** */
 
-/// Type alias for a [`HashMap`] of [`u32`] to [`Vec`]`<`[`Image`]`>`.
+/// Type alias for a [`HashMap`] of [`ContainerId`] to [`Vec`]`<`[`Image`]`>`.
 ///
 /// # Examples
 ///
@@ -60,7 +83,7 @@ impl Display for Image {
 /// let service: ImageService = ImageService::default();
 /// let containers: ImageMap = service.list_images().await?;
 /// ```
-pub type ImageMap = HashMap<u32, Vec<Image>>;
+pub type ImageMap = HashMap<ContainerId, Vec<Image>>;
 
 /* **************************************** ImageService **************************************** */
 
@@ -68,16 +91,139 @@ pub type ImageMap = HashMap<u32, Vec<Image>>;
 ///
 /// [`ImageService`] is the service layer wrapper for [`ImageRepository`]. It transforms
 /// DTO types into domain types.
-#[derive(Default)]
+///
+/// Rocket Image can't look up an image by ID, so every image returned from a listing is cached
+/// by ID for [`ImageService::get_images_by_id`] for a few minutes, and IDs which a listing didn't
+/// return are remembered as absent for a short while. Listings are fetched through a [`Mirror`].
 pub struct ImageService {
+    /// When each image ID was found absent from a listing.
+    absent: Mutex<LruCache<ImageId, Instant>>,
+    /// Images by ID, with when each expires.
+    cache: Mutex<LruCache<ImageId, (Instant, Image)>>,
+    /// Local mirror of Rocket Image.
+    mirror: Arc<Mirror>,
     /// Repository layer that the service calls.
     repository: ImageRepository,
 }
 
+impl Default for ImageService {
+    fn default() -> Self {
+        Self::new(ImageRepository::default())
+    }
+}
+
 impl ImageService {
     /// Create a new [`ImageService`].
     pub fn new(repository: ImageRepository) -> Self {
-        Self { repository }
+        Self {
+            absent: Mutex::new(LruCache::new(CACHE_CAPACITY)),
+            cache: Mutex::new(LruCache::new(CACHE_CAPACITY)),
+            mirror: Arc::new(Mirror::default()),
+            repository,
+        }
+    }
+
+    /// Set the local mirror which images are fetched through.
+    pub fn mirror(mut self, mirror: Arc<Mirror>) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// Get image by ID.
+    ///
+    /// See [`ImageService::get_images_by_id`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(image)` if the image exists.
+    /// - `Err(`[`ErrorKind::NotFound`]`)` if there is no image with ID `image_id`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::{
+    ///     service::image::{Image, ImageService},
+    ///     types::{ImageId, Result},
+    /// };
+    ///
+    /// async fn get_image(service: &ImageService) -> Result<Image> {
+    ///     service.get_image(ImageId(1)).await
+    /// }
+    /// ```
+    pub async fn get_image(&self, image_id: ImageId) -> Result<Image> {
+        trace!("ImageService::get_image {}", image_id);
+
+        self.get_images_by_id(&[image_id])
+            .await?
+            .remove(&image_id)
+            .ok_or_else(|| Error {
+                kind: ErrorKind::NotFound,
+                message: format!("Image {} not found", image_id),
+            })
+    }
+
+    /// Get images by ID.
+    ///
+    /// Returns cached images if they are all recently cached or recently absent, otherwise lists
+    /// all images from Rocket Image once. IDs which don't exist are missing from the returned map.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use rocket_container::{
+    ///     service::image::{Image, ImageService},
+    ///     types::{ImageId, Result},
+    /// };
+    ///
+    /// async fn get_images(service: &ImageService) -> Result<HashMap<ImageId, Image>> {
+    ///     service.get_images_by_id(&[ImageId(1), ImageId(2)]).await
+    /// }
+    /// ```
+    pub async fn get_images_by_id(&self, image_ids: &[ImageId]) -> Result<HashMap<ImageId, Image>> {
+        trace!("ImageService::get_images_by_id {:?}", image_ids);
+
+        let mut images: HashMap<ImageId, Image> = HashMap::with_capacity(image_ids.len());
+        let mut missing: bool = false;
+
+        {
+            let mut absent = self.absent.lock().unwrap();
+            let mut cache = self.cache.lock().unwrap();
+
+            for image_id in image_ids {
+                match cache.get(image_id) {
+                    Some((expires_at, image)) if Instant::now() < *expires_at => {
+                        images.insert(*image_id, image.clone());
+                    }
+                    _ if absent
+                        .get(image_id)
+                        .is_some_and(|listed_at| listed_at.elapsed() < ABSENT_TTL) => {}
+                    _ => missing = true,
+                }
+            }
+        }
+
+        if missing {
+            for image in self.list_images().await?.into_values().flatten() {
+                if image_ids.contains(&image.id) {
+                    images.insert(image.id, image);
+                }
+            }
+
+            let mut absent = self.absent.lock().unwrap();
+            let mut cache = self.cache.lock().unwrap();
+            let listed_at: Instant = Instant::now();
+
+            for image_id in image_ids {
+                if !images.contains_key(image_id) {
+                    cache.pop(image_id);
+                    absent.put(*image_id, listed_at);
+                }
+            }
+        }
+
+        Ok(images)
     }
 
     /// List all images from Rocket Image.
@@ -93,14 +239,17 @@ impl ImageService {
     pub async fn list_images(&self) -> Result<ImageMap> {
         trace!("ImageService::list_images");
 
-        let images = self
-            .repository
-            .list_images()
+        let images: Vec<(ContainerId, Image)> = self
+            .mirror
+            .fetch("images", self.repository.list_images())
             .await?
             .into_iter()
-            .map(|image| (image.container_id().parse().unwrap(), Image::from(image)));
+            .map(|image| (image.container_id(), Image::from(image)))
+            .collect();
+
+        self.cache(images.iter().map(|(_, image)| image));
 
-        Ok(group(images))
+        Ok(group(images.into_iter()))
     }
 
     /// List images for a container from Rocket Image.
@@ -108,35 +257,154 @@ impl ImageService {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::service::image::{Image, ImageService};
+    /// use rocket_container::{
+    ///     service::image::{Image, ImageService},
+    ///     types::ContainerId,
+    /// };
     ///
-    /// let container_id: u32 = 1;
+    /// let container_id: ContainerId = ContainerId(1);
     /// let service: ImageService = ImageService::default();
     /// let containers: Vec<Image> = service.list_images_by_container(container_id).await?;
     /// ```
-    pub async fn list_images_by_container(&self, container_id: u32) -> Result<Vec<Image>> {
+    pub async fn list_images_by_container(&self, container_id: ContainerId) -> Result<Vec<Image>> {
         trace!("ImageService::list_images_by_container {}", container_id);
 
         let images: Vec<Image> = self
-            .repository
-            .list_images_by_container(container_id)
+            .mirror
+            .fetch(
+                &format!("images.container-{}", container_id),
+                self.repository.list_images_by_container(container_id),
+            )
             .await?
             .into_iter()
             .map(Image::from)
             .collect();
 
+        self.cache(images.iter());
+
         Ok(images)
     }
+
+    /* ****************************** Private utility function ****************************** */
+
+    /// Add images to the cache, and forget that they were absent.
+    fn cache<'a, I: Iterator<Item = &'a Image>>(&self, images: I) {
+        let mut absent = self.absent.lock().unwrap();
+        let mut cache = self.cache.lock().unwrap();
+        let expires_at: Instant = Instant::now() + CACHE_TTL;
+
+        for image in images {
+            absent.pop(&image.id);
+            cache.put(image.id, (expires_at, image.clone()));
+        }
+    }
 }
 
 /* ******************************************* Tests ******************************************** */
 
 #[cfg(test)]
 mod test {
-    use crate::types::Result;
+    use std::{env, fs, path::PathBuf, sync::Arc, time::Instant};
+
+    use serde_json::json;
+
+    use crate::{
+        repository::image::ImageDto,
+        service::mirror::Mirror,
+        types::{ContainerId, ErrorKind, ImageId, Result},
+    };
 
     use super::{Image, ImageMap, ImageService};
 
+    #[tokio::test]
+    async fn get_image_cached() {
+        // Given
+        let service = ImageService::default();
+        let expected: Image = Image::new(
+            ImageId(7),
+            "Image".to_string(),
+            "https://image.com".to_string(),
+        );
+        service.cache([expected.clone()].iter());
+
+        // When
+        let result: Result<Image> = service.get_image(ImageId(7)).await;
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual, expected),
+            Err(err) => panic!("Failed to get image with error: {}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn get_image_absent() {
+        // Given
+        let path: PathBuf =
+            env::temp_dir().join(format!("image-absent-{}.sqlite3", std::process::id()));
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+        mirror
+            .fetch("images", async { Ok(Vec::<ImageDto>::new()) })
+            .await
+            .unwrap();
+        let service =
+            ImageService::default().mirror(Arc::new(Mirror::open(&path).unwrap().offline(true)));
+
+        // When
+        let listed: Result<Image> = service.get_image(ImageId(8)).await;
+        mirror
+            .fetch("images", async {
+                Ok(serde_json::from_value::<Vec<ImageDto>>(json!([{
+                    "containerId": "0",
+                    "id": "8",
+                    "name": "Image",
+                    "url": "https://image.com"
+                }]))
+                .unwrap())
+            })
+            .await
+            .unwrap();
+        let remembered: Result<Image> = service.get_image(ImageId(8)).await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(listed.is_err_and(|err| err.kind == ErrorKind::NotFound));
+        assert!(remembered.is_err_and(|err| err.kind == ErrorKind::NotFound));
+    }
+
+    #[tokio::test]
+    async fn get_image_expired() {
+        // Given
+        let path: PathBuf =
+            env::temp_dir().join(format!("image-expired-{}.sqlite3", std::process::id()));
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+        mirror
+            .fetch("images", async { Ok(Vec::<ImageDto>::new()) })
+            .await
+            .unwrap();
+        let service =
+            ImageService::default().mirror(Arc::new(Mirror::open(&path).unwrap().offline(true)));
+        service.cache.lock().unwrap().put(
+            ImageId(9),
+            (
+                Instant::now(),
+                Image::new(
+                    ImageId(9),
+                    "Image".to_string(),
+                    "https://image.com".to_string(),
+                ),
+            ),
+        );
+
+        // When
+        let result: Result<Image> = service.get_image(ImageId(9)).await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(result.is_err_and(|err| err.kind == ErrorKind::NotFound));
+        assert!(service.cache.lock().unwrap().peek(&ImageId(9)).is_none());
+    }
+
     #[tokio::test]
     async fn test_list_images() {
         // Given
@@ -156,7 +424,7 @@ mod test {
     async fn test_list_images_by_container() {
         // Given
         let service = ImageService::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
         let result: Result<Vec<Image>> = service.list_images_by_container(container_id).await;
@@ -0,0 +1,288 @@
+//! Editorial metadata for containers.
+//!
+//! Rocket Container's dependencies don't have anything about a container beyond its ID, so
+//! editors can attach [`ContainerMetadata`] to containers. [`ContainerService`][1] merges the
+//! metadata in a [`MetadataStore`] into containers as they are served, so edits apply immediately
+//! without waiting for the container snapshot to be fetched again.
+//!
+//! [1]: crate::service::container::ContainerService
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use log::info;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::{sync::Mutex, task};
+
+use crate::types::{option_to_string, ContainerId, Error, ErrorKind, ImageId, Result};
+
+/* ************************************* ContainerMetadata ************************************** */
+
+/// Editorial metadata for a container.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{service::metadata::ContainerMetadata, types::ImageId};
+///
+/// let metadata: ContainerMetadata = ContainerMetadata {
+///     title: Some("My Family".to_string()),
+///     hero_image_id: Some(ImageId(2)),
+///     featured: true,
+///     ..ContainerMetadata::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ContainerMetadata {
+    /// Title, which takes precedence over the generated title.
+    pub title: Option<String>,
+    /// Description.
+    pub description: Option<String>,
+    /// ID of the image to feature for the container.
+    pub hero_image_id: Option<ImageId>,
+    /// Whether the container is featured.
+    pub featured: bool,
+    /// Whether the container is left out of container listings.
+    pub hidden: bool,
+    /// Position of the container when listing containers by display order. Containers without a
+    /// display order come last.
+    pub display_order: Option<i32>,
+}
+
+impl Display for ContainerMetadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ContainerMetadata {{ title: {}, description: {}, hero_image_id: {}, featured: {}, \
+            hidden: {}, display_order: {} }}",
+            option_to_string(&self.title),
+            option_to_string(&self.description),
+            option_to_string(&self.hero_image_id),
+            self.featured,
+            self.hidden,
+            option_to_string(&self.display_order)
+        )
+    }
+}
+
+/* *************************************** MetadataStore **************************************** */
+
+/// Store for [`ContainerMetadata`] by container ID.
+///
+/// A store is either in memory only, or backed by a JSON file which is rewritten on every change.
+/// Changes are written one at a time, and readers only see a change once it has been written.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     service::metadata::{ContainerMetadata, MetadataStore},
+///     types::{ContainerId, Result},
+/// };
+///
+/// async fn feature(store: &MetadataStore) -> Result<()> {
+///     let metadata: ContainerMetadata = ContainerMetadata {
+///         featured: true,
+///         ..ContainerMetadata::default()
+///     };
+///
+///     store.put(ContainerId(0), metadata).await
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct MetadataStore {
+    /// File the metadata is persisted to, if any.
+    path: Option<PathBuf>,
+    /// Metadata by container ID.
+    metadata: RwLock<HashMap<ContainerId, ContainerMetadata>>,
+    /// Held while a change is written, so changes are written in order.
+    writer: Mutex<()>,
+}
+
+impl MetadataStore {
+    /// Open a store backed by a JSON file.
+    ///
+    /// The file is created on the first change if it doesn't exist yet.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(store)` if the file was loaded or doesn't exist.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if the file can't be read or parsed.
+    pub fn open(path: &Path) -> Result<Self> {
+        let metadata: HashMap<ContainerId, ContainerMetadata> = match path.exists() {
+            true => {
+                let contents: String =
+                    fs::read_to_string(path).map_err(|err| file_error(path, err.to_string()))?;
+
+                serde_json::from_str(&contents).map_err(|err| file_error(path, err.to_string()))?
+            }
+            false => HashMap::new(),
+        };
+
+        info!(
+            "Loaded metadata for {} containers from {}",
+            metadata.len(),
+            path.display()
+        );
+
+        Ok(MetadataStore {
+            path: Some(path.to_path_buf()),
+            metadata: RwLock::new(metadata),
+            writer: Mutex::new(()),
+        })
+    }
+
+    /// Get metadata for a container, or [None] if it has none.
+    pub fn get(&self, container_id: ContainerId) -> Option<ContainerMetadata> {
+        self.metadata.read().unwrap().get(&container_id).cloned()
+    }
+
+    /// Set metadata for a container, replacing any metadata it already has.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(())` if the metadata was stored.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if the file backing the store can't be written. The
+    ///   change is not kept.
+    pub async fn put(&self, container_id: ContainerId, metadata: ContainerMetadata) -> Result<()> {
+        let _writer = self.writer.lock().await;
+        let mut updated: HashMap<ContainerId, ContainerMetadata> =
+            self.metadata.read().unwrap().clone();
+
+        updated.insert(container_id, metadata);
+        self.persist(&updated).await?;
+        *self.metadata.write().unwrap() = updated;
+
+        Ok(())
+    }
+
+    /// Remove metadata for a container.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(true)` if the container had metadata, `Ok(false)` if it didn't.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if the file backing the store can't be written. The
+    ///   change is not kept.
+    pub async fn delete(&self, container_id: ContainerId) -> Result<bool> {
+        let _writer = self.writer.lock().await;
+        let mut updated: HashMap<ContainerId, ContainerMetadata> =
+            self.metadata.read().unwrap().clone();
+
+        if updated.remove(&container_id).is_none() {
+            return Ok(false);
+        }
+
+        self.persist(&updated).await?;
+        *self.metadata.write().unwrap() = updated;
+
+        Ok(true)
+    }
+
+    /// Write metadata to the file backing the store, if any, on the blocking thread pool.
+    ///
+    /// Metadata is written to a temporary file first, then renamed, so a failed write never
+    /// leaves a truncated file behind.
+    async fn persist(&self, metadata: &HashMap<ContainerId, ContainerMetadata>) -> Result<()> {
+        let path: PathBuf = match &self.path {
+            Some(path) => path.clone(),
+            None => return Ok(()),
+        };
+        let contents: String = serde_json::to_string_pretty(metadata)
+            .map_err(|err| file_error(&path, err.to_string()))?;
+
+        task::spawn_blocking(move || {
+            let temp_path: PathBuf = path.with_extension("json.tmp");
+
+            fs::write(&temp_path, contents)
+                .and_then(|_| fs::rename(&temp_path, &path))
+                .map_err(|err| file_error(&path, err.to_string()))
+        })
+        .await
+        .map_err(|err| Error {
+            kind: ErrorKind::Permanent,
+            message: format!("Failed to write container metadata: {}", err),
+        })?
+    }
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Error for a metadata file which can't be read or written.
+fn file_error(path: &Path, message: String) -> Error {
+    Error {
+        kind: ErrorKind::Permanent,
+        message: format!("Invalid container metadata {}: {}", path.display(), message),
+    }
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs, path::PathBuf};
+
+    use crate::types::{ContainerId, ImageId};
+
+    use super::{ContainerMetadata, MetadataStore};
+
+    fn metadata() -> ContainerMetadata {
+        ContainerMetadata {
+            title: Some("My Family".to_string()),
+            hero_image_id: Some(ImageId(2)),
+            display_order: Some(1),
+            ..ContainerMetadata::default()
+        }
+    }
+
+    #[test]
+    fn deserialize_partial_metadata() {
+        // Given
+        let json: &str = r#"{"featured": true, "heroImageId": 3}"#;
+
+        // When
+        let actual: ContainerMetadata = serde_json::from_str(json).unwrap();
+
+        // Then
+        assert!(actual.featured);
+        assert_eq!(actual.hero_image_id, Some(ImageId(3)));
+        assert_eq!(actual.title, None);
+    }
+
+    #[tokio::test]
+    async fn delete_metadata() {
+        // Given
+        let store: MetadataStore = MetadataStore::default();
+        store.put(ContainerId(0), metadata()).await.unwrap();
+
+        // When
+        let deleted: bool = store.delete(ContainerId(0)).await.unwrap();
+        let deleted_again: bool = store.delete(ContainerId(0)).await.unwrap();
+
+        // Then
+        assert!(deleted);
+        assert!(!deleted_again);
+        assert_eq!(store.get(ContainerId(0)), None);
+    }
+
+    #[tokio::test]
+    async fn reopen_file_backed_store() {
+        // Given
+        let path: PathBuf = env::temp_dir().join(format!("metadata-{}.json", std::process::id()));
+        let store: MetadataStore = MetadataStore::open(&path).unwrap();
+        store.put(ContainerId(4), metadata()).await.unwrap();
+
+        // When
+        let reopened: MetadataStore = MetadataStore::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert_eq!(reopened.get(ContainerId(4)), Some(metadata()));
+    }
+}
@@ -0,0 +1,560 @@
+//! Local mirror of upstream catalog data.
+//!
+//! [`AdvertisementService`][1], [`ImageService`][2], and [`VideoService`][3] write everything they
+//! fetch from Rocket Container's dependencies through to a [`Mirror`], and read from the mirror
+//! when a dependency fails, so Rocket Container keeps serving the last known catalog while its
+//! dependencies are down. The mirror can also be the only data source, e.g. to start without any
+//! dependencies at all.
+//!
+//! Responses which were built from mirrored data have an `X-Mirror-Age` header with the age of the
+//! oldest mirrored data in seconds, added by the [`mirror_age_header`] fairing. Mirror use is
+//! recorded per request in a [`MirrorUsage`], for routes wrapped with [`track_usage`].
+//!
+//! [1]: crate::service::advertisement::AdvertisementService
+//! [2]: crate::service::image::ImageService
+//! [3]: crate::service::video::VideoService
+
+use std::{
+    future::Future,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use log::{debug, warn};
+use rocket::{
+    fairing::AdHoc,
+    http::Header,
+    route::{self, Handler},
+    Data, Request, Route,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::task;
+
+use crate::types::{Error, ErrorKind, Result};
+
+/// Response header with the age of mirrored data in seconds.
+pub const MIRROR_AGE_HEADER: &str = "X-Mirror-Age";
+
+/// Schema of the mirror database.
+const SCHEMA: &str = "CREATE TABLE IF NOT EXISTS mirror (
+    key TEXT PRIMARY KEY,
+    fetched_at INTEGER NOT NULL,
+    data TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS mirror_fetched_at ON mirror (fetched_at);";
+
+tokio::task_local! {
+    /// Mirror use of the request being handled.
+    static USAGE: MirrorUsage;
+}
+
+/* ******************************************* Mirror ******************************************* */
+
+/// Local mirror of data fetched from Rocket Container's dependencies.
+///
+/// Each result is kept as JSON under its key in an SQLite database. Results which aren't fetched
+/// again within the mirror's maximum age are evicted. The default mirror has no database, and
+/// passes every fetch through.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::{env, path::PathBuf};
+///
+/// use rocket_container::service::mirror::Mirror;
+///
+/// let path: PathBuf = env::temp_dir().join("mirror.sqlite3");
+/// let mirror: Mirror = Mirror::open(&path).unwrap().offline(true);
+///
+/// assert!(mirror.is_offline());
+/// ```
+#[derive(Debug, Default)]
+pub struct Mirror {
+    /// Database mirrored data is kept in, if any.
+    db: Option<Arc<Mutex<Connection>>>,
+    /// How long data which isn't fetched again is kept, or [None] to keep it forever.
+    max_age: Option<Duration>,
+    /// Whether to read from the mirror only, without calling dependencies.
+    offline: bool,
+}
+
+impl Mirror {
+    /// Open a [`Mirror`] which keeps data in the SQLite database at `path`, creating it if it
+    /// doesn't exist.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(mirror)` if the database was opened.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if the database can't be opened or created.
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection: Connection = Connection::open(path)
+            .and_then(|connection| connection.execute_batch(SCHEMA).map(|_| connection))
+            .map_err(|err| Error {
+                kind: ErrorKind::Permanent,
+                message: format!("Failed to open mirror {}: {}", path.display(), err),
+            })?;
+
+        Ok(Mirror {
+            db: Some(Arc::new(Mutex::new(connection))),
+            max_age: None,
+            offline: false,
+        })
+    }
+
+    /// Set how long data which isn't fetched again is kept, or [None] to keep it forever.
+    pub fn max_age(mut self, max_age: Option<Duration>) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Set whether to read from the mirror only, without calling dependencies.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Get whether the mirror is read without calling dependencies.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Fetch data from a dependency, through the mirror.
+    ///
+    /// Data is written to the mirror under `key` if `fetch` succeeds. If `fetch` fails, or the
+    /// mirror is offline, data is read from the mirror instead, and recorded in the current
+    /// [`MirrorUsage`].
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(data)` if `fetch` succeeded, or the mirror has data for `key`.
+    /// - `Err(`[`ErrorKind::NotFound`]`)` if `fetch` reports the data doesn't exist. Data for
+    ///   `key` is removed from the mirror, so deleted data isn't served.
+    /// - `Err(error)` with the error from `fetch` if the mirror has no data for `key`.
+    /// - `Err(`[`ErrorKind::Transient`]`)` if the mirror is offline and has no data for `key`.
+    pub async fn fetch<T, F>(&self, key: &str, fetch: F) -> Result<T>
+    where
+        T: DeserializeOwned + Serialize,
+        F: Future<Output = Result<T>>,
+    {
+        let db: &Arc<Mutex<Connection>> = match &self.db {
+            Some(db) => db,
+            None => return fetch.await,
+        };
+
+        if self.offline {
+            return read(db, key).await?.ok_or_else(|| Error {
+                kind: ErrorKind::Transient,
+                message: format!("No mirrored data for {}", key),
+            });
+        }
+
+        match fetch.await {
+            Ok(data) => {
+                self.write(db, key, &data).await;
+
+                Ok(data)
+            }
+            Err(error) if error.kind == ErrorKind::NotFound => {
+                remove(db, key).await;
+
+                Err(error)
+            }
+            Err(error) => match read(db, key).await? {
+                Some(data) => {
+                    warn!("Serving mirrored data for {} after error {}", key, error);
+
+                    Ok(data)
+                }
+                None => Err(error),
+            },
+        }
+    }
+
+    /* ****************************** Private utility function ****************************** */
+
+    /// Write data to the mirror, and evict data older than the mirror's maximum age.
+    ///
+    /// Failing to write is logged rather than failing the request which fetched the data.
+    async fn write<T: Serialize>(&self, db: &Arc<Mutex<Connection>>, key: &str, data: &T) {
+        let contents: String = match serde_json::to_string(data) {
+            Ok(contents) => contents,
+            Err(err) => return warn!("Failed to mirror {} with error {}", key, err),
+        };
+        let fetched_at: i64 = Utc::now().timestamp_millis();
+        let evict_before: Option<i64> = self
+            .max_age
+            .and_then(|max_age| chrono::Duration::from_std(max_age).ok())
+            .map(|max_age| fetched_at.saturating_sub(max_age.num_milliseconds()));
+        let owned_key: String = key.to_string();
+        let result: Result<()> = with_db(db, move |connection| {
+            connection.execute(
+                "INSERT OR REPLACE INTO mirror (key, fetched_at, data) VALUES (?1, ?2, ?3)",
+                params![owned_key, fetched_at, contents],
+            )?;
+
+            if let Some(evict_before) = evict_before {
+                connection.execute("DELETE FROM mirror WHERE fetched_at < ?1", [evict_before])?;
+            }
+
+            Ok(())
+        })
+        .await;
+
+        match result {
+            Ok(()) => debug!("Mirrored {}", key),
+            Err(err) => warn!("Failed to mirror {} with error {}", key, err),
+        }
+    }
+}
+
+/* ***************************************** MirrorUsage **************************************** */
+
+/// Mirrored data served while handling a request.
+///
+/// Each request has its own usage in its local cache. [`Mirror::fetch`] records data it serves
+/// from the mirror in the usage of the current [`MirrorUsage::scope`], if any.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeDelta, Utc};
+/// use rocket_container::service::mirror::MirrorUsage;
+///
+/// async fn record() {
+///     let usage: MirrorUsage = MirrorUsage::default();
+///     let fetched_at = Utc::now() - TimeDelta::seconds(30);
+///
+///     usage.scope(async { MirrorUsage::record(fetched_at) }).await;
+///
+///     assert_eq!(usage.oldest(), Some(fetched_at));
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MirrorUsage {
+    /// When the oldest mirrored data which was served was fetched, if any was served.
+    oldest: Arc<Mutex<Option<DateTime<Utc>>>>,
+}
+
+impl MirrorUsage {
+    /// Get the usage of a request.
+    pub fn of<'r>(request: &'r Request<'_>) -> &'r MirrorUsage {
+        request.local_cache(MirrorUsage::default)
+    }
+
+    /// Record that data fetched at `fetched_at` was served from the mirror, in the usage of the
+    /// current scope. Does nothing outside of a scope.
+    pub fn record(fetched_at: DateTime<Utc>) {
+        let _ = USAGE.try_with(|usage| {
+            let mut oldest = usage.oldest.lock().unwrap();
+
+            *oldest = Some(oldest.map_or(fetched_at, |oldest| oldest.min(fetched_at)));
+        });
+    }
+
+    /// Run `future` with mirror use recorded in this usage.
+    pub async fn scope<F: Future>(&self, future: F) -> F::Output {
+        USAGE.scope(self.clone(), future).await
+    }
+
+    /// Get when the oldest mirrored data which was served was fetched, or [None] if no mirrored
+    /// data was served.
+    pub fn oldest(&self) -> Option<DateTime<Utc>> {
+        *self.oldest.lock().unwrap()
+    }
+
+    /// Get the age at `now` of the oldest mirrored data which was served, or [None] if no mirrored
+    /// data was served.
+    pub fn age(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.oldest()
+            .map(|fetched_at| (now - fetched_at).to_std().unwrap_or_default())
+    }
+}
+
+/* ************************************** TrackMirrorUsage ************************************** */
+
+/// Route handler which handles each request in the scope of the request's [`MirrorUsage`].
+#[derive(Clone)]
+struct TrackMirrorUsage(Box<dyn Handler>);
+
+#[rocket::async_trait]
+impl Handler for TrackMirrorUsage {
+    async fn handle<'r>(&self, request: &'r Request<'_>, data: Data<'r>) -> route::Outcome<'r> {
+        MirrorUsage::of(request)
+            .scope(self.0.handle(request, data))
+            .await
+    }
+}
+
+/// Record the mirror use of each request to `routes` in the request's [`MirrorUsage`].
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::service::mirror::track_usage;
+///
+/// #[get("/")]
+/// fn index() -> &'static str {
+///     "Hello, world!"
+/// }
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build().mount("/", track_usage(routes![index]))
+/// }
+/// ```
+pub fn track_usage(routes: Vec<Route>) -> Vec<Route> {
+    routes
+        .into_iter()
+        .map(|mut route| {
+            route.handler = Box::new(TrackMirrorUsage(route.handler));
+            route
+        })
+        .collect()
+}
+
+/* ****************************************** Fairing ******************************************* */
+
+/// Fairing which adds an [`MIRROR_AGE_HEADER`] header to responses which were built from mirrored
+/// data.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::service::mirror::mirror_age_header;
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build().attach(mirror_age_header())
+/// }
+/// ```
+pub fn mirror_age_header() -> AdHoc {
+    AdHoc::on_response("Mirror age header", |request, response| {
+        let age: Option<Duration> = MirrorUsage::of(request).age(Utc::now());
+
+        Box::pin(async move {
+            if let Some(age) = age {
+                response.set_header(Header::new(MIRROR_AGE_HEADER, age.as_secs().to_string()));
+            }
+        })
+    })
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Read data for `key` from the mirror, and record it in the current [`MirrorUsage`].
+async fn read<T: DeserializeOwned>(db: &Arc<Mutex<Connection>>, key: &str) -> Result<Option<T>> {
+    let owned_key: String = key.to_string();
+    let row: Option<(i64, String)> = with_db(db, move |connection| {
+        connection
+            .query_row(
+                "SELECT fetched_at, data FROM mirror WHERE key = ?1",
+                [owned_key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+    })
+    .await?;
+    let (fetched_at, contents): (i64, String) = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+    let data: T = serde_json::from_str(&contents).map_err(|err| Error {
+        kind: ErrorKind::Permanent,
+        message: format!("Invalid mirrored data {}: {}", key, err),
+    })?;
+
+    if let Some(fetched_at) = DateTime::from_timestamp_millis(fetched_at) {
+        MirrorUsage::record(fetched_at);
+    }
+
+    Ok(Some(data))
+}
+
+/// Remove data for `key` from the mirror.
+///
+/// Failing to remove is logged rather than failing the request.
+async fn remove(db: &Arc<Mutex<Connection>>, key: &str) {
+    let owned_key: String = key.to_string();
+    let result: Result<usize> = with_db(db, move |connection| {
+        connection.execute("DELETE FROM mirror WHERE key = ?1", [owned_key])
+    })
+    .await;
+
+    match result {
+        Ok(0) => (),
+        Ok(_) => debug!("Removed {} from the mirror", key),
+        Err(err) => warn!(
+            "Failed to remove {} from the mirror with error {}",
+            key, err
+        ),
+    }
+}
+
+/// Run `op` with the mirror's database connection on the blocking thread pool.
+async fn with_db<R, F>(db: &Arc<Mutex<Connection>>, op: F) -> Result<R>
+where
+    R: Send + 'static,
+    F: FnOnce(&Connection) -> rusqlite::Result<R> + Send + 'static,
+{
+    let db: Arc<Mutex<Connection>> = db.clone();
+
+    task::spawn_blocking(move || op(&db.lock().unwrap()))
+        .await
+        .map_err(|err| Error {
+            kind: ErrorKind::Permanent,
+            message: format!("Mirror task failed: {}", err),
+        })?
+        .map_err(|err| Error {
+            kind: ErrorKind::Permanent,
+            message: format!("Mirror database error: {}", err),
+        })
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs, path::PathBuf, time::Duration};
+
+    use crate::types::{Error, ErrorKind, Result};
+
+    use super::{Mirror, MirrorUsage};
+
+    fn path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("mirror-{}-{}.sqlite3", name, std::process::id()))
+    }
+
+    fn unavailable() -> Error {
+        Error {
+            kind: ErrorKind::Transient,
+            message: "Service unavailable".to_string(),
+        }
+    }
+
+    fn not_found() -> Error {
+        Error {
+            kind: ErrorKind::NotFound,
+            message: "Resource not found".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_falls_back_to_mirror() {
+        // Given
+        let path: PathBuf = path("fallback");
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+        let usage: MirrorUsage = MirrorUsage::default();
+        mirror.fetch("ids", async { Ok(vec![1, 2]) }).await.unwrap();
+
+        // When
+        let result: Result<Vec<u32>> = usage
+            .scope(mirror.fetch("ids", async { Err(unavailable()) }))
+            .await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert_eq!(result.unwrap(), vec![1, 2]);
+        assert!(usage.oldest().is_some());
+    }
+
+    #[tokio::test]
+    async fn fetch_from_dependency_is_not_mirror_use() {
+        // Given
+        let path: PathBuf = path("live");
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+        let usage: MirrorUsage = MirrorUsage::default();
+        mirror.fetch("ids", async { Ok(vec![1]) }).await.unwrap();
+        let _: Result<Vec<u32>> = mirror.fetch("ids", async { Err(unavailable()) }).await;
+
+        // When
+        usage
+            .scope(mirror.fetch("ids", async { Ok(vec![1]) }))
+            .await
+            .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(usage.oldest().is_none());
+    }
+
+    #[tokio::test]
+    async fn fetch_not_found_removes_mirrored_data() {
+        // Given
+        let path: PathBuf = path("deleted");
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+        mirror.fetch("ids", async { Ok(vec![1]) }).await.unwrap();
+        let _: Result<Vec<u32>> = mirror.fetch("ids", async { Err(not_found()) }).await;
+
+        // When
+        let result: Result<Vec<u32>> = mirror.fetch("ids", async { Err(unavailable()) }).await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(result.is_err_and(|err| err.kind == ErrorKind::Transient));
+    }
+
+    #[tokio::test]
+    async fn fetch_evicts_data_older_than_max_age() {
+        // Given
+        let path: PathBuf = path("evict");
+        let mirror: Mirror = Mirror::open(&path)
+            .unwrap()
+            .max_age(Some(Duration::from_millis(1)));
+        mirror.fetch("old", async { Ok(vec![1]) }).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        mirror.fetch("new", async { Ok(vec![2]) }).await.unwrap();
+
+        // When
+        let result: Result<Vec<u32>> = mirror.fetch("old", async { Err(unavailable()) }).await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(result.is_err_and(|err| err.kind == ErrorKind::Transient));
+    }
+
+    #[tokio::test]
+    async fn fetch_offline_without_mirrored_data() {
+        // Given
+        let path: PathBuf = path("offline");
+        let mirror: Mirror = Mirror::open(&path).unwrap().offline(true);
+
+        // When
+        let result: Result<Vec<u32>> = mirror
+            .fetch("ids", async { panic!("Dependency called while offline") })
+            .await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(result.is_err_and(|err| err.kind == ErrorKind::Transient));
+    }
+
+    #[tokio::test]
+    async fn usage_records_oldest() {
+        // Given
+        let usage: MirrorUsage = MirrorUsage::default();
+        let now = chrono::Utc::now();
+        let older = now - chrono::TimeDelta::seconds(60);
+
+        // When
+        usage
+            .scope(async {
+                MirrorUsage::record(now);
+                MirrorUsage::record(older);
+            })
+            .await;
+        MirrorUsage::record(now - chrono::TimeDelta::seconds(120));
+
+        // Then
+        assert_eq!(usage.oldest(), Some(older));
+        assert_eq!(usage.age(now), Some(Duration::from_secs(60)));
+    }
+}
@@ -2,12 +2,31 @@
 
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::time::Duration;
 
 pub mod advertisement;
+pub mod audit;
+pub mod catalog;
 pub mod container;
+pub mod export;
 pub mod image;
+pub mod metadata;
+pub mod mirror;
+pub mod report;
+pub mod snapshot;
+pub mod title;
 pub mod video;
 
+/// Maximum number of items kept in each by-ID cache.
+const CACHE_CAPACITY: usize = 1_024;
+
+/// How long an item from a listing is served by ID without listing again, so changes upstream are
+/// picked up.
+const CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// How long an ID which was absent from a listing is reported missing without listing again.
+const ABSENT_TTL: Duration = Duration::from_secs(60);
+
 /// Group an [Iterator] of tuples of `(K, V)` into a [`HashMap`].
 fn group<I, K, V>(iter: I) -> HashMap<K, Vec<V>>
 where
@@ -0,0 +1,208 @@
+//! Editorial reports about the catalog.
+
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::{
+    service::video::Video,
+    types::{ContainerId, Timestamp, VideoId, VideoType},
+};
+
+/// Header row of [`to_csv`].
+const CSV_HEADER: &str = "containerId,videoId,title,type,expiresAt";
+
+/* *************************************** ExpiringVideo **************************************** */
+
+/// A video which is about to expire.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::{TimeDelta, Utc};
+/// use rocket_container::{
+///     service::{report::ExpiringVideo, video::Video},
+///     types::{ContainerId, VideoId, VideoType},
+/// };
+///
+/// let video: Video = Video::builder(VideoId(1))
+///     .description(String::new())
+///     .expires_at(Some(Utc::now() + TimeDelta::days(1)))
+///     .playback_url("/path/to/test1.m3u8".to_string())
+///     .title("My Family".to_string())
+///     .r#type(VideoType::Clip)
+///     .build();
+/// let expiring: Option<ExpiringVideo> = ExpiringVideo::from(ContainerId(0), &video);
+///
+/// assert!(expiring.is_some());
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiringVideo {
+    /// ID of the container which the video belongs to.
+    container_id: ContainerId,
+    /// Unique video identifier.
+    video_id: VideoId,
+    /// Video title.
+    title: String,
+    /// Type of video.
+    r#type: VideoType,
+    /// When the video expires.
+    #[serde(serialize_with = "serialize_expires_at")]
+    expires_at: DateTime<Utc>,
+}
+
+impl ExpiringVideo {
+    /// Create an [`ExpiringVideo`] from a video in a container.
+    ///
+    /// Returns [None] if the video never expires.
+    pub fn from(container_id: ContainerId, video: &Video) -> Option<Self> {
+        video.expires_at().map(|expires_at| ExpiringVideo {
+            container_id,
+            video_id: video.id(),
+            title: video.title().to_string(),
+            r#type: video.r#type().clone(),
+            expires_at,
+        })
+    }
+
+    /// Get container ID.
+    pub fn container_id(&self) -> ContainerId {
+        self.container_id
+    }
+
+    /// Get when the video expires.
+    pub fn expires_at(&self) -> DateTime<Utc> {
+        self.expires_at
+    }
+
+    /// Get video ID.
+    pub fn video_id(&self) -> VideoId {
+        self.video_id
+    }
+}
+
+impl Display for ExpiringVideo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ExpiringVideo {{ container_id: {}, video_id: {}, title: {}, type: {}, \
+            expires_at: {} }}",
+            self.container_id,
+            self.video_id,
+            self.title,
+            self.r#type,
+            Timestamp(self.expires_at)
+        )
+    }
+}
+
+/* ************************************** Utility function ************************************** */
+
+/// Format expiring videos as CSV, with a header row.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::service::report::to_csv;
+///
+/// assert_eq!(to_csv(&[]), "containerId,videoId,title,type,expiresAt\r\n");
+/// ```
+pub fn to_csv(videos: &[ExpiringVideo]) -> String {
+    let mut csv: String = format!("{}\r\n", CSV_HEADER);
+
+    for video in videos {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\r\n",
+            video.container_id,
+            video.video_id,
+            csv_field(&video.title),
+            video.r#type,
+            Timestamp(video.expires_at)
+        ));
+    }
+
+    csv
+}
+
+/// Serialize when a video expires as a [Timestamp].
+fn serialize_expires_at<S>(
+    expires_at: &DateTime<Utc>,
+    serializer: S,
+) -> core::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(&Timestamp(*expires_at))
+}
+
+/// Quote a CSV field if it contains a delimiter, quote, or line break.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::service::report::csv_field;
+///
+/// assert_eq!(csv_field("Say \"Hi\", Bob"), "\"Say \"\"Hi\"\", Bob\"");
+/// ```
+pub fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\r', '\n']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        service::video::Video,
+        types::{parse_timestamp, ContainerId, VideoId, VideoType},
+    };
+
+    use super::{to_csv, ExpiringVideo};
+
+    fn video(id: u32, title: &str, expires_at: &str) -> Video {
+        Video::builder(VideoId(id))
+            .description(String::new())
+            .expires_at(parse_timestamp(expires_at))
+            .playback_url(format!("/path/to/test{}.m3u8", id))
+            .title(title.to_string())
+            .r#type(VideoType::Movie)
+            .build()
+    }
+
+    #[test]
+    fn expiring_video_never_expires() {
+        // Given
+        let video: Video = video(1, "Alpha", "");
+
+        // When
+        let actual: Option<ExpiringVideo> = ExpiringVideo::from(ContainerId(0), &video);
+
+        // Then
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn expiring_videos_to_csv() {
+        // Given
+        let videos: Vec<ExpiringVideo> = vec![
+            ExpiringVideo::from(ContainerId(0), &video(1, "Alpha", "2022-03-23")).unwrap(),
+            ExpiringVideo::from(ContainerId(2), &video(3, "Say \"Hi\", Bob", "2022-03-24"))
+                .unwrap(),
+        ];
+        let expected: &str = "containerId,videoId,title,type,expiresAt\r\n\
+            0,1,Alpha,MOVIE,2022-03-23T00:00:00Z\r\n\
+            2,3,\"Say \"\"Hi\"\", Bob\",MOVIE,2022-03-24T00:00:00Z\r\n";
+
+        // When
+        let actual: String = to_csv(&videos);
+
+        // Then
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,323 @@
+//! Cached snapshot of every container.
+//!
+//! Listing containers requires listing every advertisement, image, and video from Rocket
+//! Container's dependencies, so [`ContainerService`] keeps the result in a [`SnapshotCache`] and
+//! serves listings from it until it is older than its maximum age. Snapshots only hold videos
+//! which haven't expired: a background sweeper evicts each video from the snapshot as soon as it
+//! expires.
+
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use log::{debug, info};
+use rocket::fairing::AdHoc;
+use tokio::{sync::Notify, time};
+
+use crate::service::{
+    container::{Container, ContainerService},
+    mirror::MirrorUsage,
+};
+
+/// Default maximum age of a snapshot.
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(300);
+
+/* ***************************************** Snapshot ******************************************* */
+
+/// Containers fetched from Rocket Container's dependencies at a point in time.
+struct Snapshot {
+    /// Every container.
+    containers: Vec<Container>,
+    /// When the containers were fetched.
+    created_at: DateTime<Utc>,
+    /// When the oldest data served from the local mirror for the containers was fetched, if any.
+    mirrored_at: Option<DateTime<Utc>>,
+}
+
+/* *************************************** SnapshotCache **************************************** */
+
+/// Cache for a [`Snapshot`] of every container.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use chrono::Utc;
+/// use rocket_container::service::snapshot::SnapshotCache;
+///
+/// let cache: SnapshotCache = SnapshotCache::new(Duration::from_secs(60));
+///
+/// cache.set(Vec::new(), Utc::now());
+///
+/// assert_eq!(cache.get(Utc::now()), Some(Vec::new()));
+/// ```
+pub struct SnapshotCache {
+    /// Maximum age of a snapshot before it has to be fetched again.
+    max_age: Duration,
+    /// Notified when the snapshot is replaced, so the sweeper can reschedule.
+    changed: Notify,
+    /// Current snapshot.
+    snapshot: RwLock<Option<Snapshot>>,
+}
+
+impl Default for SnapshotCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_AGE)
+    }
+}
+
+impl SnapshotCache {
+    /// Create a new, empty, [`SnapshotCache`].
+    pub fn new(max_age: Duration) -> Self {
+        SnapshotCache {
+            max_age,
+            changed: Notify::new(),
+            snapshot: RwLock::new(None),
+        }
+    }
+
+    /// Get the cached containers, or [None] if there is no snapshot or it is older than the
+    /// maximum age at `now`.
+    ///
+    /// If the snapshot was built from mirrored data, that is recorded in the current
+    /// [`MirrorUsage`].
+    pub fn get(&self, now: DateTime<Utc>) -> Option<Vec<Container>> {
+        let snapshot = self.snapshot.read().unwrap();
+
+        snapshot
+            .as_ref()
+            .filter(|snapshot| {
+                (now - snapshot.created_at).to_std().unwrap_or_default() <= self.max_age
+            })
+            .map(|snapshot| {
+                if let Some(mirrored_at) = snapshot.mirrored_at {
+                    MirrorUsage::record(mirrored_at);
+                }
+
+                snapshot.containers.clone()
+            })
+    }
+
+    /// Replace the snapshot with containers fetched at `now`.
+    ///
+    /// Videos which have expired at `now` are not cached.
+    pub fn set(&self, containers: Vec<Container>, now: DateTime<Utc>) {
+        self.set_mirrored(containers, now, None);
+    }
+
+    /// Replace the snapshot with containers fetched at `now`, built from data served from the
+    /// local mirror which was fetched at `mirrored_at`, if any.
+    ///
+    /// Videos which have expired at `now` are not cached.
+    pub fn set_mirrored(
+        &self,
+        mut containers: Vec<Container>,
+        now: DateTime<Utc>,
+        mirrored_at: Option<DateTime<Utc>>,
+    ) {
+        for container in containers.iter_mut() {
+            container.remove_expired_videos(now);
+        }
+
+        *self.snapshot.write().unwrap() = Some(Snapshot {
+            containers,
+            created_at: now,
+            mirrored_at,
+        });
+
+        self.changed.notify_one();
+    }
+
+    /// Evict videos which have expired at `now` from the snapshot.
+    ///
+    /// Returns when the next video in the snapshot expires, or [None] if no video expires.
+    pub fn sweep(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut snapshot = self.snapshot.write().unwrap();
+        let containers: &mut Vec<Container> = match snapshot.as_mut() {
+            Some(snapshot) => &mut snapshot.containers,
+            None => return None,
+        };
+
+        for container in containers.iter_mut() {
+            let evicted: usize = container.remove_expired_videos(now);
+
+            if evicted > 0 {
+                info!(
+                    "Evicted {} expired videos from container {}",
+                    evicted,
+                    container.id()
+                );
+            }
+        }
+
+        containers
+            .iter()
+            .flat_map(|container| container.videos().iter())
+            .filter_map(|video| video.expires_at())
+            .min()
+    }
+
+    /// Sweep expired videos from the snapshot forever.
+    ///
+    /// Sleeps until the next video expires, or until the snapshot is replaced, then sweeps again.
+    pub async fn run_sweeper(self: Arc<Self>) {
+        loop {
+            let now: DateTime<Utc> = Utc::now();
+
+            match self.sweep(now) {
+                Some(next) => {
+                    let delay: Duration = (next - now).to_std().unwrap_or_default();
+
+                    debug!("Next video expires at {}, sweeping in {:?}", next, delay);
+
+                    tokio::select! {
+                        _ = time::sleep(delay) => (),
+                        _ = self.changed.notified() => (),
+                    }
+                }
+                None => self.changed.notified().await,
+            }
+        }
+    }
+}
+
+/* ****************************************** Fairing ******************************************* */
+
+/// Fairing which starts sweeping expired videos from the managed [`ContainerService`]'s snapshot
+/// on liftoff.
+///
+/// # Examples
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate rocket;
+///
+/// use rocket_container::service::{container::ContainerService, snapshot::expiry_sweeper};
+///
+/// #[launch]
+/// pub fn rocket() -> _ {
+///     rocket::build()
+///         .manage(ContainerService::default())
+///         .attach(expiry_sweeper())
+/// }
+/// ```
+pub fn expiry_sweeper() -> AdHoc {
+    AdHoc::on_liftoff("Expiry sweeper", |rocket| {
+        Box::pin(async move {
+            if let Some(service) = rocket.state::<ContainerService>() {
+                tokio::spawn(service.snapshot().run_sweeper());
+            }
+        })
+    })
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use chrono::{DateTime, TimeDelta, Utc};
+
+    use crate::{
+        service::{container::Container, mirror::MirrorUsage, video::Video},
+        types::{ContainerId, VideoId, VideoType},
+    };
+
+    use super::SnapshotCache;
+
+    fn video(id: u32, expires_at: Option<DateTime<Utc>>) -> Video {
+        Video::builder(VideoId(id))
+            .description(String::new())
+            .expires_at(expires_at)
+            .playback_url(format!("/path/to/test{}.m3u8", id))
+            .title(format!("Video {}", id))
+            .r#type(VideoType::Clip)
+            .build()
+    }
+
+    fn container(now: DateTime<Utc>) -> Container {
+        Container::new(
+            Vec::new(),
+            ContainerId(0),
+            Vec::new(),
+            "container-0".to_string(),
+            vec![
+                video(1, None),
+                video(2, Some(now + TimeDelta::minutes(1))),
+                video(3, Some(now + TimeDelta::hours(1))),
+            ],
+        )
+    }
+
+    fn ids(containers: &[Container]) -> Vec<u32> {
+        containers
+            .iter()
+            .flat_map(|container| container.videos().iter().map(|video| video.id().0))
+            .collect()
+    }
+
+    #[test]
+    fn get_stale_snapshot() {
+        // Given
+        let now: DateTime<Utc> = Utc::now();
+        let cache: SnapshotCache = SnapshotCache::new(Duration::from_secs(60));
+        cache.set(vec![container(now)], now);
+
+        // When
+        let fresh: Option<Vec<Container>> = cache.get(now + TimeDelta::seconds(30));
+        let stale: Option<Vec<Container>> = cache.get(now + TimeDelta::seconds(90));
+
+        // Then
+        assert!(fresh.is_some());
+        assert!(stale.is_none());
+    }
+
+    #[tokio::test]
+    async fn get_records_mirror_usage() {
+        // Given
+        let now: DateTime<Utc> = Utc::now();
+        let mirrored_at: DateTime<Utc> = now - TimeDelta::hours(1);
+        let cache: SnapshotCache = SnapshotCache::default();
+        let usage: MirrorUsage = MirrorUsage::default();
+        cache.set_mirrored(vec![container(now)], now, Some(mirrored_at));
+
+        // When
+        let containers: Option<Vec<Container>> = usage.scope(async { cache.get(now) }).await;
+
+        // Then
+        assert!(containers.is_some());
+        assert_eq!(usage.oldest(), Some(mirrored_at));
+    }
+
+    #[test]
+    fn sweep_expired_videos() {
+        // Given
+        let now: DateTime<Utc> = Utc::now();
+        let cache: SnapshotCache = SnapshotCache::default();
+        cache.set(vec![container(now)], now);
+
+        // When
+        let next: Option<DateTime<Utc>> = cache.sweep(now + TimeDelta::minutes(2));
+
+        // Then
+        assert_eq!(next, Some(now + TimeDelta::hours(1)));
+        assert_eq!(ids(&cache.get(now).unwrap()), vec![1, 3]);
+    }
+
+    #[test]
+    fn set_evicts_expired_videos() {
+        // Given
+        let now: DateTime<Utc> = Utc::now();
+        let cache: SnapshotCache = SnapshotCache::default();
+
+        // When
+        cache.set(vec![container(now)], now + TimeDelta::minutes(2));
+
+        // Then
+        assert_eq!(ids(&cache.get(now).unwrap()), vec![1, 3]);
+    }
+}
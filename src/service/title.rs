@@ -0,0 +1,405 @@
+//! Container title generation.
+//!
+//! Rocket Container's dependencies don't have titles for containers, so [`ContainerService`][1]
+//! generates one for each container with a [`ContainerTitler`].
+//!
+//! # Types
+//!
+//! - [`DefaultTitler`]: Titles such as `container-0_ads_images_videos`.
+//! - [`TemplateTitler`]: Titles rendered from a template configured at startup, such as
+//!   `{id} – {firstVideoTitle}`.
+//! - [`OverrideTitler`]: Editorial titles by container ID, loaded from a JSON or TOML file, with
+//!   another titler for every other container.
+//!
+//! [1]: crate::service::container::ContainerService
+
+use std::{collections::HashMap, fs, path::Path};
+
+use log::info;
+
+use crate::{
+    service::container::Container,
+    types::{ContainerId, Error, ErrorKind, Result},
+};
+
+/* ************************************** ContainerTitler *************************************** */
+
+/// Strategy for generating container titles.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::service::{container::Container, title::ContainerTitler};
+///
+/// struct IdTitler;
+///
+/// impl ContainerTitler for IdTitler {
+///     fn title(&self, container: &Container) -> String {
+///         container.id().to_string()
+///     }
+/// }
+/// ```
+pub trait ContainerTitler: Send + Sync {
+    /// Generate a title for `container`.
+    ///
+    /// Titles are generated once the container's advertisements, images, and videos are sorted
+    /// by ID, and before its videos are filtered.
+    fn title(&self, container: &Container) -> String;
+}
+
+/* *************************************** DefaultTitler **************************************** */
+
+/// Titles containers `container-<id>`, followed by `_ads` if the container has advertisements,
+/// `_images` if it has images, and `_videos`.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     service::{
+///         container::Container,
+///         title::{ContainerTitler, DefaultTitler},
+///     },
+///     types::ContainerId,
+/// };
+///
+/// let container: Container =
+///     Container::new(Vec::new(), ContainerId(0), Vec::new(), String::new(), Vec::new());
+///
+/// assert_eq!(DefaultTitler.title(&container), "container-0_videos");
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DefaultTitler;
+
+impl ContainerTitler for DefaultTitler {
+    fn title(&self, container: &Container) -> String {
+        let title_ads: &str = match container.ads().is_empty() {
+            false => "_ads",
+            true => "",
+        };
+        let title_images: &str = match container.images().is_empty() {
+            false => "_images",
+            true => "",
+        };
+
+        format!(
+            "container-{}{}{}_videos",
+            container.id(),
+            title_ads,
+            title_images
+        )
+    }
+}
+
+/* *************************************** TemplateTitler *************************************** */
+
+/// Placeholder in a [`TemplateTitler`] template.
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Segment {
+    /// Text copied into the title as is.
+    Literal(String),
+    /// `{id}`: Container ID.
+    Id,
+    /// `{firstVideoTitle}`: Title of the video with the lowest ID, or empty.
+    FirstVideoTitle,
+    /// `{adCount}`: Number of advertisements.
+    AdCount,
+    /// `{imageCount}`: Number of images.
+    ImageCount,
+    /// `{videoCount}`: Number of videos.
+    VideoCount,
+}
+
+/// Titles containers by rendering a template.
+///
+/// Templates can contain the placeholders `{id}`, `{firstVideoTitle}`, `{adCount}`,
+/// `{imageCount}`, and `{videoCount}`. `{firstVideoTitle}` is the title of the video with the
+/// lowest ID, or empty if the container has no videos.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     service::{
+///         container::Container,
+///         title::{ContainerTitler, TemplateTitler},
+///     },
+///     types::ContainerId,
+/// };
+///
+/// let titler: TemplateTitler = TemplateTitler::new("Container {id}").unwrap();
+/// let container: Container =
+///     Container::new(Vec::new(), ContainerId(7), Vec::new(), String::new(), Vec::new());
+///
+/// assert_eq!(titler.title(&container), "Container 7");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TemplateTitler {
+    /// Parsed template.
+    segments: Vec<Segment>,
+}
+
+impl TemplateTitler {
+    /// Parse a template.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(titler)` if the template is valid.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if the template has an unknown or unclosed placeholder.
+    pub fn new(template: &str) -> Result<Self> {
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut rest: &str = template;
+
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+
+            let end: usize = rest[start..].find('}').ok_or_else(|| Error {
+                kind: ErrorKind::Permanent,
+                message: format!("Unclosed placeholder in title template {}", template),
+            })? + start;
+
+            segments.push(match &rest[start + 1..end] {
+                "id" => Segment::Id,
+                "firstVideoTitle" => Segment::FirstVideoTitle,
+                "adCount" => Segment::AdCount,
+                "imageCount" => Segment::ImageCount,
+                "videoCount" => Segment::VideoCount,
+                placeholder => {
+                    return Err(Error {
+                        kind: ErrorKind::Permanent,
+                        message: format!("Unknown title template placeholder {{{}}}", placeholder),
+                    })
+                }
+            });
+
+            rest = &rest[end + 1..];
+        }
+
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+
+        Ok(TemplateTitler { segments })
+    }
+}
+
+impl ContainerTitler for TemplateTitler {
+    fn title(&self, container: &Container) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(literal) => literal.clone(),
+                Segment::Id => container.id().to_string(),
+                Segment::FirstVideoTitle => container
+                    .videos()
+                    .first()
+                    .map_or_else(String::new, |video| video.title().to_string()),
+                Segment::AdCount => container.ads().len().to_string(),
+                Segment::ImageCount => container.images().len().to_string(),
+                Segment::VideoCount => container.videos().len().to_string(),
+            })
+            .collect()
+    }
+}
+
+/* *************************************** OverrideTitler *************************************** */
+
+/// Titles containers with editorial overrides by container ID, and every other container with a
+/// fallback titler.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use rocket_container::{
+///     service::{
+///         container::Container,
+///         title::{ContainerTitler, DefaultTitler, OverrideTitler},
+///     },
+///     types::ContainerId,
+/// };
+///
+/// let titler: OverrideTitler = OverrideTitler::new(
+///     HashMap::from([(ContainerId(0), "My Family".to_string())]),
+///     Box::new(DefaultTitler),
+/// );
+/// let container: Container =
+///     Container::new(Vec::new(), ContainerId(0), Vec::new(), String::new(), Vec::new());
+///
+/// assert_eq!(titler.title(&container), "My Family");
+/// ```
+pub struct OverrideTitler {
+    /// Titler for containers without an override.
+    fallback: Box<dyn ContainerTitler>,
+    /// Titles by container ID.
+    overrides: HashMap<ContainerId, String>,
+}
+
+impl OverrideTitler {
+    /// Create a new [`OverrideTitler`].
+    pub fn new(
+        overrides: HashMap<ContainerId, String>,
+        fallback: Box<dyn ContainerTitler>,
+    ) -> Self {
+        OverrideTitler {
+            fallback,
+            overrides,
+        }
+    }
+
+    /// Load overrides from a JSON or TOML file, depending on its extension.
+    ///
+    /// The file is a map of container ID to title, e.g. `{"0": "My Family"}` or `0 = "My Family"`.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(titler)` if the file was loaded.
+    /// - `Err(`[`ErrorKind::Permanent`]`)` if the file can't be read or parsed, or a key isn't a
+    ///   container ID.
+    pub fn from_file(path: &Path, fallback: Box<dyn ContainerTitler>) -> Result<Self> {
+        let error = |message: String| Error {
+            kind: ErrorKind::Permanent,
+            message: format!("Invalid title overrides {}: {}", path.display(), message),
+        };
+
+        let contents: String = fs::read_to_string(path).map_err(|err| error(err.to_string()))?;
+        let titles: HashMap<String, String> = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => {
+                serde_json::from_str(&contents).map_err(|err| error(err.to_string()))?
+            }
+            Some("toml") => toml::from_str(&contents).map_err(|err| error(err.to_string()))?,
+            _ => return Err(error("expected a .json or .toml file".to_string())),
+        };
+
+        let overrides: HashMap<ContainerId, String> = titles
+            .into_iter()
+            .map(|(container_id, title)| Ok((container_id.parse()?, title)))
+            .collect::<Result<_>>()
+            .map_err(|err: Error| error(err.message))?;
+
+        info!(
+            "Loaded {} container title overrides from {}",
+            overrides.len(),
+            path.display()
+        );
+
+        Ok(OverrideTitler::new(overrides, fallback))
+    }
+}
+
+impl ContainerTitler for OverrideTitler {
+    fn title(&self, container: &Container) -> String {
+        match self.overrides.get(&container.id()) {
+            Some(title) => title.clone(),
+            None => self.fallback.title(container),
+        }
+    }
+}
+
+/* ******************************************* Tests ******************************************** */
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, env, fs, path::PathBuf};
+
+    use crate::{
+        service::{advertisement::Advertisement, container::Container, video::Video},
+        types::{AdvertisementId, ContainerId, VideoId, VideoType},
+    };
+
+    use super::{ContainerTitler, DefaultTitler, OverrideTitler, TemplateTitler};
+
+    fn container() -> Container {
+        Container::new(
+            vec![Advertisement::new(
+                AdvertisementId(0),
+                "Ad".to_string(),
+                "/ad".to_string(),
+            )],
+            ContainerId(3),
+            Vec::new(),
+            String::new(),
+            vec![Video::builder(VideoId(1))
+                .description(String::new())
+                .playback_url("/path/to/test1.m3u8".to_string())
+                .title("My Family".to_string())
+                .r#type(VideoType::Episode)
+                .build()],
+        )
+    }
+
+    #[test]
+    fn default_title() {
+        // Given
+        let container: Container = container();
+
+        // When
+        let actual: String = DefaultTitler.title(&container);
+
+        // Then
+        assert_eq!(actual, "container-3_ads_videos");
+    }
+
+    #[test]
+    fn template_title() {
+        // Given
+        let titler: TemplateTitler =
+            TemplateTitler::new("{id} – {firstVideoTitle} ({videoCount})").unwrap();
+
+        // When
+        let actual: String = titler.title(&container());
+
+        // Then
+        assert_eq!(actual, "3 – My Family (1)");
+    }
+
+    #[test]
+    fn template_unknown_placeholder() {
+        // Given
+        let templates: [&str; 2] = ["{name}", "{id"];
+
+        // When
+        let actual: Vec<bool> = templates
+            .iter()
+            .map(|template| TemplateTitler::new(template).is_err())
+            .collect();
+
+        // Then
+        assert_eq!(actual, vec![true, true]);
+    }
+
+    #[test]
+    fn override_title() {
+        // Given
+        let titler: OverrideTitler = OverrideTitler::new(
+            HashMap::from([(ContainerId(0), "Featured".to_string())]),
+            Box::new(DefaultTitler),
+        );
+
+        // When
+        let actual: String = titler.title(&container());
+
+        // Then
+        assert_eq!(actual, "container-3_ads_videos");
+    }
+
+    #[test]
+    fn override_titles_from_file() {
+        // Given
+        let path: PathBuf = env::temp_dir().join(format!("titles-{}.toml", std::process::id()));
+        fs::write(&path, "3 = \"Featured\"\n").unwrap();
+
+        // When
+        let result = OverrideTitler::from_file(&path, Box::new(DefaultTitler));
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        match result {
+            Ok(titler) => assert_eq!(titler.title(&container()), "Featured"),
+            Err(err) => panic!("Failed to load title overrides with error: {}", err),
+        }
+    }
+}
@@ -2,17 +2,27 @@
 
 extern crate futures;
 
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{Display, Formatter};
+use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use futures::future;
-use log::trace;
+use log::{debug, trace, warn};
+use rocket::FromFormField;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    repository::video::{VideoDto, VideoRepository},
-    service::group,
-    types::{array_to_string, option_to_string, AssetType, Result, VideoType},
+    config::UnknownTypePolicy,
+    repository::video::{AssetReferenceDto, VideoDto, VideoRepository},
+    service::{advertisement::Advertisement, group, image::Image, mirror::Mirror},
+    types::{
+        array_to_string, deserialize_timestamp, option_to_string, serialize_timestamp,
+        AdvertisementId, AssetId, AssetType, ContainerId, Error, ErrorKind, ImageId, Result,
+        SortOrder, Timestamp, VideoId, VideoType,
+    },
 };
 
 /* *************************************** AssetReference *************************************** */
@@ -26,13 +36,18 @@ use crate::{
 ///
 /// [1]: [crate::repository::types::video::AssetReferenceDto]
 /// [2]: [crate::repository::video]
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetReference {
     /// Unique identifier for referenced asset.
-    asset_id: u32,
+    asset_id: AssetId,
     /// Type of asset.
     asset_type: AssetType,
+    /// If the referenced asset doesn't exist.
+    ///
+    /// Only set when asset references are expanded, and omitted from JSON unless `true`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    dangling: bool,
 }
 
 impl AssetReference {
@@ -42,24 +57,170 @@ impl AssetReference {
     ///
     /// ```rust
     /// ```
-    pub fn new(asset_id: u32, asset_type: AssetType) -> Self {
+    pub fn new(asset_id: AssetId, asset_type: AssetType) -> Self {
         AssetReference {
             asset_id,
             asset_type,
+            dangling: false,
         }
     }
+
+    /// Get referenced asset ID.
+    pub fn asset_id(&self) -> AssetId {
+        self.asset_id
+    }
+
+    /// Get type of asset.
+    pub fn asset_type(&self) -> AssetType {
+        self.asset_type.clone()
+    }
+
+    /// Check if the referenced asset doesn't exist.
+    pub fn is_dangling(&self) -> bool {
+        self.dangling
+    }
 }
 
 impl Display for AssetReference {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "AssetReference {{ asset_id: {}, asset_type: {} }}",
-            self.asset_id, self.asset_type
+            "AssetReference {{ asset_id: {}, asset_type: {}, dangling: {} }}",
+            self.asset_id, self.asset_type, self.dangling
         )
     }
 }
 
+/* *************************************** ResolvedAsset **************************************** */
+
+/// Advertisement or image which an [`AssetReference`] points at.
+///
+/// Serialized as the advertisement or image with an additional `assetType` field.
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(tag = "assetType", rename_all = "UPPERCASE")]
+pub enum ResolvedAsset {
+    /// Advertisement asset.
+    Ad(Advertisement),
+    /// Image asset.
+    Image(Image),
+}
+
+impl Display for ResolvedAsset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ResolvedAsset::Ad(advertisement) => write!(f, "{}", advertisement),
+            ResolvedAsset::Image(image) => write!(f, "{}", image),
+        }
+    }
+}
+
+/* ***************************************** VideoAsset ***************************************** */
+
+/// Asset of a [`Video`].
+///
+/// Videos are built with [`AssetReference`]s. Expanding a video's assets with
+/// [`VideoAsset::resolve`] replaces each reference with the [`ResolvedAsset`] it points at, and
+/// marks references to assets which don't exist as dangling.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use rocket_container::{
+///     service::video::{AssetReference, VideoAsset},
+///     types::{AssetId, AssetType},
+/// };
+///
+/// let mut asset: VideoAsset =
+///     VideoAsset::from(AssetReference::new(AssetId(1), AssetType::Image));
+///
+/// asset.resolve(&HashMap::new(), &HashMap::new());
+///
+/// assert!(matches!(asset, VideoAsset::Reference(reference) if reference.is_dangling()));
+/// ```
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum VideoAsset {
+    /// Advertisement or image which a reference points at.
+    Resolved(ResolvedAsset),
+    /// Reference to an advertisement or image.
+    Reference(AssetReference),
+}
+
+impl VideoAsset {
+    /// Get asset ID.
+    pub fn asset_id(&self) -> AssetId {
+        match self {
+            VideoAsset::Resolved(ResolvedAsset::Ad(advertisement)) => AssetId(advertisement.id().0),
+            VideoAsset::Resolved(ResolvedAsset::Image(image)) => AssetId(image.id().0),
+            VideoAsset::Reference(reference) => reference.asset_id,
+        }
+    }
+
+    /// Get type of asset.
+    pub fn asset_type(&self) -> AssetType {
+        match self {
+            VideoAsset::Resolved(ResolvedAsset::Ad(_)) => AssetType::Ad,
+            VideoAsset::Resolved(ResolvedAsset::Image(_)) => AssetType::Image,
+            VideoAsset::Reference(reference) => reference.asset_type.clone(),
+        }
+    }
+
+    /// Check if the asset is a reference to an asset which doesn't exist.
+    pub fn is_dangling(&self) -> bool {
+        matches!(self, VideoAsset::Reference(reference) if reference.is_dangling())
+    }
+
+    /// Replace a reference with the advertisement or image it points at.
+    ///
+    /// References to assets which aren't in `advertisements` or `images` are marked as dangling.
+    /// Assets which are already resolved, and references to unknown asset types, are left as is.
+    pub fn resolve(
+        &mut self,
+        advertisements: &HashMap<AdvertisementId, Advertisement>,
+        images: &HashMap<ImageId, Image>,
+    ) {
+        if let VideoAsset::Reference(reference) = self {
+            let resolved: Option<ResolvedAsset> = match &reference.asset_type {
+                AssetType::Ad => advertisements
+                    .get(&AdvertisementId::from(reference.asset_id))
+                    .cloned()
+                    .map(ResolvedAsset::Ad),
+                AssetType::Image => images
+                    .get(&ImageId::from(reference.asset_id))
+                    .cloned()
+                    .map(ResolvedAsset::Image),
+                AssetType::Unknown(_) => return,
+            };
+
+            match resolved {
+                Some(resolved) => *self = VideoAsset::Resolved(resolved),
+                None => {
+                    warn!("Dangling asset reference {}", reference);
+
+                    reference.dangling = true;
+                }
+            }
+        }
+    }
+}
+
+impl From<AssetReference> for VideoAsset {
+    fn from(asset_reference: AssetReference) -> Self {
+        VideoAsset::Reference(asset_reference)
+    }
+}
+
+impl Display for VideoAsset {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VideoAsset::Resolved(resolved) => write!(f, "{}", resolved),
+            VideoAsset::Reference(reference) => write!(f, "{}", reference),
+        }
+    }
+}
+
 /* ******************************************* Video ******************************************** */
 
 /// Video asset returned from Rocket Container.
@@ -71,17 +232,22 @@ impl Display for AssetReference {
 ///
 /// [1]: [crate::repository::types::video::VideoDto]
 /// [2]: [crate::repository::video]
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, JsonSchema, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Video {
     /// Video assets.
-    assets: Vec<AssetReference>,
+    assets: Vec<VideoAsset>,
     /// Brief description of the video.
     description: String,
-    /// Expiration date for video in ISO-8601 format.
-    expiration_date: String,
+    /// When the video expires, or [None] if it never expires.
+    #[serde(
+        default,
+        serialize_with = "serialize_timestamp",
+        deserialize_with = "deserialize_timestamp"
+    )]
+    expires_at: Option<DateTime<Utc>>,
     /// Unique video identifier.
-    id: u32,
+    id: VideoId,
     /// URL for video playback.
     playback_url: String,
     /// Video title.
@@ -95,16 +261,16 @@ impl Video {
     pub fn new(
         assets: Vec<AssetReference>,
         description: String,
-        expiration_date: String,
-        id: u32,
+        expires_at: Option<DateTime<Utc>>,
+        id: VideoId,
         playback_url: String,
         title: String,
         r#type: VideoType,
     ) -> Self {
         Video {
-            assets,
+            assets: assets.into_iter().map(VideoAsset::from).collect(),
             description,
-            expiration_date,
+            expires_at,
             id,
             playback_url,
             title,
@@ -115,19 +281,67 @@ impl Video {
     /// Construct a new [VideoBuilder].
     ///
     /// Alias for [VideoBuilder::new].
-    pub fn builder(id: u32) -> VideoBuilder {
+    pub fn builder(id: VideoId) -> VideoBuilder {
         VideoBuilder::new(id)
     }
 
+    /// Get video assets.
+    pub fn assets(&self) -> &[VideoAsset] {
+        &self.assets
+    }
+
+    /// Get mutable video assets, e.g. to [resolve](VideoAsset::resolve) them.
+    pub fn assets_mut(&mut self) -> &mut [VideoAsset] {
+        &mut self.assets
+    }
+
+    /// Get brief description of the video.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Get when the video expires, or [None] if it never expires.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    /// Get video ID.
+    pub fn id(&self) -> VideoId {
+        self.id
+    }
+
+    /// Get URL for video playback.
+    pub fn playback_url(&self) -> &str {
+        &self.playback_url
+    }
+
+    /// Get video title.
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get type of video.
+    pub fn r#type(&self) -> &VideoType {
+        &self.r#type
+    }
+
+    /// Check if the video has expired at `now`.
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
     /// Get a [VideoBuilder] with values initialized from this [Video].
     pub fn to_builder(&self) -> VideoBuilder {
-        VideoBuilder::new(self.id)
-            .assets(self.assets.clone())
+        let mut builder: VideoBuilder = VideoBuilder::new(self.id)
             .description(self.description.clone())
-            .expiration_date(self.expiration_date.clone())
+            .expires_at(self.expires_at)
             .playback_url(self.playback_url.clone())
             .title(self.title.clone())
-            .r#type(self.r#type.clone())
+            .r#type(self.r#type.clone());
+
+        builder.assets = self.assets.clone();
+
+        builder
     }
 }
 
@@ -135,12 +349,12 @@ impl Display for Video {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Video {{ id: {}, title: {}, description: {}, expiration_date: {}, playback_url: {}, \
+            "Video {{ id: {}, title: {}, description: {}, expires_at: {}, playback_url: {}, \
             type: {}, assets: {} }}",
             self.id,
             self.title,
             self.description,
-            self.expiration_date,
+            option_to_string(&self.expires_at.map(Timestamp)),
             self.playback_url,
             self.r#type,
             self.assets.len()
@@ -155,19 +369,19 @@ pub struct VideoBuilder {
     /// See [Video::assets].
     ///
     /// Initialized to empty [Vec].
-    assets: Vec<AssetReference>,
+    assets: Vec<VideoAsset>,
     /// See [Video::description].
     ///
     /// Initialized to [None].
     description: Option<String>,
-    /// See [Video::expiration_date].
+    /// See [Video::expires_at].
     ///
-    /// Initialized to [None].
-    expiration_date: Option<String>,
+    /// Initialized to [None], which never expires.
+    expires_at: Option<DateTime<Utc>>,
     /// See [Video::id].
     ///
     /// Required value.
-    id: u32,
+    id: VideoId,
     /// See [Video::playback_url].
     ///
     /// Initialized to [None].
@@ -184,11 +398,11 @@ pub struct VideoBuilder {
 
 impl VideoBuilder {
     /// Construct a new VideoBuilder.
-    pub fn new(id: u32) -> Self {
+    pub fn new(id: VideoId) -> Self {
         VideoBuilder {
             assets: Vec::new(),
             description: None,
-            expiration_date: None,
+            expires_at: None,
             id,
             playback_url: None,
             title: None,
@@ -204,7 +418,7 @@ impl VideoBuilder {
         Video {
             assets: self.assets,
             description: self.description.unwrap(),
-            expiration_date: self.expiration_date.unwrap(),
+            expires_at: self.expires_at,
             id: self.id,
             playback_url: self.playback_url.unwrap(),
             title: self.title.unwrap(),
@@ -219,7 +433,7 @@ impl VideoBuilder {
         Video {
             assets: self.assets.clone(),
             description: self.description.clone().unwrap(),
-            expiration_date: self.expiration_date.clone().unwrap(),
+            expires_at: self.expires_at,
             id: self.id,
             playback_url: self.playback_url.clone().unwrap(),
             title: self.title.clone().unwrap(),
@@ -231,13 +445,13 @@ impl VideoBuilder {
     ///
     /// Singular form of [VideoBuilder::assets].
     pub fn asset(mut self, asset: AssetReference) -> Self {
-        self.assets.push(asset);
+        self.assets.push(VideoAsset::from(asset));
         self
     }
 
     /// Set `VideoBuilder::assets`.
     pub fn assets(mut self, assets: Vec<AssetReference>) -> Self {
-        self.assets = assets;
+        self.assets = assets.into_iter().map(VideoAsset::from).collect();
         self
     }
 
@@ -247,9 +461,9 @@ impl VideoBuilder {
         self
     }
 
-    /// Set `VideoBuilder::expiration_date`.
-    pub fn expiration_date(mut self, expiration_date: String) -> Self {
-        self.expiration_date = Some(expiration_date);
+    /// Set `VideoBuilder::expires_at`.
+    pub fn expires_at(mut self, expires_at: Option<DateTime<Utc>>) -> Self {
+        self.expires_at = expires_at;
         self
     }
 
@@ -280,7 +494,7 @@ impl Display for VideoBuilder {
                 id: {},
                 title: {},
                 description: {},
-                expiration_date: {},
+                expires_at: {},
                 playback_url: {},
                 type: {},
                 assets: {}
@@ -288,7 +502,7 @@ impl Display for VideoBuilder {
             self.id,
             option_to_string(&self.title),
             option_to_string(&self.description),
-            option_to_string(&self.expiration_date),
+            option_to_string(&self.expires_at.map(Timestamp)),
             option_to_string(&self.playback_url),
             option_to_string(&self.r#type),
             array_to_string(&self.assets),
@@ -298,7 +512,7 @@ impl Display for VideoBuilder {
 
 /* ****************************************** VideoMap ****************************************** */
 
-/// Type alias for a [`HashMap`] of [`u32`] to [`Vec`]`<`[`Video`]`>`.
+/// Type alias for a [`HashMap`] of [`ContainerId`] to [`Vec`]`<`[`Video`]`>`.
 ///
 /// # Examples
 ///
@@ -308,44 +522,253 @@ impl Display for VideoBuilder {
 /// let service: VideoService = VideoService::default();
 /// let videos: VideoMap = service.list_videos().await?;
 /// ```
-pub type VideoMap = HashMap<u32, Vec<Video>>;
+pub type VideoMap = HashMap<ContainerId, Vec<Video>>;
+
+/* **************************************** VideoFilter ***************************************** */
+
+/// Criteria which videos must match to be listed.
+///
+/// Criteria which are not set match every video, so the default filter matches every video
+/// which hasn't expired. Videos without an expiration date never expire.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     service::video::VideoFilter,
+///     types::{parse_timestamp, VideoType},
+/// };
+///
+/// let filter: VideoFilter = VideoFilter {
+///     types: vec![VideoType::Movie, VideoType::Episode],
+///     expires_after: parse_timestamp("2022-01-01"),
+///     title: Some("family".to_string()),
+///     ..VideoFilter::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VideoFilter {
+    /// Video must be one of these types.
+    ///
+    /// An empty list matches every type.
+    pub types: Vec<VideoType>,
+    /// Video must expire before this date.
+    pub expires_before: Option<DateTime<Utc>>,
+    /// Video must expire after this date, or never expire.
+    pub expires_after: Option<DateTime<Utc>>,
+    /// Include videos which have already expired.
+    pub include_expired: bool,
+    /// Video title must contain this string, ignoring case.
+    pub title: Option<String>,
+}
+
+impl VideoFilter {
+    /// Check if the filter has no criteria other than whether expired videos are included.
+    pub fn is_empty(&self) -> bool {
+        self == &VideoFilter {
+            include_expired: self.include_expired,
+            ..VideoFilter::default()
+        }
+    }
+
+    /// Check if a video matches the filter.
+    pub fn matches(&self, video: &Video) -> bool {
+        (self.include_expired || !video.is_expired(Utc::now()))
+            && (self.types.is_empty() || self.types.contains(&video.r#type))
+            && self
+                .expires_before
+                .is_none_or(|date| video.expires_at.is_some_and(|expires_at| expires_at < date))
+            && self
+                .expires_after
+                .is_none_or(|date| video.expires_at.is_none_or(|expires_at| expires_at > date))
+            && self
+                .title
+                .as_ref()
+                .is_none_or(|title| video.title.to_lowercase().contains(&title.to_lowercase()))
+    }
+}
+
+impl Display for VideoFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "VideoFilter {{ types: {}, expires_before: {}, expires_after: {}, \
+            include_expired: {}, title: {} }}",
+            array_to_string(&self.types),
+            option_to_string(&self.expires_before.map(Timestamp)),
+            option_to_string(&self.expires_after.map(Timestamp)),
+            self.include_expired,
+            option_to_string(&self.title),
+        )
+    }
+}
+
+/* ***************************************** VideoSort ****************************************** */
+
+/// Field which videos are sorted by.
+///
+/// Every sort falls back to sorting by ID so that videos with equal values have a stable order.
+#[derive(Clone, Copy, Debug, Default, Eq, FromFormField, PartialEq)]
+pub enum VideoSort {
+    /// Sort by [Video::id].
+    #[default]
+    #[field(value = "id")]
+    Id,
+    /// Sort by [Video::title].
+    #[field(value = "title")]
+    Title,
+    /// Sort by [Video::r#type].
+    #[field(value = "type")]
+    Type,
+    /// Sort by [Video::expires_at]. Videos which never expire come first.
+    #[field(value = "expirationDate")]
+    ExpirationDate,
+}
+
+impl Display for VideoSort {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VideoSort::Id => write!(f, "id"),
+            VideoSort::Title => write!(f, "title"),
+            VideoSort::Type => write!(f, "type"),
+            VideoSort::ExpirationDate => write!(f, "expirationDate"),
+        }
+    }
+}
+
+/* **************************************** VideoSorting **************************************** */
+
+/// How to sort a list of videos.
+///
+/// The default sorting is by ID in ascending order.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::{
+///     service::video::{VideoSort, VideoSorting},
+///     types::SortOrder,
+/// };
+///
+/// let sorting: VideoSorting = VideoSorting {
+///     sort: VideoSort::Title,
+///     order: SortOrder::Desc,
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct VideoSorting {
+    /// Field to sort by.
+    pub sort: VideoSort,
+    /// Sort order.
+    pub order: SortOrder,
+}
+
+impl VideoSorting {
+    /// Sort a list of videos in place.
+    pub fn sort(&self, videos: &mut [Video]) {
+        videos.sort_by(|a, b| self.order.apply(self.compare(a, b)));
+    }
+
+    /// Compare two videos in ascending order.
+    fn compare(&self, a: &Video, b: &Video) -> Ordering {
+        let ordering: Ordering = match self.sort {
+            VideoSort::Id => Ordering::Equal,
+            VideoSort::Title => a.title.cmp(&b.title),
+            VideoSort::Type => a.r#type.cmp(&b.r#type),
+            VideoSort::ExpirationDate => a.expires_at.cmp(&b.expires_at),
+        };
+
+        ordering.then(a.id.cmp(&b.id))
+    }
+}
+
+impl Display for VideoSorting {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "VideoSorting {{ sort: {}, order: {} }}",
+            self.sort, self.order
+        )
+    }
+}
 
 /* **************************************** VideoService **************************************** */
 
-/// Image service.
+/// Video service.
 ///
 /// [`VideoService`] is the service layer wrapper for [`VideoRepository`]. It transforms
 /// DTO types into domain types.
+///
+/// Videos and asset references of types Rocket Container doesn't know are passed through, dropped,
+/// or reported according to an [`UnknownTypePolicy`]. Everything is fetched through a [`Mirror`].
 #[derive(Default)]
 pub struct VideoService {
+    /// Local mirror of Rocket Video.
+    mirror: Arc<Mirror>,
+    /// What to do with videos and asset references of unknown types.
+    unknown_types: UnknownTypePolicy,
     /// Repository layer that the service calls.
     video_repository: VideoRepository,
 }
 
-impl<'a> VideoService {
+impl VideoService {
     /// Create a new [`VideoService`].
     pub fn new(video_repository: VideoRepository) -> Self {
-        Self { video_repository }
+        Self {
+            mirror: Arc::new(Mirror::default()),
+            unknown_types: UnknownTypePolicy::default(),
+            video_repository,
+        }
+    }
+
+    /// Set the local mirror which videos are fetched through.
+    pub fn mirror(mut self, mirror: Arc<Mirror>) -> Self {
+        self.mirror = mirror;
+        self
+    }
+
+    /// Set what to do with videos and asset references of unknown types.
+    pub fn unknown_type_policy(mut self, unknown_types: UnknownTypePolicy) -> Self {
+        self.unknown_types = unknown_types;
+        self
     }
 
     /// Get video by ID from Rocket Video.
     ///
+    /// Returns `Err(`[`ErrorKind::NotFound`]`)` if there is no video with ID `video_id`, or if
+    /// its type is unknown and unknown types are dropped.
+    ///
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::service::video::{Video, VideoService};
+    /// use rocket_container::{
+    ///     service::video::{Video, VideoService},
+    ///     types::VideoId,
+    /// };
     ///
-    /// let video_id: u32 = 1;
+    /// let video_id: VideoId = VideoId(1);
     /// let service: VideoService = VideoService::default();
     /// let video: Video = service.get_video(video_id).await?;
     /// ```
-    pub async fn get_video(&self, video_id: u32) -> Result<Video> {
+    pub async fn get_video(&self, video_id: VideoId) -> Result<Video> {
         trace!("VideoService::get_video {}", video_id);
 
+        let video_dto: VideoDto = self
+            .admit_videos(vec![
+                self.mirror
+                    .fetch(
+                        &format!("video-{}", video_id),
+                        self.video_repository.get_video(video_id),
+                    )
+                    .await?,
+            ])?
+            .pop()
+            .ok_or_else(|| Error {
+                kind: ErrorKind::NotFound,
+                message: format!("Video {} has an unknown type", video_id),
+            })?;
         let assets: Vec<AssetReference> = self.list_asset_references(video_id).await?;
-        let video: Video = VideoBuilder::from(self.video_repository.get_video(video_id).await?)
-            .assets(assets)
-            .build();
+        let video: Video = VideoBuilder::from(video_dto).assets(assets).build();
 
         Ok(video)
     }
@@ -355,19 +778,27 @@ impl<'a> VideoService {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::service::video::{AssetReference, VideoService};
+    /// use rocket_container::{
+    ///     service::video::{AssetReference, VideoService},
+    ///     types::VideoId,
+    /// };
     ///
-    /// let video_id: u32 = 1;
+    /// let video_id: VideoId = VideoId(1);
     /// let service: VideoService = VideoService::default();
     /// let assets: Vec<AssetReference> = service.list_asset_references(video_id).await?;
     /// ```
-    pub async fn list_asset_references(&self, video_id: u32) -> Result<Vec<AssetReference>> {
+    pub async fn list_asset_references(&self, video_id: VideoId) -> Result<Vec<AssetReference>> {
         trace!("VideoService::list_asset_references {}", video_id);
 
         let asset_references: Vec<AssetReference> = self
-            .video_repository
-            .list_asset_references(video_id)
-            .await?
+            .admit_asset_references(
+                self.mirror
+                    .fetch(
+                        &format!("video-{}.assets", video_id),
+                        self.video_repository.list_asset_references(video_id),
+                    )
+                    .await?,
+            )?
             .into_iter()
             .map(AssetReference::from)
             .collect();
@@ -380,9 +811,12 @@ impl<'a> VideoService {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::{service::video::{AssetReference, VideoService}, types::AssetType};
+    /// use rocket_container::{
+    ///     service::video::{AssetReference, VideoService},
+    ///     types::{AssetType, VideoId},
+    /// };
     ///
-    /// let video_id: u32 = 1;
+    /// let video_id: VideoId = VideoId(1);
     /// let asset_type: AssetType = AssetType::Image;
     /// let service: VideoService = VideoService::default();
     /// let assets: Vec<AssetReference> =
@@ -390,7 +824,7 @@ impl<'a> VideoService {
     /// ```
     pub async fn list_asset_references_by_type(
         &self,
-        video_id: u32,
+        video_id: VideoId,
         asset_type: AssetType,
     ) -> Result<Vec<AssetReference>> {
         trace!(
@@ -400,9 +834,15 @@ impl<'a> VideoService {
         );
 
         let asset_references: Vec<AssetReference> = self
-            .video_repository
-            .list_asset_references_by_type(video_id, asset_type)
-            .await?
+            .admit_asset_references(
+                self.mirror
+                    .fetch(
+                        &format!("video-{}.assets-{}", video_id, asset_type),
+                        self.video_repository
+                            .list_asset_references_by_type(video_id, asset_type),
+                    )
+                    .await?,
+            )?
             .into_iter()
             .map(AssetReference::from)
             .collect();
@@ -423,15 +863,16 @@ impl<'a> VideoService {
     pub async fn list_videos(&self) -> Result<VideoMap> {
         trace!("VideoService::list_videos");
 
-        let images: Vec<(u32, Video)> = future::try_join_all(
-            self.video_repository
-                .list_videos()
-                .await?
-                .into_iter()
-                .map(|video_dto| self.map_video_dto_to_tuple(video_dto)),
+        let images: Vec<(ContainerId, Video)> = future::try_join_all(
+            self.admit_videos(
+                self.mirror
+                    .fetch("videos", self.video_repository.list_videos())
+                    .await?,
+            )?
+            .into_iter()
+            .map(|video_dto| self.map_video_dto_to_tuple(video_dto)),
         )
-        .await
-        .unwrap();
+        .await?;
 
         Ok(group(images.into_iter()))
     }
@@ -441,24 +882,31 @@ impl<'a> VideoService {
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::service::video::{Video, VideoService};
+    /// use rocket_container::{
+    ///     service::video::{Video, VideoService},
+    ///     types::ContainerId,
+    /// };
     ///
-    /// let container_id: u32 = 1;
+    /// let container_id: ContainerId = ContainerId(1);
     /// let service: VideoService = VideoService::default();
     /// let videos: Vec<Video> = service.list_videos_by_container(container_id).await?;
     /// ```
-    pub async fn list_videos_by_container(&self, container_id: u32) -> Result<Vec<Video>> {
+    pub async fn list_videos_by_container(&self, container_id: ContainerId) -> Result<Vec<Video>> {
         trace!("VideoService::list_videos_by_container {}", container_id);
 
         let images: Vec<Video> = future::try_join_all(
-            self.video_repository
-                .list_videos_by_container(container_id)
-                .await?
-                .into_iter()
-                .map(|video_dto| self.map_video_dto_to_video(video_dto)),
+            self.admit_videos(
+                self.mirror
+                    .fetch(
+                        &format!("videos.container-{}", container_id),
+                        self.video_repository.list_videos_by_container(container_id),
+                    )
+                    .await?,
+            )?
+            .into_iter()
+            .map(|video_dto| self.map_video_dto_to_video(video_dto)),
         )
-        .await
-        .unwrap();
+        .await?;
 
         Ok(images)
     }
@@ -477,27 +925,71 @@ impl<'a> VideoService {
     pub async fn list_videos_by_type(&self, video_type: VideoType) -> Result<VideoMap> {
         trace!("VideoService::list_videos_by_type {}", video_type);
 
-        let images: Vec<(u32, Video)> = future::try_join_all(
-            self.video_repository
-                .list_videos_by_type(video_type)
-                .await?
-                .into_iter()
-                .map(|video_dto| self.map_video_dto_to_tuple(video_dto)),
+        let images: Vec<(ContainerId, Video)> = future::try_join_all(
+            self.admit_videos(
+                self.mirror
+                    .fetch(
+                        &format!("videos.type-{}", video_type),
+                        self.video_repository.list_videos_by_type(video_type),
+                    )
+                    .await?,
+            )?
+            .into_iter()
+            .map(|video_dto| self.map_video_dto_to_tuple(video_dto)),
         )
-        .await
-        .unwrap();
+        .await?;
 
         Ok(group(images.into_iter()))
     }
 
+    /// List all videos with any of the given types from Rocket Video.
+    ///
+    /// Makes one request to Rocket Video per distinct type, concurrently.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::{
+    ///     service::video::{VideoMap, VideoService},
+    ///     types::{Result, VideoType},
+    /// };
+    ///
+    /// async fn list_episodes_and_movies(service: &VideoService) -> Result<VideoMap> {
+    ///     let video_types: [VideoType; 2] = [VideoType::Episode, VideoType::Movie];
+    ///
+    ///     service.list_videos_by_types(&video_types).await
+    /// }
+    /// ```
+    pub async fn list_videos_by_types(&self, video_types: &[VideoType]) -> Result<VideoMap> {
+        trace!(
+            "VideoService::list_videos_by_types {}",
+            array_to_string(video_types)
+        );
+
+        let video_types: BTreeSet<&VideoType> = video_types.iter().collect();
+        let video_maps: Vec<VideoMap> = future::try_join_all(
+            video_types
+                .into_iter()
+                .map(|video_type| self.list_videos_by_type(video_type.clone())),
+        )
+        .await?;
+
+        Ok(group(video_maps.into_iter().flatten().flat_map(
+            |(container_id, videos)| videos.into_iter().map(move |video| (container_id, video)),
+        )))
+    }
+
     /// List all videos for a container, by type, from Rocket Video.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use rocket_container::{service::video::{VideoMap, VideoService}, types::VideoType};
+    /// use rocket_container::{
+    ///     service::video::{VideoMap, VideoService},
+    ///     types::{ContainerId, VideoType},
+    /// };
     ///
-    /// let container_id: u32 = 1;
+    /// let container_id: ContainerId = ContainerId(1);
     /// let video_type: VideoType = VideoType::Movie;
     /// let service: VideoService = VideoService::default();
     /// let videos: VideoMap =
@@ -505,7 +997,7 @@ impl<'a> VideoService {
     /// ```
     pub async fn list_videos_by_container_and_type(
         &self,
-        container_id: u32,
+        container_id: ContainerId,
         video_type: VideoType,
     ) -> Result<VideoMap> {
         trace!(
@@ -514,36 +1006,156 @@ impl<'a> VideoService {
             video_type
         );
 
-        let images: Vec<(u32, Video)> = future::try_join_all(
-            self.video_repository
-                .list_videos_by_container_and_type(container_id, video_type)
-                .await?
-                .into_iter()
-                .map(|video_dto| self.map_video_dto_to_tuple(video_dto)),
+        let images: Vec<(ContainerId, Video)> = future::try_join_all(
+            self.admit_videos(
+                self.mirror
+                    .fetch(
+                        &format!("videos.container-{}.type-{}", container_id, video_type),
+                        self.video_repository
+                            .list_videos_by_container_and_type(container_id, video_type),
+                    )
+                    .await?,
+            )?
+            .into_iter()
+            .map(|video_dto| self.map_video_dto_to_tuple(video_dto)),
         )
-        .await
-        .unwrap();
+        .await?;
 
         Ok(group(images.into_iter()))
     }
 
+    /// List all videos for a container with any of the given types from Rocket Video.
+    ///
+    /// Makes one request to Rocket Video per distinct type, concurrently.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::{
+    ///     service::video::{Video, VideoService},
+    ///     types::{ContainerId, Result, VideoType},
+    /// };
+    ///
+    /// async fn list_episodes_and_movies(
+    ///     service: &VideoService,
+    ///     container_id: ContainerId,
+    /// ) -> Result<Vec<Video>> {
+    ///     let video_types: [VideoType; 2] = [VideoType::Episode, VideoType::Movie];
+    ///
+    ///     service
+    ///         .list_videos_by_container_and_types(container_id, &video_types)
+    ///         .await
+    /// }
+    /// ```
+    pub async fn list_videos_by_container_and_types(
+        &self,
+        container_id: ContainerId,
+        video_types: &[VideoType],
+    ) -> Result<Vec<Video>> {
+        trace!(
+            "VideoService::list_videos_by_container_and_types ({}, {})",
+            container_id,
+            array_to_string(video_types)
+        );
+
+        let video_types: BTreeSet<&VideoType> = video_types.iter().collect();
+        let video_maps: Vec<VideoMap> =
+            future::try_join_all(video_types.into_iter().map(|video_type| {
+                self.list_videos_by_container_and_type(container_id, video_type.clone())
+            }))
+            .await?;
+
+        Ok(video_maps
+            .into_iter()
+            .flat_map(VideoMap::into_values)
+            .flatten()
+            .collect())
+    }
+
     /* ****************************** Private utility function ****************************** */
 
+    /// Apply the [`UnknownTypePolicy`] to an item, described by `item`, which may have an unknown
+    /// type.
+    ///
+    /// Returns whether to keep the item, or an error if unknown types are reported.
+    fn admit<F>(&self, unknown: bool, item: F) -> Result<bool>
+    where
+        F: FnOnce() -> String,
+    {
+        if !unknown {
+            return Ok(true);
+        }
+
+        match self.unknown_types {
+            UnknownTypePolicy::Pass => {
+                debug!("Passing through {}", item());
+
+                Ok(true)
+            }
+            UnknownTypePolicy::Drop => {
+                warn!("Dropping {}", item());
+
+                Ok(false)
+            }
+            UnknownTypePolicy::Report => Err(Error {
+                kind: ErrorKind::Permanent,
+                message: format!("Unexpected {}", item()),
+            }),
+        }
+    }
+
+    /// Keep the asset references which the [`UnknownTypePolicy`] admits.
+    fn admit_asset_references(
+        &self,
+        asset_reference_dtos: Vec<AssetReferenceDto>,
+    ) -> Result<Vec<AssetReferenceDto>> {
+        let mut admitted: Vec<AssetReferenceDto> = Vec::with_capacity(asset_reference_dtos.len());
+
+        for asset_reference_dto in asset_reference_dtos {
+            let unknown: bool = asset_reference_dto.asset_type().is_unknown();
+
+            if self.admit(unknown, || {
+                format!("asset reference with unknown type {}", asset_reference_dto)
+            })? {
+                admitted.push(asset_reference_dto);
+            }
+        }
+
+        Ok(admitted)
+    }
+
+    /// Keep the videos which the [`UnknownTypePolicy`] admits.
+    fn admit_videos(&self, video_dtos: Vec<VideoDto>) -> Result<Vec<VideoDto>> {
+        let mut admitted: Vec<VideoDto> = Vec::with_capacity(video_dtos.len());
+
+        for video_dto in video_dtos {
+            let unknown: bool = video_dto.r#type().is_unknown();
+
+            if self.admit(unknown, || {
+                format!(
+                    "video {} with unknown type {}",
+                    video_dto.id(),
+                    video_dto.r#type()
+                )
+            })? {
+                admitted.push(video_dto);
+            }
+        }
+
+        Ok(admitted)
+    }
+
     async fn map_video_dto_to_video(&self, video_dto: VideoDto) -> Result<Video> {
-        let assets: Vec<AssetReference> = self
-            .list_asset_references(video_dto.id().parse().unwrap())
-            .await?;
+        let assets: Vec<AssetReference> = self.list_asset_references(video_dto.id()).await?;
 
         Ok(VideoBuilder::from(video_dto).assets(assets).build())
     }
 
-    async fn map_video_dto_to_tuple(&self, video_dto: VideoDto) -> Result<(u32, Video)> {
-        let assets: Vec<AssetReference> = self
-            .list_asset_references(video_dto.id().parse().unwrap())
-            .await?;
+    async fn map_video_dto_to_tuple(&self, video_dto: VideoDto) -> Result<(ContainerId, Video)> {
+        let assets: Vec<AssetReference> = self.list_asset_references(video_dto.id()).await?;
 
         Ok((
-            video_dto.container_id().parse().unwrap(),
+            video_dto.container_id(),
             VideoBuilder::from(video_dto).assets(assets).build(),
         ))
     }
@@ -553,24 +1165,473 @@ impl<'a> VideoService {
 
 #[cfg(test)]
 mod test {
+    use std::{
+        collections::HashMap,
+        env, fs,
+        path::{Path, PathBuf},
+        sync::Arc,
+    };
+
+    use serde_json::json;
+
     use crate::{
-        service::video::{AssetReference, Video, VideoMap},
-        types::{AssetType, Result, VideoType},
+        config::UnknownTypePolicy,
+        repository::video::{AssetReferenceDto, VideoDto, VideoRepository},
+        service::{
+            advertisement::Advertisement,
+            image::Image,
+            mirror::Mirror,
+            video::{AssetReference, Video, VideoFilter, VideoMap, VideoSort, VideoSorting},
+        },
+        types::{
+            parse_timestamp, AdvertisementId, AssetId, AssetType, ContainerId, ImageId, Result,
+            SortOrder, VideoId, VideoType,
+        },
     };
 
-    use super::VideoService;
+    use super::{ResolvedAsset, VideoAsset, VideoService};
+
+    fn video(id: u32, title: &str, r#type: VideoType, expires_at: &str) -> Video {
+        Video::builder(VideoId(id))
+            .description(String::new())
+            .expires_at(parse_timestamp(expires_at))
+            .playback_url(format!("/path/to/test{}.m3u8", id))
+            .title(title.to_string())
+            .r#type(r#type)
+            .build()
+    }
+
+    fn videos() -> Vec<Video> {
+        vec![
+            video(3, "Beta", VideoType::Clip, "2999-03-23"),
+            video(1, "Beta", VideoType::Movie, "2999-01-01"),
+            video(2, "Alpha", VideoType::Episode, ""),
+        ]
+    }
+
+    fn video_dtos() -> Vec<VideoDto> {
+        [
+            (1, VideoType::Clip),
+            (2, VideoType::Unknown("TRAILER".to_string())),
+        ]
+        .into_iter()
+        .map(|(id, r#type)| {
+            VideoDto::new(
+                ContainerId(0),
+                String::new(),
+                String::new(),
+                VideoId(id),
+                format!("/path/to/test{}.m3u8", id),
+                format!("Video {}", id),
+                r#type,
+            )
+        })
+        .collect()
+    }
+
+    /// Get a service which serves the `video_dtos` of known types and the asset references of each
+    /// video in `assets` from an offline mirror at `path`.
+    async fn mirrored_service(
+        path: &Path,
+        assets: Vec<(u32, Vec<AssetReferenceDto>)>,
+        policy: UnknownTypePolicy,
+    ) -> VideoService {
+        let mirror: Mirror = Mirror::open(path).unwrap();
+
+        mirror
+            .fetch("videos", async { Ok(video_dtos()) })
+            .await
+            .unwrap();
+
+        for (video_id, asset_reference_dtos) in assets {
+            mirror
+                .fetch(&format!("video-{}.assets", video_id), async {
+                    Ok(asset_reference_dtos)
+                })
+                .await
+                .unwrap();
+        }
+
+        VideoService::new(VideoRepository::default())
+            .mirror(Arc::new(Mirror::open(path).unwrap().offline(true)))
+            .unknown_type_policy(policy)
+    }
+
+    fn ids(videos: &[Video]) -> Vec<u32> {
+        videos.iter().map(|video| video.id().0).collect()
+    }
+
+    #[test]
+    fn filter_videos_default() {
+        // Given
+        let filter: VideoFilter = VideoFilter::default();
+
+        // When
+        let actual: Vec<Video> = videos()
+            .into_iter()
+            .filter(|video| filter.matches(video))
+            .collect();
+
+        // Then
+        assert!(filter.is_empty());
+        assert_eq!(ids(&actual), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn filter_videos_by_type_and_title() {
+        // Given
+        let filter: VideoFilter = VideoFilter {
+            types: vec![VideoType::Clip, VideoType::Movie],
+            title: Some("BET".to_string()),
+            ..VideoFilter::default()
+        };
+
+        // When
+        let actual: Vec<Video> = videos()
+            .into_iter()
+            .filter(|video| filter.matches(video))
+            .collect();
+
+        // Then
+        assert_eq!(ids(&actual), vec![3, 1]);
+    }
+
+    #[test]
+    fn filter_videos_by_expiration_date() {
+        // Given
+        let expires_before: VideoFilter = VideoFilter {
+            expires_before: parse_timestamp("2999-02-01"),
+            ..VideoFilter::default()
+        };
+        let expires_after: VideoFilter = VideoFilter {
+            expires_after: parse_timestamp("2999-02-01"),
+            ..VideoFilter::default()
+        };
+
+        // When
+        let before: Vec<Video> = videos()
+            .into_iter()
+            .filter(|video| expires_before.matches(video))
+            .collect();
+        let after: Vec<Video> = videos()
+            .into_iter()
+            .filter(|video| expires_after.matches(video))
+            .collect();
+
+        // Then
+        assert_eq!(ids(&before), vec![1]);
+        assert_eq!(ids(&after), vec![3, 2]);
+    }
+
+    #[test]
+    fn filter_videos_expired() {
+        // Given
+        let mut videos: Vec<Video> = videos();
+        videos.push(video(4, "Gamma", VideoType::Clip, "2022-03-23"));
+        let include_expired: VideoFilter = VideoFilter {
+            include_expired: true,
+            ..VideoFilter::default()
+        };
+
+        // When
+        let hidden: Vec<Video> = videos
+            .iter()
+            .filter(|video| VideoFilter::default().matches(video))
+            .cloned()
+            .collect();
+        let included: Vec<Video> = videos
+            .into_iter()
+            .filter(|video| include_expired.matches(video))
+            .collect();
+
+        // Then
+        assert_eq!(ids(&hidden), vec![3, 1, 2]);
+        assert_eq!(ids(&included), vec![3, 1, 2, 4]);
+        assert!(include_expired.is_empty());
+    }
+
+    #[test]
+    fn serialize_expires_at() {
+        // Given
+        let video: Video = video(1, "Alpha", VideoType::Clip, "2022-03-23T12:30:00+02:00");
+
+        // When
+        let result = serde_json::to_value(&video);
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual["expiresAt"], "2022-03-23T10:30:00Z"),
+            Err(err) => panic!("Failed to serialize video with error: {}", err),
+        }
+    }
+
+    #[test]
+    fn resolve_asset() {
+        // Given
+        let advertisement: Advertisement = Advertisement::new(
+            AdvertisementId(1),
+            "Advertisement".to_string(),
+            "https://ad.com".to_string(),
+        );
+        let advertisements: HashMap<AdvertisementId, Advertisement> =
+            HashMap::from([(AdvertisementId(1), advertisement.clone())]);
+        let mut asset: VideoAsset =
+            VideoAsset::from(AssetReference::new(AssetId(1), AssetType::Ad));
+
+        // When
+        asset.resolve(&advertisements, &HashMap::new());
+
+        // Then
+        assert_eq!(
+            asset,
+            VideoAsset::Resolved(ResolvedAsset::Ad(advertisement))
+        );
+    }
+
+    #[test]
+    fn resolve_dangling_asset() {
+        // Given
+        let images: HashMap<ImageId, Image> = HashMap::from([(
+            ImageId(2),
+            Image::new(
+                ImageId(2),
+                "Image".to_string(),
+                "https://image.com".to_string(),
+            ),
+        )]);
+        let mut asset: VideoAsset =
+            VideoAsset::from(AssetReference::new(AssetId(1), AssetType::Image));
+
+        // When
+        asset.resolve(&HashMap::new(), &images);
+
+        // Then
+        match asset {
+            VideoAsset::Reference(reference) => assert!(reference.is_dangling()),
+            VideoAsset::Resolved(resolved) => panic!("Resolved dangling reference {}", resolved),
+        }
+    }
+
+    #[test]
+    fn serialize_video_assets() {
+        // Given
+        let assets: Vec<VideoAsset> = vec![
+            VideoAsset::from(AssetReference::new(AssetId(1), AssetType::Ad)),
+            VideoAsset::Resolved(ResolvedAsset::Image(Image::new(
+                ImageId(2),
+                "Image".to_string(),
+                "https://image.com".to_string(),
+            ))),
+        ];
+        let expected: String = json!([
+            {"assetId": 1, "assetType": "AD"},
+            {"assetType": "IMAGE", "id": 2, "name": "Image", "url": "https://image.com"},
+        ])
+        .to_string();
+
+        // When
+        let result = serde_json::to_string(&assets);
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual, expected),
+            Err(err) => panic!("Failed to serialize assets with error: {}", err),
+        }
+    }
+
+    #[test]
+    fn sort_videos_default() {
+        // Given
+        let mut actual: Vec<Video> = videos();
+
+        // When
+        VideoSorting::default().sort(&mut actual);
+
+        // Then
+        assert_eq!(ids(&actual), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn sort_videos_by_title() {
+        // Given
+        let mut actual: Vec<Video> = videos();
+        let sorting: VideoSorting = VideoSorting {
+            sort: VideoSort::Title,
+            order: SortOrder::Asc,
+        };
+
+        // When
+        sorting.sort(&mut actual);
+
+        // Then
+        assert_eq!(ids(&actual), vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn sort_videos_by_type_descending() {
+        // Given
+        let mut actual: Vec<Video> = videos();
+        let sorting: VideoSorting = VideoSorting {
+            sort: VideoSort::Type,
+            order: SortOrder::Desc,
+        };
+
+        // When
+        sorting.sort(&mut actual);
+
+        // Then
+        assert_eq!(ids(&actual), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn sort_videos_by_expiration_date() {
+        // Given
+        let mut actual: Vec<Video> = videos();
+        let sorting: VideoSorting = VideoSorting {
+            sort: VideoSort::ExpirationDate,
+            order: SortOrder::Asc,
+        };
+
+        // When
+        sorting.sort(&mut actual);
+
+        // Then
+        assert_eq!(ids(&actual), vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn admit_unknown_video_types() {
+        // Given
+        let service = |policy: UnknownTypePolicy| {
+            VideoService::new(VideoRepository::default()).unknown_type_policy(policy)
+        };
+
+        // When
+        let pass: Result<Vec<VideoDto>> =
+            service(UnknownTypePolicy::Pass).admit_videos(video_dtos());
+        let drop: Result<Vec<VideoDto>> =
+            service(UnknownTypePolicy::Drop).admit_videos(video_dtos());
+        let report: Result<Vec<VideoDto>> =
+            service(UnknownTypePolicy::Report).admit_videos(video_dtos());
+
+        // Then
+        assert_eq!(pass.map(|videos| videos.len()), Ok(2));
+        assert_eq!(drop.map(|videos| videos.len()), Ok(1));
+        assert!(report.is_err(), "Unknown video type should be reported");
+    }
+
+    #[tokio::test]
+    async fn list_videos_reports_unknown_asset_types() {
+        // Given
+        let path: PathBuf =
+            env::temp_dir().join(format!("video-report-{}.sqlite3", std::process::id()));
+        let trailer = |video_id: u32| {
+            vec![AssetReferenceDto::new(
+                AssetId(9),
+                AssetType::Unknown("TRAILER".to_string()),
+                VideoId(video_id),
+            )]
+        };
+        let service: VideoService = mirrored_service(
+            &path,
+            vec![(1, trailer(1)), (2, trailer(2))],
+            UnknownTypePolicy::Report,
+        )
+        .await;
+
+        // When
+        let result: Result<VideoMap> = service.list_videos().await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        assert!(result.is_err(), "Unknown asset type should be reported");
+    }
+
+    #[tokio::test]
+    async fn list_videos_by_types_ignores_duplicate_types() {
+        // Given
+        let path: PathBuf =
+            env::temp_dir().join(format!("video-types-{}.sqlite3", std::process::id()));
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+
+        mirror
+            .fetch("videos.type-CLIP", async {
+                Ok(video_dtos().into_iter().take(1).collect::<Vec<VideoDto>>())
+            })
+            .await
+            .unwrap();
+        mirror
+            .fetch("video-1.assets", async {
+                Ok(Vec::<AssetReferenceDto>::new())
+            })
+            .await
+            .unwrap();
+
+        let service: VideoService = VideoService::new(VideoRepository::default())
+            .mirror(Arc::new(Mirror::open(&path).unwrap().offline(true)));
+
+        // When
+        let result: Result<VideoMap> = service
+            .list_videos_by_types(&[VideoType::Clip, VideoType::Clip])
+            .await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(ids(&actual[&ContainerId(0)]), vec![1]),
+            Err(err) => panic!("Failed to list videos with error: {}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn list_videos_by_container_and_types_ignores_duplicate_types() {
+        // Given
+        let path: PathBuf = env::temp_dir().join(format!(
+            "container-video-types-{}.sqlite3",
+            std::process::id()
+        ));
+        let mirror: Mirror = Mirror::open(&path).unwrap();
+
+        mirror
+            .fetch("videos.container-0.type-CLIP", async {
+                Ok(video_dtos().into_iter().take(1).collect::<Vec<VideoDto>>())
+            })
+            .await
+            .unwrap();
+        mirror
+            .fetch("video-1.assets", async {
+                Ok(Vec::<AssetReferenceDto>::new())
+            })
+            .await
+            .unwrap();
+
+        let service: VideoService = VideoService::new(VideoRepository::default())
+            .mirror(Arc::new(Mirror::open(&path).unwrap().offline(true)));
+
+        // When
+        let result: Result<Vec<Video>> = service
+            .list_videos_by_container_and_types(ContainerId(0), &[VideoType::Clip, VideoType::Clip])
+            .await;
+        fs::remove_file(&path).unwrap();
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(ids(&actual), vec![1]),
+            Err(err) => panic!("Failed to list videos with error: {}", err),
+        }
+    }
 
     #[tokio::test]
     async fn test_get_video() {
         // Given
         let service = VideoService::default();
-        let video_id: u32 = 1301;
+        let video_id: VideoId = VideoId(1301);
         let expected: Video = Video::new(
             Vec::new(),
             "Etiam vel augue. Vestibulum rutrum rutrum neque. Aenean auctor gravida sem."
                 .to_string(),
-            "".to_string(),
-            1301,
+            None,
+            VideoId(1301),
             "/path/to/test1301.m3u8".to_string(),
             "My Family".to_string(),
             VideoType::Clip,
@@ -590,8 +1651,9 @@ mod test {
     async fn test_list_asset_references() {
         // Given
         let service = VideoService::default();
-        let video_id: u32 = 1404;
-        let expected: Vec<AssetReference> = vec![AssetReference::new(120, AssetType::Image)];
+        let video_id: VideoId = VideoId(1404);
+        let expected: Vec<AssetReference> =
+            vec![AssetReference::new(AssetId(120), AssetType::Image)];
 
         // When
         let result: Result<Vec<AssetReference>> = service.list_asset_references(video_id).await;
@@ -608,8 +1670,9 @@ mod test {
         // Given
         let service = VideoService::default();
         let asset_type: AssetType = AssetType::Image;
-        let video_id: u32 = 1404;
-        let expected: Vec<AssetReference> = vec![AssetReference::new(120, AssetType::Image)];
+        let video_id: VideoId = VideoId(1404);
+        let expected: Vec<AssetReference> =
+            vec![AssetReference::new(AssetId(120), AssetType::Image)];
 
         // When
         let result: Result<Vec<AssetReference>> = service
@@ -642,7 +1705,7 @@ mod test {
     async fn test_list_videos_by_container() {
         // Given
         let service = VideoService::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
 
         // When
         let result: Result<Vec<Video>> = service.list_videos_by_container(container_id).await;
@@ -674,7 +1737,7 @@ mod test {
     async fn test_list_videos_by_container_and_type() {
         // Given
         let service = VideoService::default();
-        let container_id: u32 = 0;
+        let container_id: ContainerId = ContainerId(0);
         let video_type: VideoType = VideoType::Movie;
 
         // When
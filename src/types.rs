@@ -1,8 +1,24 @@
 //! Public crate type definitions.
 
-use std::fmt::{Display, Formatter};
+use std::{
+    cmp::Ordering,
+    fmt::{Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
 
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use log::warn;
+use rocket::{
+    form::{self, FromFormField, ValueField},
+    request::FromParam,
+};
+use schemars::{
+    gen::SchemaGenerator,
+    schema::{InstanceType, Metadata, Schema, SchemaObject},
+    JsonSchema,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /* ******************************************* Types ******************************************** */
 
@@ -12,24 +28,36 @@ use serde::{Deserialize, Serialize};
 /// Those assets can be either advertisements or images, and the type of asset is tracked by the
 /// types of this enum.
 ///
+/// Types which Rocket Container doesn't know yet deserialize to [`AssetType::Unknown`] instead of
+/// failing, and serialize back to the same name. Parsing with [`FromStr`] only accepts known
+/// types.
+///
 /// # Examples
 ///
 /// ```rust
-/// use rocket_container::{repository::video::AssetReferenceDto, types::AssetType};
+/// use rocket_container::{
+///     repository::video::AssetReferenceDto,
+///     types::{AssetId, AssetType, VideoId},
+/// };
 ///
-/// let asset_reference: AssetReferenceDto = AssetReferenceDto::new(
-///     "120".to_string(),
-///     AssetType::Image,
-///     "1404".to_string(),
-/// );
+/// let asset_reference: AssetReferenceDto =
+///     AssetReferenceDto::new(AssetId(120), AssetType::Image, VideoId(1404));
 /// ```
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(rename_all = "UPPERCASE")]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum AssetType {
     /// Advertisement asset.
     Ad,
     /// Image asset.
     Image,
+    /// Asset type which Rocket Container doesn't know, such as `SUBTITLE`.
+    Unknown(String),
+}
+
+impl AssetType {
+    /// Check if Rocket Container doesn't know this asset type.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, AssetType::Unknown(_))
+    }
 }
 
 impl Display for AssetType {
@@ -37,7 +65,253 @@ impl Display for AssetType {
         match self {
             AssetType::Ad => write!(f, "AD"),
             AssetType::Image => write!(f, "IMAGE"),
+            AssetType::Unknown(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl FromStr for AssetType {
+    type Err = Error;
+
+    /// Parse a known [AssetType] from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "AD" => Ok(AssetType::Ad),
+            "IMAGE" => Ok(AssetType::Image),
+            _ => Err(Error {
+                kind: ErrorKind::Permanent,
+                message: format!("Unknown asset type {}", s),
+            }),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'v> FromFormField<'v> for AssetType {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        match field.value.parse::<AssetType>() {
+            Ok(asset_type) => Ok(asset_type),
+            Err(err) => Err(form::Error::validation(err.message).into()),
+        }
+    }
+}
+
+impl Serialize for AssetType {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AssetType {
+    /// Deserialize an [AssetType], keeping unknown names as [`AssetType::Unknown`].
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name: String = String::deserialize(deserializer)?;
+
+        Ok(name.parse().unwrap_or(AssetType::Unknown(name)))
+    }
+}
+
+impl JsonSchema for AssetType {
+    fn schema_name() -> String {
+        "AssetType".to_string()
+    }
+
+    /// Any string, since unknown asset types are kept as is.
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        name_schema("Type of asset, such as AD or IMAGE.", &["AD", "IMAGE"])
+    }
+}
+
+/// Define a newtype identifier wrapping a [u32].
+///
+/// Identifiers serialize as bare numbers, parse with [`FromStr`], and can be used as Rocket path
+/// parameters with [`FromParam`]. `$name` is used in parse error messages.
+macro_rules! id_type {
+    ($(#[$meta:meta])* $id:ident, $name:literal) => {
+        $(#[$meta])*
+        #[derive(
+            Clone,
+            Copy,
+            Debug,
+            Default,
+            Deserialize,
+            Eq,
+            Hash,
+            JsonSchema,
+            Ord,
+            PartialEq,
+            PartialOrd,
+            Serialize,
+        )]
+        #[serde(transparent)]
+        pub struct $id(pub u32);
+
+        impl Display for $id {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl FromStr for $id {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self> {
+                match s.trim().parse::<u32>() {
+                    Ok(id) => Ok($id(id)),
+                    Err(_) => Err(Error {
+                        kind: ErrorKind::Permanent,
+                        message: format!("Invalid {} ID {}", $name, s),
+                    }),
+                }
+            }
+        }
+
+        impl<'a> FromParam<'a> for $id {
+            type Error = Error;
+
+            fn from_param(param: &'a str) -> Result<Self> {
+                param.parse()
+            }
+        }
+    };
+}
+
+id_type!(
+    /// Unique advertisement identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::types::AdvertisementId;
+    ///
+    /// let id: AdvertisementId = "120".parse().unwrap();
+    ///
+    /// assert_eq!(id, AdvertisementId(120));
+    /// ```
+    AdvertisementId,
+    "advertisement"
+);
+
+id_type!(
+    /// Unique identifier of the advertisement or image an `AssetReference` links to.
+    ///
+    /// Convert it into an [AdvertisementId] or an [ImageId] depending on the [AssetType].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::types::{AssetId, ImageId};
+    ///
+    /// let id: AssetId = AssetId(120);
+    ///
+    /// assert_eq!(ImageId::from(id), ImageId(120));
+    /// ```
+    AssetId,
+    "asset"
+);
+
+id_type!(
+    /// Unique container, e.g. show or series, identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::types::ContainerId;
+    ///
+    /// let id: ContainerId = "0".parse().unwrap();
+    ///
+    /// assert_eq!(id.to_string(), "0");
+    /// ```
+    ContainerId,
+    "container"
+);
+
+id_type!(
+    /// Unique image identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::types::ImageId;
+    ///
+    /// let id: ImageId = "1404".parse().unwrap();
+    ///
+    /// assert_eq!(id, ImageId(1404));
+    /// ```
+    ImageId,
+    "image"
+);
+
+id_type!(
+    /// Unique video identifier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use rocket_container::types::VideoId;
+    ///
+    /// assert!("video-1".parse::<VideoId>().is_err());
+    /// ```
+    VideoId,
+    "video"
+);
+
+impl From<AssetId> for AdvertisementId {
+    fn from(id: AssetId) -> Self {
+        AdvertisementId(id.0)
+    }
+}
+
+impl From<AssetId> for ImageId {
+    fn from(id: AssetId) -> Self {
+        ImageId(id.0)
+    }
+}
+
+/// A list of values separated by commas, such as `MOVIE,EPISODE`.
+///
+/// Used for query parameters which accept multiple values. Each value is parsed with
+/// [`FromStr`] and empty values are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::types::{CommaSeparated, VideoType};
+///
+/// // type=MOVIE,EPISODE
+/// let types: CommaSeparated<VideoType> =
+///     CommaSeparated(vec![VideoType::Movie, VideoType::Episode]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CommaSeparated<T>(pub Vec<T>);
+
+#[rocket::async_trait]
+impl<'v, T> FromFormField<'v> for CommaSeparated<T>
+where
+    T: FromStr + Send,
+    T::Err: Display,
+{
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        let mut values: Vec<T> = Vec::new();
+
+        for value in field.value.split(',').map(str::trim) {
+            if value.is_empty() {
+                continue;
+            }
+
+            match value.parse::<T>() {
+                Ok(value) => values.push(value),
+                Err(err) => return Err(form::Error::validation(err.to_string()).into()),
+            }
         }
+
+        Ok(CommaSeparated(values))
     }
 }
 
@@ -56,7 +330,7 @@ impl Display for AssetType {
 ///                 Ok(response)
 ///             } else if response.status() == StatusCode::NOT_FOUND {
 ///                 Err(Error {
-///                     kind: ErrorKind::Permanent,
+///                     kind: ErrorKind::NotFound,
 ///                     message: "Resource not found".to_string()
 ///                 })
 ///             } else if response.status() == StatusCode::INTERNAL_SERVER_ERROR {
@@ -93,7 +367,7 @@ impl Display for Error {
     }
 }
 
-/// Type of [Error] (whether the error is retryable or not).
+/// Type of [Error] (whether the error is retryable or not, or the resource does not exist).
 ///
 /// # Examples
 ///
@@ -116,6 +390,8 @@ impl Display for Error {
 /// ```
 #[derive(Debug, Eq, PartialEq)]
 pub enum ErrorKind {
+    /// The requested resource does not exist. Not retryable.
+    NotFound,
     /// A permanent, non-retryable error.
     Permanent,
     /// A transient, retryable error.
@@ -125,21 +401,184 @@ pub enum ErrorKind {
 impl Display for ErrorKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
+            ErrorKind::NotFound => write!(f, "NotFound"),
             ErrorKind::Permanent => write!(f, "Permanent"),
             ErrorKind::Transient => write!(f, "Transient"),
         }
     }
 }
 
-/// Alias for [core::result::Result] where the error type is always [Error].
-pub type Result<T> = core::result::Result<T, Error>;
+/// Alias for [core::result::Result] where the error type defaults to [Error].
+///
+/// The error type can still be given, so derived code which names `Result<T, E>` compiles where
+/// this alias is in scope.
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+/// Order in which sorted listings are returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::cmp::Ordering;
+///
+/// use rocket_container::types::SortOrder;
+///
+/// assert_eq!(SortOrder::Desc.apply(Ordering::Less), Ordering::Greater);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, FromFormField, PartialEq)]
+pub enum SortOrder {
+    /// Ascending order.
+    #[default]
+    #[field(value = "asc")]
+    Asc,
+    /// Descending order.
+    #[field(value = "desc")]
+    Desc,
+}
+
+impl SortOrder {
+    /// Apply this sort order to an ascending [Ordering].
+    pub fn apply(&self, ordering: Ordering) -> Ordering {
+        match self {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    }
+}
+
+impl Display for SortOrder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortOrder::Asc => write!(f, "asc"),
+            SortOrder::Desc => write!(f, "desc"),
+        }
+    }
+}
+
+/// A point in time, in UTC.
+///
+/// Parsed from either an RFC 3339 timestamp, such as `2022-03-23T12:00:00Z`, or an ISO-8601 date,
+/// such as `2022-03-23`, which is midnight UTC at the start of that date. Displayed as an RFC 3339
+/// timestamp with second precision.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::types::Timestamp;
+///
+/// let timestamp: Timestamp = "2022-03-23".parse().unwrap();
+///
+/// assert_eq!(timestamp.to_string(), "2022-03-23T00:00:00Z");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Timestamp(pub DateTime<Utc>);
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    /// Parse a [Timestamp] from an RFC 3339 timestamp or an ISO-8601 date.
+    fn from_str(s: &str) -> Result<Self> {
+        let s: &str = s.trim();
+
+        if let Ok(timestamp) = DateTime::parse_from_rfc3339(s) {
+            return Ok(Timestamp(timestamp.with_timezone(&Utc)));
+        }
+
+        match NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            Ok(date) => Ok(Timestamp(date.and_hms_opt(0, 0, 0).unwrap().and_utc())),
+            Err(_) => Err(Error {
+                kind: ErrorKind::Permanent,
+                message: format!("Invalid timestamp {}", s),
+            }),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'v> FromFormField<'v> for Timestamp {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        match field.value.parse::<Timestamp>() {
+            Ok(timestamp) => Ok(timestamp),
+            Err(err) => Err(form::Error::validation(err.message).into()),
+        }
+    }
+}
+
+/// A length of time, such as `7d` or `12h 30m`.
+///
+/// Parsed and displayed in [humantime]'s format.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use rocket_container::types::HumanDuration;
+///
+/// let duration: HumanDuration = "7d".parse().unwrap();
+///
+/// assert_eq!(duration.0, Duration::from_secs(7 * 24 * 60 * 60));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct HumanDuration(pub Duration);
+
+impl Display for HumanDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", humantime::format_duration(self.0))
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = Error;
+
+    /// Parse a [HumanDuration] such as `7d` or `12h 30m`.
+    fn from_str(s: &str) -> Result<Self> {
+        match humantime::parse_duration(s.trim()) {
+            Ok(duration) => Ok(HumanDuration(duration)),
+            Err(err) => Err(Error {
+                kind: ErrorKind::Permanent,
+                message: format!("Invalid duration {}: {}", s, err),
+            }),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'v> FromFormField<'v> for HumanDuration {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        match field.value.parse::<HumanDuration>() {
+            Ok(duration) => Ok(duration),
+            Err(err) => Err(form::Error::validation(err.message).into()),
+        }
+    }
+}
 
 /// Type of `Video`
 ///
 /// Videos can be either short clips, TV length episodes, or full length movies, and the type of
 /// video is tracked by the types of this enum.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-#[serde(rename_all = "UPPERCASE")]
+///
+/// Types which Rocket Container doesn't know yet deserialize to [`VideoType::Unknown`] instead of
+/// failing, serialize back to the same name, and sort after every known type. Parsing with
+/// [`FromStr`] only accepts known types.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::types::VideoType;
+///
+/// let video_type: VideoType = serde_json::from_str(r#""TRAILER""#).unwrap();
+///
+/// assert_eq!(video_type, VideoType::Unknown("TRAILER".to_string()));
+/// assert_eq!(video_type.to_string(), "TRAILER");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum VideoType {
     /// A short clip.
     Clip,
@@ -147,6 +586,15 @@ pub enum VideoType {
     Episode,
     /// A full length movie.
     Movie,
+    /// Video type which Rocket Container doesn't know, such as `TRAILER`.
+    Unknown(String),
+}
+
+impl VideoType {
+    /// Check if Rocket Container doesn't know this video type.
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, VideoType::Unknown(_))
+    }
 }
 
 impl Display for VideoType {
@@ -155,6 +603,59 @@ impl Display for VideoType {
             VideoType::Clip => write!(f, "CLIP"),
             VideoType::Episode => write!(f, "EPISODE"),
             VideoType::Movie => write!(f, "MOVIE"),
+            VideoType::Unknown(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl Serialize for VideoType {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for VideoType {
+    /// Deserialize a [VideoType], keeping unknown names as [`VideoType::Unknown`].
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let name: String = String::deserialize(deserializer)?;
+
+        Ok(name.parse().unwrap_or(VideoType::Unknown(name)))
+    }
+}
+
+impl JsonSchema for VideoType {
+    fn schema_name() -> String {
+        "VideoType".to_string()
+    }
+
+    /// Any string, since unknown video types are kept as is.
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        name_schema(
+            "Type of video, such as CLIP, EPISODE, or MOVIE.",
+            &["CLIP", "EPISODE", "MOVIE"],
+        )
+    }
+}
+
+impl FromStr for VideoType {
+    type Err = Error;
+
+    /// Parse a known [VideoType] from its name, ignoring case.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_uppercase().as_str() {
+            "CLIP" => Ok(VideoType::Clip),
+            "EPISODE" => Ok(VideoType::Episode),
+            "MOVIE" => Ok(VideoType::Movie),
+            _ => Err(Error {
+                kind: ErrorKind::Permanent,
+                message: format!("Unknown video type {}", s),
+            }),
         }
     }
 }
@@ -182,6 +683,90 @@ where
     }
 }
 
+/// Parse an optional [Timestamp].
+///
+/// Empty strings mean there is no timestamp. Invalid timestamps are logged and treated the same
+/// as empty strings, so that one malformed value from a dependency doesn't fail a whole request.
+///
+/// # Examples
+///
+/// ```rust
+/// use rocket_container::types::parse_timestamp;
+///
+/// assert!(parse_timestamp("2022-03-23").is_some());
+/// assert!(parse_timestamp("").is_none());
+/// assert!(parse_timestamp("tomorrow").is_none());
+/// ```
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    if value.trim().is_empty() {
+        return None;
+    }
+
+    match value.parse::<Timestamp>() {
+        Ok(timestamp) => Some(timestamp.0),
+        Err(err) => {
+            warn!("Ignoring timestamp: {}", err.message);
+
+            None
+        }
+    }
+}
+
+/// Deserialize an identifier from a string, such as `"1404"`.
+///
+/// Rocket Container's dependencies send identifiers as strings. For use with
+/// `#[serde(deserialize_with = "...")]`.
+pub fn deserialize_id<'de, D, T>(deserializer: D) -> core::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = Error>,
+{
+    String::deserialize(deserializer)?
+        .parse::<T>()
+        .map_err(|err| serde::de::Error::custom(err.message))
+}
+
+/// Serialize an identifier as a string, such as `"1404"`.
+///
+/// For use with `#[serde(serialize_with = "...")]`.
+pub fn serialize_id<S, T>(id: &T, serializer: S) -> core::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Display,
+{
+    serializer.serialize_str(&id.to_string())
+}
+
+/// Serialize an optional timestamp as an RFC 3339 string, or `null`.
+///
+/// For use with `#[serde(serialize_with = "...")]`.
+pub fn serialize_timestamp<S>(
+    timestamp: &Option<DateTime<Utc>>,
+    serializer: S,
+) -> core::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match timestamp {
+        Some(timestamp) => serializer.serialize_str(&Timestamp(*timestamp).to_string()),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Deserialize an optional timestamp with [parse_timestamp].
+///
+/// For use with `#[serde(deserialize_with = "...")]`.
+pub fn deserialize_timestamp<'de, D>(
+    deserializer: D,
+) -> core::result::Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value: Option<String> = Option::deserialize(deserializer)?;
+
+    Ok(value.as_deref().and_then(parse_timestamp))
+}
+
 /// Convert an array to a [String].
 ///
 /// # Examples
@@ -207,12 +792,176 @@ where
     }
 }
 
+/// JSON schema of a type name which accepts any string, so unknown names can pass through,
+/// with the known names as examples.
+fn name_schema(description: &str, known: &[&str]) -> Schema {
+    Schema::Object(SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_string()),
+            examples: known.iter().map(|name| (*name).into()).collect(),
+            ..Metadata::default()
+        })),
+        ..SchemaObject::default()
+    })
+}
+
 /* ******************************************* Tests ******************************************** */
 
 #[cfg(test)]
 mod test {
+    use std::str::FromStr;
+
     use super::AssetType;
     use super::VideoType;
+    use super::{parse_timestamp, ContainerId, HumanDuration, Timestamp, VideoId};
+
+    #[test]
+    fn parse_video_type() {
+        // Given
+        let data: &str = "episode";
+
+        // When
+        let actual = VideoType::from_str(data);
+
+        // Then
+        match actual {
+            Ok(video_type) => assert_eq!(video_type, VideoType::Episode),
+            Err(err) => panic!("Failed to parse with error: {}", err),
+        }
+    }
+
+    #[test]
+    fn parse_video_type_unknown() {
+        // Given
+        let data: &str = "TRAILER";
+
+        // When
+        let actual = VideoType::from_str(data);
+
+        // Then
+        assert!(actual.is_err(), "Unknown video type should not parse");
+    }
+
+    #[test]
+    fn parse_timestamp_formats() {
+        // Given
+        let date: &str = "2022-03-23";
+        let timestamp: &str = "2022-03-23T02:00:00+02:00";
+
+        // When
+        let from_date = Timestamp::from_str(date);
+        let from_timestamp = Timestamp::from_str(timestamp);
+
+        // Then
+        match (from_date, from_timestamp) {
+            (Ok(from_date), Ok(from_timestamp)) => {
+                assert_eq!(from_date, from_timestamp);
+                assert_eq!(from_date.to_string(), "2022-03-23T00:00:00Z");
+            }
+            (from_date, from_timestamp) => {
+                panic!(
+                    "Failed to parse timestamps: {:?} {:?}",
+                    from_date, from_timestamp
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn parse_timestamp_tolerant() {
+        // Given
+        let values: [&str; 3] = ["", "  ", "23/03/2022"];
+
+        // When
+        let actual: Vec<bool> = values
+            .iter()
+            .map(|value| parse_timestamp(value).is_none())
+            .collect();
+
+        // Then
+        assert_eq!(actual, vec![true, true, true]);
+        assert!(Timestamp::from_str("23/03/2022").is_err());
+    }
+
+    #[test]
+    fn parse_ids() {
+        // Given
+        let values: [&str; 3] = ["0", " 1301 ", "-1"];
+
+        // When
+        let actual: Vec<Option<VideoId>> = values
+            .iter()
+            .map(|value| VideoId::from_str(value).ok())
+            .collect();
+
+        // Then
+        assert_eq!(actual, vec![Some(VideoId(0)), Some(VideoId(1301)), None]);
+    }
+
+    #[test]
+    fn serialize_id_as_number() {
+        // Given
+        let id: ContainerId = ContainerId(7);
+
+        // When
+        let result: serde_json::Result<String> = serde_json::to_string(&id);
+
+        // Then
+        match result {
+            Ok(actual) => assert_eq!(actual, "7"),
+            Err(err) => panic!("Failed to serialize with error: {}", err),
+        }
+    }
+
+    #[test]
+    fn parse_human_duration() {
+        // Given
+        let values: [&str; 3] = ["7d", "12h 30m", "week"];
+
+        // When
+        let actual: Vec<Option<u64>> = values
+            .iter()
+            .map(|value| HumanDuration::from_str(value).ok().map(|d| d.0.as_secs()))
+            .collect();
+
+        // Then
+        assert_eq!(actual, vec![Some(604_800), Some(45_000), None]);
+    }
+
+    #[test]
+    fn deserialize_unknown_types() {
+        // Given
+        let data: &str = r#"["TRAILER","SUBTITLE"]"#;
+
+        // When
+        let result: serde_json::Result<(VideoType, AssetType)> = serde_json::from_str(data);
+
+        // Then
+        match result {
+            Ok((video_type, asset_type)) => {
+                assert_eq!(video_type, VideoType::Unknown("TRAILER".to_string()));
+                assert_eq!(asset_type, AssetType::Unknown("SUBTITLE".to_string()));
+                assert_eq!(
+                    serde_json::to_string(&(video_type, asset_type)).unwrap(),
+                    data
+                );
+            }
+            Err(err) => panic!("Failed to deserialize with error: {}", err),
+        }
+    }
+
+    #[test]
+    fn parse_unknown_asset_type() {
+        // Given
+        let data: &str = "SUBTITLE";
+
+        // When
+        let actual = AssetType::from_str(data);
+
+        // Then
+        assert!(actual.is_err(), "Unknown asset type should not parse");
+    }
 
     #[test]
     fn deserialize_asset_type_ad() {